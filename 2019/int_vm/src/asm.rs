@@ -0,0 +1,321 @@
+//! Compiles Intcode assembly, the language of the `disasm` listings, to a program
+//!
+//! A line holds an instruction, `.data` values or nothing, after an optional `label:`; `;` starts
+//! a comment. Instructions are the `INSTRUCTIONS` names, their operands separated by commas and
+//! prefixed by `imm` or `rel` out of position mode. Values are numbers or labels, standing for
+//! their address. A number before the instruction, the address in a listing, is ignored.
+//!
+//! ```text
+//! start:  input n
+//! loop:   output n
+//!         add n, imm -1, n
+//!         jump-if-true n, imm loop
+//!         exit
+//! n:      .data 0
+//! ```
+
+use crate::{Mode, Opcode};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Why a source could not be assembled, at `line` (from 1)
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum AsmError {
+    UnknownMnemonic {
+        line: usize,
+        mnemonic: String,
+    },
+    OperandCount {
+        line: usize,
+        mnemonic: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// An operand with a mode other than `imm` or `rel`, or several words
+    BadOperand {
+        line: usize,
+        operand: String,
+    },
+    /// Neither a number nor a label
+    BadValue {
+        line: usize,
+        value: String,
+    },
+    /// `param` (from 1) is written to, but in immediate mode
+    WriteImmediate {
+        line: usize,
+        param: usize,
+    },
+    UndefinedLabel {
+        line: usize,
+        label: String,
+    },
+    DuplicateLabel {
+        line: usize,
+        label: String,
+    },
+}
+
+impl AsmError {
+    pub fn line(&self) -> usize {
+        match *self {
+            AsmError::UnknownMnemonic { line, .. }
+            | AsmError::OperandCount { line, .. }
+            | AsmError::BadOperand { line, .. }
+            | AsmError::BadValue { line, .. }
+            | AsmError::WriteImmediate { line, .. }
+            | AsmError::UndefinedLabel { line, .. }
+            | AsmError::DuplicateLabel { line, .. } => line,
+        }
+    }
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line())?;
+        match self {
+            AsmError::UnknownMnemonic { mnemonic, .. } => {
+                write!(f, "unknown instruction {:?}", mnemonic)
+            }
+            AsmError::OperandCount {
+                mnemonic,
+                expected,
+                found,
+                ..
+            } => write!(f, "{} takes {} operands, not {}", mnemonic, expected, found),
+            AsmError::BadOperand { operand, .. } => write!(f, "invalid operand {:?}", operand),
+            AsmError::BadValue { value, .. } => write!(f, "invalid value {:?}", value),
+            AsmError::WriteImmediate { param, .. } => {
+                write!(f, "parameter {} is written to, in immediate mode", param)
+            }
+            AsmError::UndefinedLabel { label, .. } => write!(f, "undefined label {}", label),
+            AsmError::DuplicateLabel { label, .. } => write!(f, "label {} defined twice", label),
+        }
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+/// A value of the program, known once all the labels are
+enum Value<'a> {
+    Number(i64),
+    Label(&'a str),
+}
+
+fn is_label(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn value(line: usize, word: &str) -> Result<Value<'_>, AsmError> {
+    match word.parse() {
+        Ok(number) => Ok(Value::Number(number)),
+        Err(_) if is_label(word) => Ok(Value::Label(word)),
+        Err(_) => Err(AsmError::BadValue {
+            line,
+            value: word.to_string(),
+        }),
+    }
+}
+
+fn operand(line: usize, text: &str) -> Result<(Mode, Value<'_>), AsmError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mode = match words[..] {
+        [_] => Mode::Position,
+        ["imm", _] => Mode::Immediate,
+        ["rel", _] => Mode::Relative,
+        _ => {
+            return Err(AsmError::BadOperand {
+                line,
+                operand: text.to_string(),
+            })
+        }
+    };
+    Ok((mode, value(line, words[words.len() - 1])?))
+}
+
+/// The program written in `source`
+pub fn assemble(source: &str) -> Result<Vec<i64>, AsmError> {
+    // the values with the line they are from, then the labels once all addresses are known
+    let mut values = Vec::new();
+    let mut labels = HashMap::new();
+    for (line, text) in (1..).zip(source.lines()) {
+        let mut text = text.split(';').next().unwrap_or_default().trim();
+        if let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+            if !is_label(label) {
+                return Err(AsmError::BadValue {
+                    line,
+                    value: label.to_string(),
+                });
+            }
+            if labels.insert(label, values.len() as i64).is_some() {
+                return Err(AsmError::DuplicateLabel {
+                    line,
+                    label: label.to_string(),
+                });
+            }
+            text = text[colon + 1..].trim();
+        }
+        let mut words = text.splitn(2, char::is_whitespace);
+        let mut first = words.next().unwrap_or_default();
+        let mut rest = words.next().unwrap_or_default().trim();
+        if first.parse::<usize>().is_ok() && !rest.is_empty() {
+            let mut words = rest.splitn(2, char::is_whitespace);
+            first = words.next().unwrap_or_default();
+            rest = words.next().unwrap_or_default().trim();
+        }
+        let args: Vec<&str> = if rest.is_empty() {
+            Vec::new()
+        } else {
+            rest.split(',').map(str::trim).collect()
+        };
+        if first.is_empty() {
+            continue;
+        }
+        if first == ".data" {
+            for arg in args {
+                values.push((line, value(line, arg)?));
+            }
+            continue;
+        }
+        let opcode = Opcode::from_name(first).ok_or_else(|| AsmError::UnknownMnemonic {
+            line,
+            mnemonic: first.to_string(),
+        })?;
+        if args.len() != opcode.nb_params {
+            return Err(AsmError::OperandCount {
+                line,
+                mnemonic: opcode.name,
+                expected: opcode.nb_params,
+                found: args.len(),
+            });
+        }
+        let operands = args
+            .iter()
+            .map(|arg| operand(line, arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut instruction = opcode.code;
+        let mut scale = 100;
+        for (param, (mode, _)) in operands.iter().enumerate() {
+            if *mode == Mode::Immediate && opcode.st == Some(param) {
+                return Err(AsmError::WriteImmediate {
+                    line,
+                    param: param + 1,
+                });
+            }
+            instruction += mode.digit() * scale;
+            scale *= 10;
+        }
+        values.push((line, Value::Number(instruction)));
+        values.extend(operands.into_iter().map(|(_, value)| (line, value)));
+    }
+    values
+        .into_iter()
+        .map(|(line, value)| match value {
+            Value::Number(number) => Ok(number),
+            Value::Label(label) => {
+                labels
+                    .get(label)
+                    .copied()
+                    .ok_or_else(|| AsmError::UndefinedLabel {
+                        line,
+                        label: label.to_string(),
+                    })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disasm::disassemble;
+
+    #[test]
+    fn test_assemble() {
+        let source = "
+            ; counts down from its input
+            start:  input n
+            loop:   output n
+                    add n, imm -1, n
+                    jump-if-true n, imm loop
+                    exit
+            n:      .data 0
+            unused: .data start, -7
+        ";
+        assert_eq!(
+            assemble(source),
+            Ok(vec![
+                3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, 0, 0, -7
+            ])
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let program = [3, 13, 1005, 13, 9, 104, 0, 99, 7, 4, 13, 99, 42, 0];
+        let listing = disassemble(&program).to_string();
+        assert_eq!(assemble(&listing), Ok(program.to_vec()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |source| assemble(source).unwrap_err();
+        assert_eq!(
+            error("exit\nfoo 1"),
+            AsmError::UnknownMnemonic {
+                line: 2,
+                mnemonic: "foo".to_string()
+            }
+        );
+        assert_eq!(
+            error("output 1, 2"),
+            AsmError::OperandCount {
+                line: 1,
+                mnemonic: "output",
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            error("output pos 1"),
+            AsmError::BadOperand {
+                line: 1,
+                operand: "pos 1".to_string()
+            }
+        );
+        assert_eq!(
+            error("output imm 1x"),
+            AsmError::BadValue {
+                line: 1,
+                value: "1x".to_string()
+            }
+        );
+        assert_eq!(
+            error("add 1, 2, imm 3"),
+            AsmError::WriteImmediate { line: 1, param: 3 }
+        );
+        assert_eq!(
+            error("output x"),
+            AsmError::UndefinedLabel {
+                line: 1,
+                label: "x".to_string()
+            }
+        );
+        assert_eq!(
+            error("a: exit\na: exit"),
+            AsmError::DuplicateLabel {
+                line: 2,
+                label: "a".to_string()
+            }
+        );
+        assert_eq!(
+            error("input imm 4").to_string(),
+            "line 1: parameter 1 is written to, in immediate mode"
+        );
+    }
+}
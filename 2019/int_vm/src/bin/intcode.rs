@@ -0,0 +1,63 @@
+//! Tools to study Intcode programs
+//!
+//! `intcode disasm [FILE]` prints the listing of the program in FILE, or read from stdin
+//!
+//! `intcode asm [FILE]` prints the program written in assembly in FILE, or read from stdin
+//!
+//! `intcode debug FILE` runs the program in FILE in a debugger, reading commands from stdin
+
+use int_vm::debug::Debugger;
+use int_vm::{asm, disasm, parse_program};
+use std::io::{self, Read};
+use std::process;
+
+const USAGE: &str = "usage: intcode disasm|asm [FILE]\n       intcode debug FILE";
+
+/// The text of `path`, or of stdin without one
+fn read(path: Option<&str>) -> Result<String, String> {
+    Ok(match path {
+        Some(path) => {
+            std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path, e))?
+        }
+        None => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("cannot read stdin: {}", e))?;
+            text
+        }
+    })
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args[..] {
+        ["disasm"] | ["disasm", _] => read(args.get(1).copied())
+            .and_then(|text| parse_program(&text))
+            .map(|program| disasm::disassemble(&program).to_string()),
+        ["asm"] | ["asm", _] => read(args.get(1).copied()).and_then(|text| {
+            let program = asm::assemble(&text).map_err(|e| e.to_string())?;
+            let values: Vec<_> = program.iter().map(i64::to_string).collect();
+            Ok(values.join(",") + "\n")
+        }),
+        ["debug", path] => read(Some(path))
+            .and_then(|text| parse_program(&text))
+            .and_then(|program| {
+                let stdin = io::stdin();
+                let mut debugger = Debugger::new(program);
+                debugger
+                    .repl(stdin.lock(), io::stdout())
+                    .map_err(|e| format!("cannot debug: {}", e))?;
+                Ok(String::new())
+            }),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(output) => print!("{}", output),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! An interactive debugger, running a program an instruction at a time with `Vm::run_one`
+
+use crate::disasm::decode;
+use crate::{InputMode, OutputMode, Vm, VmError, VmState};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+const HELP: &str = "\
+step [N]          run N instructions, 1 by default
+continue          run until a breakpoint, a watchpoint, an input or the end of the program
+break ADDRESS     stop before the instruction at ADDRESS
+watch ADDRESS     stop when the value at ADDRESS changes
+delete ADDRESS    remove the breakpoint and the watchpoint at ADDRESS
+input VALUE...    give input to the program
+regs              show the pc, the relative base, the registers and the input left
+dump START [END]  show the memory from START to END, 8 values by default
+quit
+Commands can be shortened to their first letter, an empty line repeats the last one.";
+
+/// Values per line of `dump`
+const DUMP_PER_LINE: usize = 8;
+
+/// Why the program stopped running
+enum Stop {
+    Breakpoint,
+    /// The addresses whose value changed, with the values before and after
+    Watchpoints(Vec<(usize, i64, i64)>),
+    Input,
+    Exit,
+    Error(VmError),
+    /// The program exited or failed before
+    Ended,
+}
+
+pub struct Debugger {
+    pub vm: Vm,
+    breakpoints: BTreeSet<usize>,
+    /// The addresses watched, with the last value seen there
+    watchpoints: BTreeMap<usize, i64>,
+    /// How many outputs of the program were shown
+    outputs_shown: usize,
+    last_command: String,
+}
+
+fn number<T: FromStr>(word: &str) -> Result<T, String> {
+    word.parse()
+        .map_err(|_| format!("invalid number {:?}", word))
+}
+
+impl Debugger {
+    pub fn new(program: Vec<i64>) -> Self {
+        Debugger {
+            vm: Vm::new(program, vec![], InputMode::VecInterupt, OutputMode::No),
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeMap::new(),
+            outputs_shown: 0,
+            last_command: String::new(),
+        }
+    }
+
+    /// Runs one instruction, and tells if the program has to stop there
+    fn step(&mut self) -> Option<Stop> {
+        if self.vm.state == VmState::Off {
+            return Some(Stop::Ended);
+        }
+        self.vm.state = VmState::On;
+        if let Err(e) = self.vm.run_one() {
+            return Some(Stop::Error(e));
+        }
+        match self.vm.state {
+            VmState::Off => return Some(Stop::Exit),
+            VmState::Halt => return Some(Stop::Input),
+            VmState::On => (),
+        }
+        let mut changes = Vec::new();
+        for (&address, seen) in self.watchpoints.iter_mut() {
+            let value = self.vm.ram.get(address);
+            if value != *seen {
+                changes.push((address, *seen, value));
+                *seen = value;
+            }
+        }
+        if !changes.is_empty() {
+            Some(Stop::Watchpoints(changes))
+        } else if self.breakpoints.contains(&self.vm.pc) {
+            Some(Stop::Breakpoint)
+        } else {
+            None
+        }
+    }
+
+    /// Runs up to `count` instructions, then tells what the program output, why it stopped and
+    /// where
+    fn run(&mut self, count: usize) -> String {
+        let stop = (0..count).find_map(|_| self.step());
+        let mut text = String::new();
+        for value in &self.vm.output[self.outputs_shown..] {
+            text += &format!("output {}\n", value);
+        }
+        self.outputs_shown = self.vm.output.len();
+        match stop {
+            Some(Stop::Breakpoint) => text += "breakpoint\n",
+            Some(Stop::Watchpoints(changes)) => {
+                for (address, old, new) in changes {
+                    text += &format!("watchpoint {}: {} -> {}\n", address, old, new);
+                }
+            }
+            Some(Stop::Input) => text += "waiting for input\n",
+            Some(Stop::Exit) => text += "the program exited\n",
+            Some(Stop::Error(e)) => text += &format!("error: {}\n", e),
+            Some(Stop::Ended) => return text + "the program has ended\n",
+            None => (),
+        }
+        text + &self.instruction()
+    }
+
+    /// The instruction at the pc, as in listings
+    fn instruction(&self) -> String {
+        let pc = self.vm.pc;
+        let window: Vec<i64> = (pc..pc + 4)
+            .map(|address| self.vm.ram.get(address))
+            .collect();
+        match decode(&window, 0) {
+            Some((opcode, operands)) => {
+                let operands: Vec<_> = operands.iter().map(|o| format!(" {}", o)).collect();
+                format!("{:>5}  {}{}\n", pc, opcode.name, operands.join(","))
+            }
+            None => format!("{:>5}  .data {}\n", pc, window[0]),
+        }
+    }
+
+    fn dump(&self, start: usize, end: usize) -> String {
+        let mut text = String::new();
+        for row in (start..end).step_by(DUMP_PER_LINE) {
+            let values: Vec<_> = (row..end.min(row.saturating_add(DUMP_PER_LINE)))
+                .map(|address| self.vm.ram.get(address).to_string())
+                .collect();
+            text += &format!("{:>5}  {}\n", row, values.join(", "));
+        }
+        text
+    }
+
+    fn execute(&mut self, words: &[&str]) -> Result<String, String> {
+        let vm = &self.vm;
+        Ok(match *words {
+            ["s"] | ["step"] => self.run(1),
+            ["s", count] | ["step", count] => self.run(number(count)?),
+            ["c"] | ["continue"] => self.run(usize::MAX),
+            ["b", address] | ["break", address] => {
+                self.breakpoints.insert(number(address)?);
+                String::new()
+            }
+            ["w", address] | ["watch", address] => {
+                let address = number(address)?;
+                self.watchpoints.insert(address, vm.ram.get(address));
+                String::new()
+            }
+            ["d", address] | ["delete", address] => {
+                let address = number(address)?;
+                self.breakpoints.remove(&address);
+                self.watchpoints.remove(&address);
+                String::new()
+            }
+            ["i", ref values @ ..] | ["input", ref values @ ..] if !values.is_empty() => {
+                let values = values.iter().map(|value| number::<i64>(value));
+                self.vm.input.extend(values.collect::<Result<Vec<_>, _>>()?);
+                String::new()
+            }
+            ["r"] | ["regs"] => {
+                let input: Vec<_> = vm.input.iter().map(i64::to_string).collect();
+                let registers: Vec<_> = vm.reg.iter().map(i64::to_string).collect();
+                format!(
+                    "pc {}, relative base {}\nregisters {}\ninput [{}]\n{}",
+                    vm.pc,
+                    vm.rel_base,
+                    registers.join(", "),
+                    input.join(", "),
+                    self.instruction()
+                )
+            }
+            ["x", start] | ["dump", start] => {
+                let start = number(start)?;
+                self.dump(start, start.saturating_add(DUMP_PER_LINE))
+            }
+            ["x", start, end] | ["dump", start, end] => self.dump(number(start)?, number(end)?),
+            ["h"] | ["help"] => format!("{}\n", HELP),
+            _ => return Err(format!("unknown command {:?}, try help", words.join(" "))),
+        })
+    }
+
+    /// Runs a command line, and tells what to show, or `None` to quit
+    pub fn command(&mut self, line: &str) -> Option<String> {
+        let line = match line.trim() {
+            "" => self.last_command.clone(),
+            line => line.to_string(),
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if let ["q"] | ["quit"] = words[..] {
+            return None;
+        }
+        let reply = match words[..] {
+            [] => String::new(),
+            _ => self
+                .execute(&words)
+                .unwrap_or_else(|error| format!("{}\n", error)),
+        };
+        self.last_command = line;
+        Some(reply)
+    }
+
+    /// Reads commands from `input` until `quit` or its end
+    pub fn repl(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        write!(output, "{}", self.instruction())?;
+        let mut lines = input.lines();
+        loop {
+            write!(output, "(intcode) ")?;
+            output.flush()?;
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            match self.command(&line) {
+                Some(reply) => write!(output, "{}", reply)?,
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    #[test]
+    fn test_debugger() {
+        let program = assemble(
+            "
+                    input n
+            loop:   output n
+                    add n, imm -1, n
+                    jump-if-true n, imm loop
+                    exit
+            n:      .data 0
+            ",
+        )
+        .unwrap();
+        let mut debugger = Debugger::new(program);
+        let mut command = |line| debugger.command(line).unwrap();
+        assert_eq!(command("step"), "waiting for input\n    0  input 12\n");
+        assert_eq!(command("input 2"), "");
+        assert_eq!(command("watch 12"), "");
+        assert_eq!(command("s"), "watchpoint 12: 0 -> 2\n    2  output 12\n");
+        assert_eq!(command("d 12"), "");
+        assert_eq!(command("break 11"), "");
+        assert_eq!(
+            command("continue"),
+            "output 2\noutput 1\nbreakpoint\n   11  exit\n"
+        );
+        assert_eq!(command("dump 10 13"), "   10  2, 99, 0\n");
+        assert_eq!(
+            command("dump 18446744073709551614"),
+            "18446744073709551614  0\n"
+        );
+        assert_eq!(
+            command("regs"),
+            "pc 11, relative base 0\nregisters 0, 2, 12, 0\ninput []\n   11  exit\n"
+        );
+        assert_eq!(command(""), command("regs"));
+        assert_eq!(command("c"), "the program exited\n   11  exit\n");
+        assert_eq!(command("c"), "the program has ended\n");
+        assert_eq!(command("step x"), "invalid number \"x\"\n");
+        assert_eq!(command("jump 3"), "unknown command \"jump 3\", try help\n");
+        assert_eq!(debugger.command("quit"), None);
+    }
+}
@@ -0,0 +1,288 @@
+//! Turns an Intcode program back into a listing, following its jumps to tell code from data
+
+use crate::{Mode, Opcode};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Values per `.data` line
+const DATA_PER_LINE: usize = 8;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Operand {
+    pub mode: Mode,
+    pub value: i64,
+    /// The value is the address of code (a jump target, a return address), shown as its label
+    pub is_address: bool,
+}
+
+impl Operand {
+    /// How the mode is written before the value
+    fn prefix(&self) -> &'static str {
+        match self.mode {
+            Mode::Position => "",
+            Mode::Immediate => "imm ",
+            Mode::Relative => "rel ",
+        }
+    }
+}
+
+impl fmt::Display for Operand {
+    /// The operand as in listings, with its value rather than a label
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.prefix(), self.value)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        opcode: Opcode,
+        operands: Vec<Operand>,
+    },
+    Data {
+        address: usize,
+        values: Vec<i64>,
+    },
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match *self {
+            Line::Instruction { address, .. } | Line::Data { address, .. } => address,
+        }
+    }
+}
+
+/// A disassembled program, its lines in address order and the labels of the code addresses
+/// it refers to
+#[derive(Debug, Clone)]
+pub struct Listing {
+    pub lines: Vec<Line>,
+    pub labels: BTreeMap<usize, String>,
+}
+
+/// The instruction at `address`, if it looks like one: a known opcode, modes for its parameters
+/// only, no write in immediate mode and every parameter inside the program
+pub fn decode(program: &[i64], address: usize) -> Option<(Opcode, Vec<Operand>)> {
+    let instruction = *program.get(address)?;
+    if instruction <= 0 {
+        return None;
+    }
+    let opcode = Opcode::from_code(instruction % 100)?;
+    let mut modes = instruction / 100;
+    let mut operands = Vec::with_capacity(opcode.nb_params);
+    for param in 0..opcode.nb_params {
+        let mode = Mode::from_digit(modes % 10)?;
+        modes /= 10;
+        if mode == Mode::Immediate && opcode.st == Some(param) {
+            return None;
+        }
+        operands.push(Operand {
+            mode,
+            value: *program.get(address + 1 + param)?,
+            is_address: false,
+        });
+    }
+    // the VM ignores the digits past the modes of the parameters, code does not have them
+    if modes != 0 {
+        return None;
+    }
+    Some((opcode, operands))
+}
+
+/// The address of the program held by an immediate operand
+fn constant_address(program: &[i64], operand: &Operand) -> Option<usize> {
+    match operand.mode {
+        Mode::Immediate if (0..program.len() as i64).contains(&operand.value) => {
+            Some(operand.value as usize)
+        }
+        _ => None,
+    }
+}
+
+/// Whether the instruction at `address` is a jump
+fn is_jump(program: &[i64], address: usize) -> bool {
+    decode(program, address)
+        .is_some_and(|(opcode, _)| opcode.name == "jump-if-true" || opcode.name == "jump-if-false")
+}
+
+/// Disassembles the code reachable from address 0, following the jumps with a constant target.
+/// Computed jumps (the returns of functions) cannot be followed: a call moves its return
+/// address to memory (`add imm <address>, imm 0, ...`) then jumps, so a constant moved right
+/// before a jump, and pointing right after it, is taken as code too. Everything else is data.
+pub fn disassemble(program: &[i64]) -> Listing {
+    let mut instructions = BTreeMap::new();
+    let mut covered = vec![false; program.len()];
+    let mut labels = BTreeMap::new();
+    let mut entries = vec![0];
+    while let Some(mut address) = entries.pop() {
+        while address < program.len() && !covered[address] {
+            let (opcode, mut operands) = match decode(program, address) {
+                Some(decoded) => decoded,
+                None => break,
+            };
+            let next = address + 1 + opcode.nb_params;
+            if covered[address..next].iter().any(|&c| c) {
+                break;
+            }
+            covered[address..next].iter_mut().for_each(|c| *c = true);
+            let mut falls_through = true;
+            match opcode.name {
+                "exit" => falls_through = false,
+                "jump-if-true" | "jump-if-false" => {
+                    let condition = &operands[0];
+                    falls_through = condition.mode != Mode::Immediate
+                        || (condition.value != 0) != (opcode.name == "jump-if-true");
+                    if let Some(target) = constant_address(program, &operands[1]) {
+                        operands[1].is_address = true;
+                        entries.push(target);
+                    }
+                }
+                "add" | "mul" => {
+                    let neutral = if opcode.name == "add" { 0 } else { 1 };
+                    for (moved, other) in [(0, 1), (1, 0)].iter().copied() {
+                        let is_move = operands[other].mode == Mode::Immediate
+                            && operands[other].value == neutral;
+                        match constant_address(program, &operands[moved]) {
+                            Some(target)
+                                if is_move && is_jump(program, next) && target == next + 3 =>
+                            {
+                                operands[moved].is_address = true;
+                                entries.push(target);
+                                break;
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                _ => (),
+            }
+            for operand in operands.iter().filter(|o| o.is_address) {
+                let target = operand.value as usize;
+                labels.insert(target, format!("l{}", target));
+            }
+            instructions.insert(address, (opcode, operands));
+            if !falls_through {
+                break;
+            }
+            address = next;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut address = 0;
+    while address < program.len() {
+        match instructions.remove(&address) {
+            Some((opcode, operands)) => {
+                lines.push(Line::Instruction {
+                    address,
+                    opcode,
+                    operands,
+                });
+                address += 1 + opcode.nb_params;
+            }
+            None => {
+                let start = address;
+                address += 1;
+                while address < program.len()
+                    && address - start < DATA_PER_LINE
+                    && !covered[address]
+                    && !labels.contains_key(&address)
+                {
+                    address += 1;
+                }
+                lines.push(Line::Data {
+                    address: start,
+                    values: program[start..address].to_vec(),
+                });
+            }
+        }
+    }
+    // a jump into the middle of an instruction has no line to label
+    labels.retain(|address, _| lines.iter().any(|line| line.address() == *address));
+    Listing { lines, labels }
+}
+
+impl fmt::Display for Listing {
+    /// A line per instruction or `.data`, after its address, and the labels on their own line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let last = self.lines.last().map_or(0, Line::address);
+        let width = last.to_string().len();
+        for line in &self.lines {
+            if let Some(label) = self.labels.get(&line.address()) {
+                writeln!(f, "{}:", label)?;
+            }
+            write!(f, "{:>width$}  ", line.address(), width = width)?;
+            match line {
+                Line::Instruction {
+                    opcode, operands, ..
+                } => {
+                    write!(f, "{}", opcode.name)?;
+                    for (i, operand) in operands.iter().enumerate() {
+                        let separator = if i == 0 { " " } else { ", " };
+                        let label = self
+                            .labels
+                            .get(&(operand.value as usize))
+                            .filter(|_| operand.is_address);
+                        match label {
+                            Some(label) => write!(f, "{}{}{}", separator, operand.prefix(), label)?,
+                            None => write!(f, "{}{}", separator, operand)?,
+                        }
+                    }
+                }
+                Line::Data { values, .. } => {
+                    let values: Vec<_> = values.iter().map(i64::to_string).collect();
+                    write!(f, ".data {}", values.join(", "))?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let (opcode, operands) = decode(&[1002, 4, -3, 4], 0).unwrap();
+        assert_eq!(
+            (opcode.name, operands[1].mode, operands[1].value),
+            ("mul", Mode::Immediate, -3)
+        );
+        // unknown opcode or mode, immediate write, extra mode
+        for program in [
+            [42, 0, 0, 0],
+            [301, 0, 0, 0],
+            [11101, 0, 0, 0],
+            [100099, 0, 0, 0],
+        ]
+        .iter()
+        {
+            assert_eq!(decode(&program[..], 0), None, "{:?}", program);
+        }
+        assert_eq!(decode(&[1, 0, 0], 0), None);
+    }
+
+    #[test]
+    fn test_disassemble() {
+        let program = [3, 13, 1005, 13, 9, 104, 0, 99, 7, 4, 13, 99, 42, 0];
+        let listing = disassemble(&program);
+        assert_eq!(
+            listing.to_string(),
+            " 0  input 13
+ 2  jump-if-true 13, imm l9
+ 5  output imm 0
+ 7  exit
+ 8  .data 7
+l9:
+ 9  output 13
+11  exit
+12  .data 42, 0
+"
+        );
+    }
+}
@@ -0,0 +1,654 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+pub mod asm;
+pub mod debug;
+pub mod disasm;
+mod memory;
+use memory::Memory;
+
+macro_rules! log {
+    ($vm:expr, $($arg:tt)*) => (if cfg! (debug_assertions) && $vm.log { println!($($arg)*) } )
+}
+
+#[derive(PartialEq)]
+pub enum OutputMode {
+    Stdout,
+    Stderr,
+    No,
+}
+
+#[derive(PartialEq)]
+pub enum InputMode {
+    Stdin,
+    VecDirect,
+    VecInterupt,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum VmState {
+    On,
+    Halt,
+    Off,
+}
+
+/// Why a program stopped before reaching `99`, at the instruction `instruction` found at `pc`.
+/// The VM is then `Off`, its `pc` on that instruction.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum VmError {
+    UnknownOpcode {
+        pc: usize,
+        instruction: i64,
+    },
+    /// `param` (from 1) has a mode other than 0, 1 or 2
+    BadMode {
+        pc: usize,
+        instruction: i64,
+        param: usize,
+        mode: i64,
+    },
+    /// `param` (from 1) is written to, but in immediate mode
+    WriteImmediate {
+        pc: usize,
+        instruction: i64,
+        param: usize,
+    },
+    NegativeAddress {
+        pc: usize,
+        instruction: i64,
+        address: i64,
+    },
+    /// An `input` instruction without input left, or at the end of stdin
+    InputExhausted {
+        pc: usize,
+        instruction: i64,
+    },
+    /// A sum, a product or a relative address out of the range of `i64`
+    Overflow {
+        pc: usize,
+        instruction: i64,
+    },
+    /// Reading stdin or writing stdout failed, in `InputMode::Stdin`
+    Io {
+        pc: usize,
+        instruction: i64,
+        kind: io::ErrorKind,
+    },
+}
+
+impl VmError {
+    pub fn pc(&self) -> usize {
+        match *self {
+            VmError::UnknownOpcode { pc, .. }
+            | VmError::BadMode { pc, .. }
+            | VmError::WriteImmediate { pc, .. }
+            | VmError::NegativeAddress { pc, .. }
+            | VmError::InputExhausted { pc, .. }
+            | VmError::Overflow { pc, .. }
+            | VmError::Io { pc, .. } => pc,
+        }
+    }
+
+    pub fn instruction(&self) -> i64 {
+        match *self {
+            VmError::UnknownOpcode { instruction, .. }
+            | VmError::BadMode { instruction, .. }
+            | VmError::WriteImmediate { instruction, .. }
+            | VmError::NegativeAddress { instruction, .. }
+            | VmError::InputExhausted { instruction, .. }
+            | VmError::Overflow { instruction, .. }
+            | VmError::Io { instruction, .. } => instruction,
+        }
+    }
+}
+
+impl fmt::Display for VmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VmError::UnknownOpcode { .. } => write!(f, "unknown opcode")?,
+            VmError::BadMode { param, mode, .. } => {
+                write!(f, "unknown mode {} of parameter {}", mode, param)?
+            }
+            VmError::WriteImmediate { param, .. } => {
+                write!(f, "parameter {} is written to, in immediate mode", param)?
+            }
+            VmError::NegativeAddress { address, .. } => write!(f, "negative address {}", address)?,
+            VmError::InputExhausted { .. } => write!(f, "no input left")?,
+            VmError::Overflow { .. } => write!(f, "integer overflow")?,
+            VmError::Io { kind, .. } => write!(f, "i/o error: {}", kind)?,
+        }
+        write!(f, " (instruction {} at {})", self.instruction(), self.pc())
+    }
+}
+
+impl std::error::Error for VmError {}
+
+pub struct Vm {
+    pc: usize,
+    /// Address and value of the instruction being run, for errors
+    inst_pc: usize,
+    inst_raw: i64,
+    rel_base: i64,
+    reg: [i64; 4],
+    ram: Memory,
+    pub state: VmState,
+    pub input: VecDeque<i64>,
+    i: InputMode,
+    o: OutputMode,
+    pub output: Vec<i64>,
+    /// Prints what runs, in debug builds, see `set_log`
+    log: bool,
+}
+
+impl Vm {
+    pub fn new(ram: Vec<i64>, input: Vec<i64>, i: InputMode, o: OutputMode) -> Self {
+        let input = VecDeque::from(input);
+        Vm {
+            pc: 0,
+            inst_pc: 0,
+            inst_raw: 0,
+            rel_base: 0,
+            reg: [0, 0, 0, 0],
+            ram: Memory::new(ram),
+            state: VmState::Halt,
+            input,
+            i,
+            o,
+            output: Vec::new(),
+            log: false,
+        }
+    }
+
+    /// Prints every instruction run to stdout, in debug builds only. Off by default, the output
+    /// of the runner goes to stdout too.
+    pub fn set_log(&mut self, log: bool) {
+        self.log = log;
+    }
+
+    /// The value at `address` of the memory, 0 if never written
+    pub fn peek(&self, address: usize) -> i64 {
+        self.ram.get(address)
+    }
+}
+
+struct Instruction {
+    exec: &'static dyn Fn(&mut Vm) -> Result<(), VmError>,
+    nb_params: u8,
+    st: u8,
+    name: &'static str,
+}
+impl Instruction {
+    pub const fn new(
+        exec: &'static dyn Fn(&mut Vm) -> Result<(), VmError>,
+        nb_params: u8,
+        st: u8,
+        name: &'static str,
+    ) -> Instruction {
+        Instruction {
+            exec,
+            nb_params,
+            st,
+            name,
+        }
+    }
+}
+#[rustfmt::skip]
+const INSTRUCTIONS: [Instruction; 10] = [
+    Instruction::new(&Vm::exit,        0, 99, "exit"),
+    Instruction::new(&Vm::add,         3,  2, "add"),
+    Instruction::new(&Vm::mul,         3,  2, "mul"),
+    Instruction::new(&Vm::inp,         1,  0, "input"),
+    Instruction::new(&Vm::out,         1, 99, "output"),
+    Instruction::new(&Vm::jmp_true,    2, 99, "jump-if-true"),
+    Instruction::new(&Vm::jmp_false,   2, 99, "jump-if-false"),
+    Instruction::new(&Vm::cmp_le,      3,  2, "less-than"),
+    Instruction::new(&Vm::cmp_eq,      3,  2, "equals"),
+    Instruction::new(&Vm::st_rel_base, 1, 99, "set-rel-base"),
+];
+
+/// An opcode as the tools reading or writing programs see it, from `INSTRUCTIONS`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Opcode {
+    pub code: i64,
+    pub name: &'static str,
+    pub nb_params: usize,
+    /// The parameter written to, if any
+    pub st: Option<usize>,
+}
+
+impl Opcode {
+    fn at(index: usize) -> Self {
+        let inst = &INSTRUCTIONS[index];
+        Opcode {
+            code: if index == 0 { 99 } else { index as i64 },
+            name: inst.name,
+            nb_params: inst.nb_params as usize,
+            st: Some(inst.st as usize).filter(|&st| st < inst.nb_params as usize),
+        }
+    }
+
+    /// The opcode of an instruction, its last two digits
+    pub fn from_code(code: i64) -> Option<Self> {
+        match code {
+            99 => Some(Self::at(0)),
+            1..=9 => Some(Self::at(code as usize)),
+            _ => None,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..INSTRUCTIONS.len())
+            .map(Self::at)
+            .find(|opcode| opcode.name == name)
+    }
+}
+
+/// How a parameter is read: as the address of the value, the value itself, or an address
+/// relative to the relative base
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl Mode {
+    pub fn from_digit(digit: i64) -> Option<Self> {
+        match digit {
+            0 => Some(Mode::Position),
+            1 => Some(Mode::Immediate),
+            2 => Some(Mode::Relative),
+            _ => None,
+        }
+    }
+
+    pub fn digit(self) -> i64 {
+        match self {
+            Mode::Position => 0,
+            Mode::Immediate => 1,
+            Mode::Relative => 2,
+        }
+    }
+}
+
+/// A program as written in the puzzle inputs, comma separated values
+pub fn parse_program(text: &str) -> Result<Vec<i64>, String> {
+    text.trim()
+        .split(',')
+        .enumerate()
+        .map(|(i, value)| {
+            let value = value.trim();
+            value
+                .parse()
+                .map_err(|_| format!("invalid value {:?} at position {}", value, i))
+        })
+        .collect()
+}
+
+// Memory
+impl Vm {
+    /// The index in memory of `address`
+    fn address(&self, address: i64) -> Result<usize, VmError> {
+        usize::try_from(address).map_err(|_| VmError::NegativeAddress {
+            pc: self.inst_pc,
+            instruction: self.inst_raw,
+            address,
+        })
+    }
+    /// The address `offset` away from the relative base
+    fn relative(&self, offset: i64) -> Result<i64, VmError> {
+        self.rel_base
+            .checked_add(offset)
+            .ok_or_else(|| self.overflow())
+    }
+    fn overflow(&self) -> VmError {
+        VmError::Overflow {
+            pc: self.inst_pc,
+            instruction: self.inst_raw,
+        }
+    }
+    fn load(&self, address: i64) -> Result<i64, VmError> {
+        Ok(self.ram.get(self.address(address)?))
+    }
+    fn store(&mut self, address: i64, value: i64) -> Result<(), VmError> {
+        let address = self.address(address)?;
+        self.ram.set(address, value);
+        Ok(())
+    }
+}
+
+// Instructions
+impl Vm {
+    pub fn exit(&mut self) -> Result<(), VmError> {
+        self.pc -= 1;
+        log!(self, "{:?}", self.ram);
+        self.state = VmState::Off;
+        Ok(())
+    }
+    pub fn add(&mut self) -> Result<(), VmError> {
+        let sum = self.reg[0].checked_add(self.reg[1]);
+        self.store(self.reg[2], sum.ok_or_else(|| self.overflow())?)
+    }
+    pub fn mul(&mut self) -> Result<(), VmError> {
+        let product = self.reg[0].checked_mul(self.reg[1]);
+        self.store(self.reg[2], product.ok_or_else(|| self.overflow())?)
+    }
+    pub fn inp(&mut self) -> Result<(), VmError> {
+        let exhausted = VmError::InputExhausted {
+            pc: self.inst_pc,
+            instruction: self.inst_raw,
+        };
+        let io_error = |e: io::Error| VmError::Io {
+            pc: self.inst_pc,
+            instruction: self.inst_raw,
+            kind: e.kind(),
+        };
+        let input = if self.i == InputMode::Stdin {
+            loop {
+                print!("Input a number: ");
+                io::stdout().flush().map_err(io_error)?;
+                match io::stdin().lock().lines().next() {
+                    Some(Ok(line)) => match line.trim().parse() {
+                        Ok(input) => break input,
+                        Err(_) => println!("{:?} is not a number", line),
+                    },
+                    Some(Err(e)) => return Err(io_error(e)),
+                    None => return Err(exhausted),
+                }
+            }
+        } else {
+            match self.input.pop_front() {
+                Some(i) => i,
+                None if self.i == InputMode::VecInterupt => {
+                    log!(self, "No input, halting");
+                    self.pc -= 2;
+                    self.state = VmState::Halt;
+                    return Ok(());
+                }
+                None => return Err(exhausted),
+            }
+        };
+        self.store(self.reg[0], input)
+    }
+    pub fn out(&mut self) -> Result<(), VmError> {
+        self.output.push(self.reg[0]);
+        match self.o {
+            OutputMode::Stderr => eprintln!("{}", self.reg[0]),
+            OutputMode::Stdout => println!("{}", self.reg[0]),
+            _ => (),
+        };
+        Ok(())
+    }
+    pub fn jmp_true(&mut self) -> Result<(), VmError> {
+        if self.reg[0] != 0 {
+            self.pc = self.address(self.reg[1])?;
+        }
+        Ok(())
+    }
+    pub fn jmp_false(&mut self) -> Result<(), VmError> {
+        if self.reg[0] == 0 {
+            self.pc = self.address(self.reg[1])?;
+        }
+        Ok(())
+    }
+    pub fn cmp_le(&mut self) -> Result<(), VmError> {
+        self.store(self.reg[2], (self.reg[0] < self.reg[1]).into())
+    }
+    pub fn cmp_eq(&mut self) -> Result<(), VmError> {
+        self.store(self.reg[2], (self.reg[0] == self.reg[1]).into())
+    }
+    pub fn st_rel_base(&mut self) -> Result<(), VmError> {
+        self.rel_base = self.relative(self.reg[0])?;
+        Ok(())
+    }
+}
+
+impl Vm {
+    /// Runs until the program halts (`99`), or waits for input in `VecInterupt` mode
+    pub fn run(&mut self) -> Result<&Vec<i64>, VmError> {
+        log!(self, "RUN");
+        self.state = VmState::On;
+        while self.state == VmState::On {
+            self.run_one()?;
+        }
+        log!(self, "STOP: {:?}", self.state);
+        Ok(&self.output)
+    }
+    /// Runs the instruction at `pc`. On errors, the VM is turned `Off` on that instruction.
+    pub fn run_one(&mut self) -> Result<(), VmError> {
+        let result = self.step();
+        if result.is_err() {
+            self.pc = self.inst_pc;
+            self.state = VmState::Off;
+        }
+        result
+    }
+    #[rustfmt::skip]
+    fn step(&mut self) -> Result<(), VmError> {
+        self.inst_pc = self.pc;
+        self.inst_raw = self.ram.get(self.pc);
+        let (pc, instruction) = (self.inst_pc, self.inst_raw);
+        let inst = match instruction % 100 {
+            99 => &INSTRUCTIONS[0],
+            opcode @ 1..=9 if instruction > 0 => &INSTRUCTIONS[opcode as usize],
+            _ => return Err(VmError::UnknownOpcode { pc, instruction }),
+        };
+        const FETCH_MODES: [i64; 5] = [100, 1000, 10_000, 100_000, 1_000_000];
+        for i in 0..inst.nb_params as usize {
+            let mode = instruction % FETCH_MODES[i + 1] / FETCH_MODES[i];
+            let arg = self.ram.get(self.pc + 1 + i);
+            let param = i + 1;
+            self.reg[i] = match (mode, i == inst.st.into()) {
+                (2, false) => self.load(self.relative(arg)?)?, // relative (ld)
+                (2, true) => self.relative(arg)?,              // relative (st)
+                (1, false) => arg,                             // immediate (ld)
+                (1, true) => return Err(VmError::WriteImmediate { pc, instruction, param }),
+                (0, false) => self.load(arg)?,                 // position (ld)
+                (0, true) => arg,                              // position (st)
+                _ => return Err(VmError::BadMode { pc, instruction, param, mode }),
+            };
+        }
+        log!(self, "{:10} reg: {:?}", inst.name, &self.reg[..inst.nb_params as usize]);
+        self.pc += 1 + inst.nb_params as usize;
+        (inst.exec)(self)?;
+        log!(self, "-> pc move to {}", self.pc);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY9: [i64; 973] = [1102,34463338,34463338,63,1007,63,34463338,63,1005,63,53,1101,3,0,1000,109,988,209,12,9,1000,209,6,209,3,203,0,1008,1000,1,63,1005,63,65,1008,1000,2,63,1005,63,904,1008,1000,0,63,1005,63,58,4,25,104,0,99,4,0,104,0,99,4,17,104,0,99,0,0,1102,1,344,1023,1101,0,0,1020,1101,0,481,1024,1102,1,1,1021,1101,0,24,1005,1101,0,29,1018,1102,39,1,1019,1102,313,1,1028,1102,1,35,1009,1101,28,0,1001,1101,26,0,1013,1101,0,351,1022,1101,564,0,1027,1102,1,32,1011,1101,23,0,1006,1102,1,25,1015,1101,21,0,1003,1101,0,31,1014,1101,33,0,1004,1102,37,1,1000,1102,476,1,1025,1101,22,0,1007,1102,30,1,1012,1102,1,27,1017,1102,1,34,1002,1101,38,0,1008,1102,1,36,1010,1102,1,20,1016,1102,567,1,1026,1102,1,304,1029,109,-6,2108,35,8,63,1005,63,201,1001,64,1,64,1106,0,203,4,187,1002,64,2,64,109,28,21101,40,0,-9,1008,1013,38,63,1005,63,227,1001,64,1,64,1105,1,229,4,209,1002,64,2,64,109,-2,1205,1,243,4,235,1105,1,247,1001,64,1,64,1002,64,2,64,109,-12,2102,1,-5,63,1008,63,24,63,1005,63,271,1001,64,1,64,1105,1,273,4,253,1002,64,2,64,109,8,2108,22,-9,63,1005,63,295,4,279,1001,64,1,64,1106,0,295,1002,64,2,64,109,17,2106,0,-5,4,301,1001,64,1,64,1106,0,313,1002,64,2,64,109,-21,21107,41,40,7,1005,1019,333,1001,64,1,64,1105,1,335,4,319,1002,64,2,64,109,1,2105,1,10,1001,64,1,64,1105,1,353,4,341,1002,64,2,64,109,10,1206,-3,371,4,359,1001,64,1,64,1105,1,371,1002,64,2,64,109,-5,21108,42,42,-7,1005,1011,393,4,377,1001,64,1,64,1105,1,393,1002,64,2,64,109,-8,2101,0,-4,63,1008,63,23,63,1005,63,415,4,399,1105,1,419,1001,64,1,64,1002,64,2,64,109,13,21102,43,1,-6,1008,1017,43,63,1005,63,441,4,425,1106,0,445,1001,64,1,64,1002,64,2,64,109,-21,1207,0,33,63,1005,63,465,1001,64,1,64,1106,0,467,4,451,1002,64,2,64,109,19,2105,1,3,4,473,1106,0,485,1001,64,1,64,1002,64,2,64,109,1,21101,44,0,-7,1008,1015,44,63,1005,63,511,4,491,1001,64,1,64,1106,0,511,1002,64,2,64,109,2,1206,-3,527,1001,64,1,64,1105,1,529,4,517,1002,64,2,64,109,-8,1201,-7,0,63,1008,63,35,63,1005,63,555,4,535,1001,64,1,64,1105,1,555,1002,64,2,64,109,1,2106,0,10,1105,1,573,4,561,1001,64,1,64,1002,64,2,64,109,4,21107,45,46,-7,1005,1014,591,4,579,1106,0,595,1001,64,1,64,1002,64,2,64,109,-12,1208,-6,21,63,1005,63,617,4,601,1001,64,1,64,1105,1,617,1002,64,2,64,109,-11,1208,6,31,63,1005,63,637,1001,64,1,64,1106,0,639,4,623,1002,64,2,64,109,16,2101,0,-7,63,1008,63,20,63,1005,63,659,1105,1,665,4,645,1001,64,1,64,1002,64,2,64,109,3,2102,1,-9,63,1008,63,38,63,1005,63,691,4,671,1001,64,1,64,1106,0,691,1002,64,2,64,109,4,1205,-1,703,1105,1,709,4,697,1001,64,1,64,1002,64,2,64,109,-14,21108,46,45,7,1005,1014,729,1001,64,1,64,1105,1,731,4,715,1002,64,2,64,109,7,21102,47,1,0,1008,1014,45,63,1005,63,755,1001,64,1,64,1106,0,757,4,737,1002,64,2,64,109,-12,2107,34,7,63,1005,63,775,4,763,1105,1,779,1001,64,1,64,1002,64,2,64,109,-5,1207,6,22,63,1005,63,797,4,785,1106,0,801,1001,64,1,64,1002,64,2,64,109,12,1202,0,1,63,1008,63,35,63,1005,63,827,4,807,1001,64,1,64,1105,1,827,1002,64,2,64,109,-5,1202,0,1,63,1008,63,36,63,1005,63,851,1001,64,1,64,1105,1,853,4,833,1002,64,2,64,109,-2,1201,4,0,63,1008,63,20,63,1005,63,873,1105,1,879,4,859,1001,64,1,64,1002,64,2,64,109,2,2107,22,-1,63,1005,63,899,1001,64,1,64,1106,0,901,4,885,4,64,99,21102,1,27,1,21101,0,915,0,1105,1,922,21201,1,53897,1,204,1,99,109,3,1207,-2,3,63,1005,63,964,21201,-2,-1,1,21101,0,942,0,1106,0,922,21202,1,1,-1,21201,-2,-3,1,21101,0,957,0,1105,1,922,22201,1,-1,-2,1105,1,968,22102,1,-2,-2,109,-3,2105,1,0];
+
+    #[test]
+    fn test_day09_p1() {
+        let t: &[i64] = &DAY9;
+        let mut vm = Vm::new(Vec::from(t), vec![1], InputMode::VecDirect, OutputMode::No);
+        vm.run().unwrap();
+        assert_eq!(vm.output.len(), 1);
+        assert_eq!(vm.output[0], 4080871669);
+    }
+
+    #[test]
+    fn test_day09_p2() {
+        let t: &[i64] = &DAY9;
+        let mut vm = Vm::new(Vec::from(t), vec![2], InputMode::VecDirect, OutputMode::No);
+        vm.run().unwrap();
+        assert_eq!(vm.output.len(), 1);
+        assert_eq!(vm.output[0], 75202);
+    }
+
+    fn run(code: &[i64], input: Vec<i64>) -> Result<Vec<i64>, VmError> {
+        let mut vm = Vm::new(code.to_vec(), input, InputMode::VecDirect, OutputMode::No);
+        vm.run().cloned()
+    }
+
+    #[test]
+    fn test_memory() {
+        // writes far away and reads back, untouched memory reads as 0
+        let far = 1_000_000_000_000;
+        let code = [
+            1101, 7, 0, far, 4, far, 4, 10_000, 1101, 8, 0, 10_000, 4, 10_000, 99,
+        ];
+        assert_eq!(run(&code, vec![]), Ok(vec![7, 0, 8]));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(run(&[3, 5, 4, 5, 99, 0], vec![7]), Ok(vec![7]));
+        assert_eq!(
+            run(&[1101, 1, 1, 5, 42], vec![]),
+            Err(VmError::UnknownOpcode {
+                pc: 4,
+                instruction: 42
+            })
+        );
+        assert_eq!(
+            run(&[-1], vec![]),
+            Err(VmError::UnknownOpcode {
+                pc: 0,
+                instruction: -1
+            })
+        );
+        assert_eq!(
+            run(&[3001, 0, 0, 0, 99], vec![]),
+            Err(VmError::BadMode {
+                pc: 0,
+                instruction: 3001,
+                param: 2,
+                mode: 3
+            })
+        );
+        assert_eq!(
+            run(&[11101, 0, 0, 0, 99], vec![]),
+            Err(VmError::WriteImmediate {
+                pc: 0,
+                instruction: 11101,
+                param: 3
+            })
+        );
+        assert_eq!(
+            run(&[1, -3, 0, 0, 99], vec![]),
+            Err(VmError::NegativeAddress {
+                pc: 0,
+                instruction: 1,
+                address: -3
+            })
+        );
+        assert_eq!(
+            run(&[1105, 1, -1], vec![]),
+            Err(VmError::NegativeAddress {
+                pc: 0,
+                instruction: 1105,
+                address: -1
+            })
+        );
+        let exhausted = run(&[3, 0, 3, 0, 99], vec![1]).unwrap_err();
+        assert_eq!((exhausted.pc(), exhausted.instruction()), (2, 3));
+        assert_eq!(
+            exhausted,
+            VmError::InputExhausted {
+                pc: 2,
+                instruction: 3
+            }
+        );
+        assert_eq!(exhausted.to_string(), "no input left (instruction 3 at 2)");
+
+        let max = i64::MAX;
+        for (code, pc, instruction) in [
+            (vec![1101, max, 1, 0, 99], 0, 1101),
+            (vec![1102, max, 2, 0, 99], 0, 1102),
+            (vec![109, max, 109, 1, 99], 2, 109),
+            (vec![109, max, 204, 1, 99], 2, 204),
+        ] {
+            assert_eq!(
+                run(&code, vec![]),
+                Err(VmError::Overflow { pc, instruction })
+            );
+        }
+
+        // the VM stops on the faulty instruction
+        let mut vm = Vm::new(
+            vec![104, 1, 42],
+            vec![],
+            InputMode::VecDirect,
+            OutputMode::No,
+        );
+        assert!(vm.run().is_err());
+        assert_eq!((vm.pc, &vm.state, &vm.output), (2, &VmState::Off, &vec![1]));
+    }
+
+    #[test]
+    fn test_disassemble_day09() {
+        let listing = disasm::disassemble(&DAY9);
+        // the function at 922 and the return addresses of its calls
+        for address in [922, 915, 942, 957].iter() {
+            assert!(listing.labels.contains_key(address), "{}", address);
+        }
+        // an argument of the call
+        assert!(!listing.labels.contains_key(&27));
+        let at = |address| {
+            listing
+                .lines
+                .iter()
+                .find(|line| line.address() == address)
+                .unwrap()
+        };
+        match at(915) {
+            disasm::Line::Instruction { opcode, .. } => assert_eq!(opcode.name, "add"),
+            line => panic!("{:?}", line),
+        }
+        let text = listing.to_string();
+        assert!(text.contains("l915:\n915  add rel 1, imm 53897, rel 1\n"));
+        assert!(text.contains("912  jump-if-true imm 1, imm l922\n"));
+        assert!(text.contains("961  jump-if-true imm 1, imm l968\n"));
+        // the lines cover the program once, in order
+        let mut next = 0;
+        for line in &listing.lines {
+            assert_eq!(line.address(), next);
+            next += match line {
+                disasm::Line::Instruction { opcode, .. } => 1 + opcode.nb_params,
+                disasm::Line::Data { values, .. } => values.len(),
+            };
+        }
+        assert_eq!(next, DAY9.len());
+    }
+
+    #[test]
+    fn test_assemble_day09() {
+        let listing = disasm::disassemble(&DAY9).to_string();
+        assert_eq!(asm::assemble(&listing), Ok(DAY9.to_vec()));
+    }
+
+    #[test]
+    fn test_call() {
+        let code = asm::assemble(
+            "
+                    set-rel-base imm stack
+                    input rel 1                     ; the argument
+                    add imm back, imm 0, rel 0      ; the return address
+                    jump-if-true imm 1, imm double
+            back:   output rel 1
+                    exit
+            double: add rel 1, rel 1, rel 1
+                    jump-if-true imm 1, rel 0
+            stack:  .data 0, 0
+            ",
+        )
+        .unwrap();
+        assert_eq!(run(&code, vec![21]), Ok(vec![42]));
+    }
+
+    #[test]
+    fn test_parse_program() {
+        assert_eq!(parse_program("1,-2, 3\n"), Ok(vec![1, -2, 3]));
+        assert_eq!(
+            parse_program("1,x,3"),
+            Err("invalid value \"x\" at position 1".to_string())
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+/// Writes further than this past the end of the cells go to the sparse map
+const FAR: usize = 1 << 16;
+
+/// The memory of a program, where every address holds 0 until written. The cells from address 0
+/// grow on demand; writes far past them are kept in a map instead, so that a program writing
+/// at address 10^12 does not allocate terabytes.
+#[derive(Debug, Clone, Default)]
+pub struct Memory {
+    cells: Vec<i64>,
+    far: BTreeMap<usize, i64>,
+}
+
+impl Memory {
+    pub fn new(program: Vec<i64>) -> Self {
+        Memory {
+            cells: program,
+            far: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, address: usize) -> i64 {
+        match self.cells.get(address) {
+            Some(&value) => value,
+            None => self.get_far(address),
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, address: usize, value: i64) {
+        match self.cells.get_mut(address) {
+            Some(cell) => *cell = value,
+            None => self.set_far(address, value),
+        }
+    }
+
+    // out of the way of the accesses to the cells, by far the most common
+    #[cold]
+    #[inline(never)]
+    fn get_far(&self, address: usize) -> i64 {
+        self.far.get(&address).copied().unwrap_or(0)
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn set_far(&mut self, address: usize, value: i64) {
+        if address - self.cells.len() >= FAR {
+            self.far.insert(address, value);
+            return;
+        }
+        self.cells.resize(address + 1, 0);
+        self.cells[address] = value;
+        // far cells now in range move to the vector
+        let cells = &mut self.cells;
+        self.far
+            .retain(|&address, &mut value| match cells.get_mut(address) {
+                Some(cell) => {
+                    *cell = value;
+                    false
+                }
+                None => true,
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory() {
+        let mut memory = Memory::new(vec![1, 2, 3]);
+        assert_eq!(
+            (memory.get(2), memory.get(3), memory.get(1 << 40)),
+            (3, 0, 0)
+        );
+        memory.set(10, 7);
+        assert_eq!((memory.get(9), memory.get(10)), (0, 7));
+        assert_eq!(memory.cells.len(), 11);
+
+        memory.set(1 << 40, -1);
+        memory.set(FAR + 20, 5);
+        assert_eq!(memory.far.len(), 2);
+        assert_eq!((memory.get(1 << 40), memory.get(FAR + 20)), (-1, 5));
+        // growing the cells over a far address keeps its value
+        memory.set(FAR, 4);
+        memory.set(FAR + 30, 6);
+        assert_eq!(memory.far.len(), 1);
+        assert_eq!((memory.get(FAR), memory.get(FAR + 20)), (4, 5));
+        assert_eq!((memory.get(FAR + 30), memory.get(1 << 40)), (6, -1));
+    }
+}
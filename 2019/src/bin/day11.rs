@@ -1,5 +1,3 @@
-#![feature(const_fn)]
-
 extern crate int_vm;
 
 use int_vm::{InputMode, OutputMode, Vm, VmState};
@@ -38,7 +36,7 @@ fn main() -> Result<(), std::io::Error> {
 
 		let curr_pan = &mut pannels[robot.pos.1][robot.pos.0];
 		vm.input.push_back(match *curr_pan {b'#' => 1, _ => 0});
-		let out = vm.run();
+		let out = vm.run().unwrap();
 		*curr_pan = match out[0] {1 => b'#', _ => b'.'};
 
 		robot.dir = match out[1] {
@@ -74,7 +72,7 @@ fn main() -> Result<(), std::io::Error> {
 	while vm.state != VmState::Off {
 		let curr_pan = &mut pannels[robot.pos.1][robot.pos.0];
 		vm.input.push_back(match *curr_pan {b'#' => 1, _ => 0});
-		let out = vm.run();
+		let out = vm.run().unwrap();
 		*curr_pan = match out[0] {1 => b'#', _ => b'.'};
 
 		robot.dir = match out[1] {
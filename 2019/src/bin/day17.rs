@@ -1,8 +1,6 @@
-#![feature(const_fn)]
-
 extern crate int_vm;
 
-use int_vm::{InputMode, OutputMode, Vm, VmState};
+use int_vm::{InputMode, OutputMode, Vm};
 use std::io::{self, BufRead};
 
 // #[derive(Clone, Copy, Debug, PartialEq)]
@@ -18,8 +16,6 @@ use std::io::{self, BufRead};
 // 	dir: Dir,
 // }
 
-const PANELS: usize = 180;
-
 fn main() -> Result<(), std::io::Error> {
     let stdin = io::stdin();
 	println!("Please input the incode program, followed by \\n");
@@ -35,7 +31,7 @@ fn main() -> Result<(), std::io::Error> {
 	// let mut pannels = vec![vec![b' '; PANELS]; PANELS];
 	// let mut robot = Robot {pos: (PANELS/2, PANELS/2), dir: Dir::Up};
 	let mut vm = Vm::new(code.clone(), vec![], InputMode::VecDirect, OutputMode::No);
-	vm.run();
+	vm.run().unwrap();
 	let map = vm.output;
 	let s: String = map.iter().map(|&x| x as u8 as char).collect();
 	println!("{}", s);
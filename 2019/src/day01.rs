@@ -0,0 +1,71 @@
+use aoc_common::{parse, Example, ParseError, Solution};
+
+fn fuel(mass: u64) -> u64 {
+    mass / 3 - 2
+}
+
+/// The fuel of a module, with the fuel of its fuel
+fn total_fuel(mut mass: u64) -> u64 {
+    let mut total_fuel = 0;
+    while mass > 6 {
+        mass = fuel(mass);
+        total_fuel += mass;
+    }
+    total_fuel
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<u64>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "14\n",
+            part1: Some("2"),
+            part2: Some("2"),
+        },
+        Example {
+            input: "1969\n",
+            part1: Some("654"),
+            part2: Some("966"),
+        },
+        Example {
+            input: "100756\n",
+            part1: Some("33583"),
+            part2: Some("50346"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| match parse::token(input, l)? {
+                // the fuel would be negative
+                mass if mass < 6 => Err(ParseError::at(input, l, "a module weighs at least 6")),
+                mass => Ok(mass),
+            })
+            .collect()
+    }
+
+    fn part1(masses: &Self::Parsed<'_>) -> String {
+        masses.iter().map(|&m| fuel(m)).sum::<u64>().to_string()
+    }
+
+    fn part2(masses: &Self::Parsed<'_>) -> String {
+        masses.iter().map(|&m| total_fuel(m)).sum::<u64>().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuel() {
+        assert_eq!((fuel(14), total_fuel(14)), (2, 2));
+        assert_eq!((fuel(1969), total_fuel(1969)), (654, 966));
+        assert_eq!((fuel(100756), total_fuel(100756)), (33583, 50346));
+    }
+}
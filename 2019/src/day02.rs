@@ -0,0 +1,58 @@
+use aoc_common::{ParseError, Solution};
+use int_vm::{InputMode, OutputMode, Vm, VmError};
+
+/// The value the program leaves at address 0, run with `noun` and `verb`
+fn run_with(code: &[i64], noun: i64, verb: i64) -> Result<i64, VmError> {
+    let mut code = code.to_vec();
+    code[1] = noun;
+    code[2] = verb;
+    let mut vm = Vm::new(code, vec![], InputMode::VecDirect, OutputMode::No);
+    vm.run()?;
+    Ok(vm.peek(0))
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let code = crate::parse_intcode(input)?;
+        match code.len() {
+            // room for the noun and the verb
+            0..=2 => Err(ParseError::at(input, "", "the program is too short")),
+            _ => Ok(code),
+        }
+    }
+
+    fn part1(code: &Self::Parsed<'_>) -> String {
+        match run_with(code, 12, 2) {
+            Ok(value) => value.to_string(),
+            Err(e) => format!("the program failed: {e}"),
+        }
+    }
+
+    fn part2(code: &Self::Parsed<'_>) -> String {
+        (0..100)
+            .flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
+            .find(|&(noun, verb)| run_with(code, noun, verb) == Ok(19_690_720))
+            .map_or("no noun and verb give 19690720".to_string(), |(noun, verb)| {
+                (100 * noun + verb).to_string()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_with() {
+        let code = [1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        assert_eq!(run_with(&code, 9, 10), Ok(3500));
+        // truncated, and out of range operands
+        assert!(run_with(&[1, 0, 0, 3, 1], 0, 0).is_err());
+        assert_eq!(run_with(&[1, 0, 0, 1000, 99], 0, 0), Ok(1));
+        assert!(run_with(&[1, -5, 0, 0, 99], -5, 0).is_err());
+    }
+}
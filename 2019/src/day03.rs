@@ -0,0 +1,98 @@
+use aoc_common::{parse, Example, ParseError, Solution};
+use std::collections::HashMap;
+
+/// Every point the wire goes through, with the number of steps needed to first reach it. `path`
+/// is a line of `input`.
+fn trace_wire(input: &str, path: &str) -> Result<Wire, ParseError> {
+    let mut points = HashMap::new();
+    let (mut x, mut y) = (0, 0);
+    let mut steps = 0;
+
+    for p in path.trim().split(',') {
+        let (dx, dy) = match p.as_bytes().first() {
+            Some(b'U') => (0, 1),
+            Some(b'D') => (0, -1),
+            Some(b'L') => (-1, 0),
+            Some(b'R') => (1, 0),
+            _ => return Err(ParseError::at(input, p, "expected U, D, L or R")),
+        };
+        let dist: usize = parse::token(input, &p[1..])?;
+        for _ in 0..dist {
+            x += dx;
+            y += dy;
+            steps += 1;
+            points.entry((x, y)).or_insert(steps);
+        }
+    }
+    Ok(points)
+}
+
+type Wire = HashMap<(i32, i32), usize>;
+
+/// Every crossing of the two wires, with the combined number of steps needed to reach it
+fn intersections(wires: &[Wire; 2]) -> impl Iterator<Item = (&(i32, i32), usize)> {
+    wires[0]
+        .iter()
+        .filter_map(move |(pos, steps0)| wires[1].get(pos).map(|steps1| (pos, steps0 + steps1)))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = [Wire; 2];
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "R8,U5,L5,D3\nU7,R6,D4,L4\n",
+            part1: Some("6"),
+            part2: Some("30"),
+        },
+        Example {
+            input: "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n",
+            part1: Some("159"),
+            part2: Some("610"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let mut wires = input.lines().map(|l| trace_wire(input, l));
+        let missing = || ParseError::at(input, "", "expected two wires");
+        Ok([
+            wires.next().ok_or_else(missing)??,
+            wires.next().ok_or_else(missing)??,
+        ])
+    }
+
+    fn part1(wires: &Self::Parsed<'_>) -> String {
+        intersections(wires)
+            .map(|(pos, _)| pos.0.abs() + pos.1.abs())
+            .min()
+            .unwrap()
+            .to_string()
+    }
+
+    fn part2(wires: &Self::Parsed<'_>) -> String {
+        intersections(wires)
+            .map(|(_, steps)| steps)
+            .min()
+            .unwrap()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(
+            Day03::solve("R8,U5,L5,D3\nU7,R6,D4,L4\n"),
+            Ok(("6".into(), "30".into()))
+        );
+        assert_eq!(
+            Day03::solve("R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n"),
+            Ok(("159".into(), "610".into()))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use aoc_common::{ParseError, Solution};
+
+fn increasing_digits(nb: &[u8; 6]) -> bool {
+    (0..nb.len() - 1).all(|i| nb[i] <= nb[i + 1])
+}
+
+fn double_digits(nb: &[u8; 6]) -> bool {
+    (0..nb.len() - 1).any(|i| nb[i] == nb[i + 1])
+}
+
+fn strict_double_digits(nb: &[u8; 6]) -> bool {
+    let l = nb.len() - 1;
+    let mut i = 0;
+    while i < l {
+        if nb[i] == nb[i + 1] {
+            match ((i + 1) == l) || nb[i + 1] != nb[i + 2] {
+                true => return true,
+                false => {
+                    while i != l && nb[i] == nb[i + 1] {
+                        i += 1
+                    }
+                }
+            }
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Increments `nb`, skipping every number whose digits are not increasing
+fn increment(nb: &mut [u8; 6]) {
+    for i in (0..nb.len()).rev() {
+        nb[i] += 1;
+        if nb[i] != 10 {
+            let d = nb[i];
+            nb[i + 1..].iter_mut().for_each(|x| *x = d);
+            return;
+        }
+    }
+}
+
+/// The digits of `nb`, a slice of `input`
+fn parse_number(input: &str, nb: &str) -> Result<[u8; 6], ParseError> {
+    let nb = nb.trim();
+    if nb.len() != 6 || !nb.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(ParseError::at(input, nb, "expected a 6 digits number"));
+    }
+    let mut res = [0; 6];
+    res.iter_mut()
+        .zip(nb.bytes())
+        .for_each(|(r, c)| *r = c - b'0');
+    Ok(res)
+}
+
+/// Number of passwords in `start..=end` with increasing digits which follow `rule`
+fn count_passwords(mut nb: [u8; 6], end: [u8; 6], rule: fn(&[u8; 6]) -> bool) -> usize {
+    let mut occurences = 0;
+    while nb <= end {
+        if increasing_digits(&nb) && rule(&nb) {
+            occurences += 1;
+        }
+        increment(&mut nb);
+    }
+    occurences
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = ([u8; 6], [u8; 6]);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let (start, end) = input
+            .split_once('-')
+            .ok_or_else(|| ParseError::at(input, input, "expected a range, like 123456-234567"))?;
+        Ok((parse_number(input, start)?, parse_number(input, end)?))
+    }
+
+    fn part1(&(start, end): &Self::Parsed<'_>) -> String {
+        count_passwords(start, end, double_digits).to_string()
+    }
+
+    fn part2(&(start, end): &Self::Parsed<'_>) -> String {
+        count_passwords(start, end, strict_double_digits).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(nb: &str) -> [u8; 6] {
+        parse_number(nb, nb).unwrap()
+    }
+
+    #[test]
+    fn test_rules() {
+        assert!(double_digits(&digits("111111")));
+        assert!(!increasing_digits(&digits("223450")));
+        assert!(!double_digits(&digits("123789")));
+        assert!(strict_double_digits(&digits("112233")));
+        assert!(!strict_double_digits(&digits("123444")));
+        assert!(strict_double_digits(&digits("111122")));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Day04::parse("111111-111123\n"),
+            Ok((digits("111111"), digits("111123")))
+        );
+        let error = Day04::parse("111111-1111x3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+        assert!(Day04::parse("111111").is_err());
+    }
+}
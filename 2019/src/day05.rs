@@ -0,0 +1,33 @@
+use aoc_common::{ParseError, Solution};
+use int_vm::{InputMode, OutputMode, Vm};
+
+fn run_diagnostic(code: &[i64], system_id: i64) -> i64 {
+    let mut vm = Vm::new(
+        code.to_vec(),
+        vec![system_id],
+        InputMode::VecDirect,
+        OutputMode::No,
+    );
+    let output = vm.run().expect("the diagnostic program failed");
+    // Every output but the last one is a test result, which must be 0
+    debug_assert!(output[..output.len() - 1].iter().all(|&o| o == 0));
+    *output.last().unwrap()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        crate::parse_intcode(input)
+    }
+
+    fn part1(code: &Self::Parsed<'_>) -> String {
+        run_diagnostic(code, 1).to_string()
+    }
+
+    fn part2(code: &Self::Parsed<'_>) -> String {
+        run_diagnostic(code, 5).to_string()
+    }
+}
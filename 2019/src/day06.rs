@@ -0,0 +1,124 @@
+use aoc_common::{Example, ParseError, Solution};
+use std::collections::HashMap;
+
+/// child -> parent. YOU and SAN are optional (the first example has neither), but one without
+/// the other, or without a route between them, is an error.
+fn parse_orbits(input: &str) -> Result<HashMap<&str, &str>, ParseError> {
+    let objects: HashMap<&str, &str> = input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|line| match line.split_once(')') {
+            Some((parent, child)) => Ok((child, parent)),
+            None => Err(ParseError::at(input, line, "expected an orbit, like A)B")),
+        })
+        .collect::<Result<_, _>>()?;
+    if objects.is_empty() {
+        return Err(ParseError::at(input, "", "expected at least one orbit"));
+    }
+    match (objects.get_key_value("YOU"), objects.get_key_value("SAN")) {
+        (Some((you, _)), Some(_)) if part_2(&objects).is_none() => Err(ParseError::at(
+            input,
+            you,
+            "YOU and SAN do not orbit around a common object",
+        )),
+        (Some((object, _)), None) | (None, Some((object, _))) => Err(ParseError::at(
+            input,
+            object,
+            "expected both YOU and SAN, or neither",
+        )),
+        _ => Ok(objects),
+    }
+}
+
+fn route_to_com<'a>(objects: &HashMap<&'a str, &'a str>, mut object: &'a str) -> Vec<&'a str> {
+    let mut route = Vec::new();
+    while let Some(p) = objects.get(object) {
+        object = p;
+        route.push(object);
+    }
+    route
+}
+
+fn part_1(objects: &HashMap<&str, &str>) -> usize {
+    objects
+        .keys()
+        .map(|object| route_to_com(objects, object).len())
+        .sum()
+}
+
+fn part_2(objects: &HashMap<&str, &str>) -> Option<usize> {
+    let santas_route = route_to_com(objects, "SAN");
+    let my_route = route_to_com(objects, "YOU");
+
+    for (my_steps, me) in my_route.iter().enumerate() {
+        if let Some(santas_steps) = santas_route.iter().position(|santa| santa == me) {
+            return Some(my_steps + santas_steps);
+        }
+    }
+    None
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = HashMap<&'a str, &'a str>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n",
+            part1: Some("42"),
+            part2: None,
+        },
+        Example {
+            input: "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN\n",
+            part1: None,
+            part2: Some("4"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_orbits(input)
+    }
+
+    fn part1(objects: &Self::Parsed<'_>) -> String {
+        part_1(objects).to_string()
+    }
+
+    fn part2(objects: &Self::Parsed<'_>) -> String {
+        match part_2(objects) {
+            Some(steps) => steps.to_string(),
+            None => "no YOU and SAN in the map".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_1() {
+        let objects =
+            parse_orbits("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n").unwrap();
+        assert_eq!(part_1(&objects), 42);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let objects =
+            parse_orbits("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN\n")
+                .unwrap();
+        assert_eq!(part_2(&objects), Some(4));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(parse_orbits("").is_err());
+        assert_eq!(
+            parse_orbits("COM)B\nB)YOU\n").unwrap_err(),
+            ParseError::new(2, 3, "expected both YOU and SAN, or neither")
+        );
+        let error = parse_orbits("COM)B\nB)YOU\nX)SAN\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
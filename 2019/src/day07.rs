@@ -0,0 +1,138 @@
+use aoc_common::{Example, ParseError, Solution};
+use int_vm::{InputMode, OutputMode, Vm, VmState};
+
+/// Heap's algorithm
+fn permutations(mut phases: [i64; 5]) -> Vec<[i64; 5]> {
+    fn generate(k: usize, phases: &mut [i64; 5], res: &mut Vec<[i64; 5]>) {
+        if k == 1 {
+            res.push(*phases);
+            return;
+        }
+        for i in 0..k {
+            generate(k - 1, phases, res);
+            let swap = if k.is_multiple_of(2) { i } else { 0 };
+            phases.swap(swap, k - 1);
+        }
+    }
+    let mut res = Vec::with_capacity(120);
+    generate(phases.len(), &mut phases, &mut res);
+    res
+}
+
+/// Runs an amplifier on `arg` until its next output, the reason it has none otherwise
+fn run_vm_arg(vm: &mut Vm, arg: i64) -> Result<i64, String> {
+    vm.input.push_back(arg);
+    vm.run()
+        .map_err(|e| format!("the amplifier program failed: {e}"))?;
+    vm.output
+        .pop()
+        .ok_or_else(|| "the amplifier program gave no output".to_string())
+}
+
+fn amplifiers(code: &[i64], phase_seq: [i64; 5]) -> Vec<Vm> {
+    phase_seq
+        .iter()
+        .map(|&ps| {
+            Vm::new(
+                code.to_vec(),
+                vec![ps],
+                InputMode::VecInterupt,
+                OutputMode::No,
+            )
+        })
+        .collect()
+}
+
+fn chain(code: &[i64], phase_seq: [i64; 5]) -> Result<i64, String> {
+    amplifiers(code, phase_seq)
+        .iter_mut()
+        .try_fold(0, |out, vm| run_vm_arg(vm, out))
+}
+
+fn feedback_loop(code: &[i64], phase_seq: [i64; 5]) -> Result<i64, String> {
+    let mut vms = amplifiers(code, phase_seq);
+    let mut out = 0;
+    while vms[4].state != VmState::Off {
+        out = vms
+            .iter_mut()
+            .try_fold(out, |out, vm| run_vm_arg(vm, out))?;
+    }
+    Ok(out)
+}
+
+/// The highest signal sent to the thrusters, or why a phase setting sends none
+fn highest_signal(signals: impl Iterator<Item = Result<i64, String>>) -> String {
+    match signals.collect::<Result<Vec<_>, _>>() {
+        Ok(signals) => signals.into_iter().max().unwrap().to_string(),
+        Err(reason) => reason,
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = Vec<i64>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+            part1: Some("43210"),
+            part2: None,
+        },
+        Example {
+            input: "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+            part1: None,
+            part2: Some("139629729"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        crate::parse_intcode(input)
+    }
+
+    fn part1(code: &Self::Parsed<'_>) -> String {
+        highest_signal(
+            permutations([0, 1, 2, 3, 4])
+                .into_iter()
+                .map(|ps| chain(code, ps)),
+        )
+    }
+
+    fn part2(code: &Self::Parsed<'_>) -> String {
+        highest_signal(
+            permutations([5, 6, 7, 8, 9])
+                .into_iter()
+                .map(|ps| feedback_loop(code, ps)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        let code = crate::parse_intcode("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0").unwrap();
+        assert_eq!(chain(&code, [4, 3, 2, 1, 0]), Ok(43210));
+        // cut off before the output
+        let code = crate::parse_intcode("3,15,3,16,1002,16,10,16").unwrap();
+        assert!(chain(&code, [4, 3, 2, 1, 0]).is_err());
+    }
+
+    #[test]
+    fn test_feedback_loop() {
+        let code = crate::parse_intcode(
+            "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+        )
+        .unwrap();
+        assert_eq!(feedback_loop(&code, [9, 8, 7, 6, 5]), Ok(139629729));
+    }
+
+    #[test]
+    fn test_permutations() {
+        let perms = permutations([0, 1, 2, 3, 4]);
+        assert_eq!(perms.len(), 120);
+        assert!(perms.iter().all(|p| (0..5).all(|i| p.contains(&i))));
+    }
+}
@@ -0,0 +1,67 @@
+use aoc_common::{ocr, ParseError, Solution};
+
+// The image you received is 25 pixels wide and 6 pixels tall.
+const IMG_DIM: (usize, usize) = (25, 6);
+const IMG_PIXELS: usize = IMG_DIM.0 * IMG_DIM.1;
+
+fn part_1(layers: &[&[u8]]) -> usize {
+    let count = |layer: &[u8], digit| layer.iter().filter(|&&c| c == digit).count();
+    let layer = layers.iter().min_by_key(|l| count(l, b'0')).unwrap();
+    count(layer, b'1') * count(layer, b'2')
+}
+
+fn _print_image(img: &[u8]) {
+    for line in img.chunks(IMG_DIM.0) {
+        let line: String = line
+            .iter()
+            .map(|&px| if px == b'1' { '#' } else { ' ' })
+            .collect();
+        println!("{}", line);
+    }
+}
+
+/// The code on the image, its first visible pixel being on for `1`
+fn part_2(layers: &[&[u8]]) -> String {
+    let mut img = [b'2'; IMG_PIXELS];
+    for layer in layers {
+        img.iter_mut()
+            .zip(layer.iter())
+            .filter(|(px, _)| **px == b'2')
+            .for_each(|(px, lay)| *px = *lay);
+    }
+    // _print_image(&img);
+    ocr::read(IMG_DIM.0, |x, y| img[x + y * IMG_DIM.0] == b'1')
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = Vec<&'a [u8]>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let pixels = input.trim();
+        if let Some(i) = pixels.find(|c| !matches!(c, '0' | '1' | '2')) {
+            return Err(ParseError::at_offset(
+                input,
+                i,
+                "expected a pixel among 0, 1 and 2",
+            ));
+        }
+        if pixels.is_empty() || !pixels.len().is_multiple_of(IMG_PIXELS) {
+            return Err(ParseError::at(
+                input,
+                "",
+                format!("the layers must have {IMG_PIXELS} pixels"),
+            ));
+        }
+        Ok(pixels.as_bytes().chunks(IMG_PIXELS).collect())
+    }
+
+    fn part1(layers: &Self::Parsed<'_>) -> String {
+        part_1(layers).to_string()
+    }
+
+    fn part2(layers: &Self::Parsed<'_>) -> String {
+        part_2(layers)
+    }
+}
@@ -0,0 +1,30 @@
+use aoc_common::{ParseError, Solution};
+use int_vm::{InputMode, OutputMode, Vm};
+
+fn run_boost(code: &[i64], mode: i64) -> i64 {
+    let mut vm = Vm::new(
+        code.to_vec(),
+        vec![mode],
+        InputMode::VecDirect,
+        OutputMode::No,
+    );
+    vm.run().expect("the BOOST program failed")[0]
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        crate::parse_intcode(input)
+    }
+
+    fn part1(code: &Self::Parsed<'_>) -> String {
+        run_boost(code, 1).to_string()
+    }
+
+    fn part2(code: &Self::Parsed<'_>) -> String {
+        run_boost(code, 2).to_string()
+    }
+}
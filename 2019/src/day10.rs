@@ -0,0 +1,22 @@
+use aoc_common::{parse, ParseError, Solution};
+
+// The asteroid map is read, but the monitoring station is not solved yet.
+const NOT_IMPLEMENTED: &str = "not implemented";
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse::grid(input, ".#")
+    }
+
+    fn part1(_map: &Self::Parsed<'_>) -> String {
+        NOT_IMPLEMENTED.to_string()
+    }
+
+    fn part2(_map: &Self::Parsed<'_>) -> String {
+        NOT_IMPLEMENTED.to_string()
+    }
+}
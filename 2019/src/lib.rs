@@ -0,0 +1,11 @@
+// `pub mod dayNN;` for every `src/dayNN.rs`, `SOLUTIONS` and what the runner uses of a year,
+// see `aoc_common::build`
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn parse_intcode(input: &str) -> Result<Vec<i64>, aoc_common::ParseError> {
+    input
+        .trim()
+        .split(',')
+        .map(|s| aoc_common::parse::token(input, s))
+        .collect()
+}
@@ -1,4 +1,4 @@
-const INPUT: &[u8] = include_bytes!("../input/day01.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 fn part_1(mut numbers: Vec<usize>) -> String {
     while let Some(nb) = numbers.pop() {
@@ -31,15 +31,28 @@ fn part_2(mut numbers: Vec<usize>) -> String {
     unreachable!("Part 2: Not found !");
 }
 
-pub fn day01() -> (String, String) {
-    let numbers: Vec<usize> = INPUT
-        .split(|&c| c == b'\n')
-        .filter(|s| !s.is_empty())
-        .map(|e| unsafe { std::str::from_utf8_unchecked(e).parse().unwrap() })
-        .filter(|&nb| nb <= 2020)
-        .collect();
+pub struct Day01;
 
-    let p1 = part_1(numbers.clone());
-    let p2 = part_2(numbers);
-    (p1, p2)
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let mut numbers = Vec::new();
+        for line in input.lines().filter(|l| !l.is_empty()) {
+            let nb = parse::token(input, line)?;
+            if nb <= 2020 {
+                numbers.push(nb);
+            }
+        }
+        // part 2 sums three entries
+        parse::at_least(input, numbers, 3, "entries up to 2020")
+    }
+
+    fn part1(numbers: &Self::Parsed<'_>) -> String {
+        part_1(numbers.clone())
+    }
+
+    fn part2(numbers: &Self::Parsed<'_>) -> String {
+        part_2(numbers.clone())
+    }
 }
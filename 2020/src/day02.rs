@@ -1,24 +1,36 @@
-const INPUT: &str = include_str!("../input/day02.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 #[derive(Debug)]
-struct Rule {
+pub struct Rule {
     pub char: u8,
     pub min: u8,
     pub max: u8,
 }
 
-fn parse() -> impl Iterator<Item = (Rule, &'static [u8])> {
-    INPUT.lines().map(|s| {
-        let mut t = s.split(": ");
-        let rule = t.next().unwrap();
-        let passwd = t.next().unwrap();
-        let mut t = rule.split(' ');
-        let mut tt = t.next().unwrap().split('-');
-        let min = tt.next().unwrap().parse().unwrap();
-        let max = tt.next().unwrap().parse().unwrap();
-        let char = t.next().unwrap().as_bytes()[0];
-        (Rule { char, min, max }, passwd.as_bytes())
-    })
+/// Parses a line like `1-3 a: abcde`
+fn parse_line<'a>(input: &str, line: &'a str) -> Result<(Rule, &'a [u8]), ParseError> {
+    let invalid = |token, reason| ParseError::at(input, token, reason);
+    let (rule, passwd) = line
+        .split_once(": ")
+        .ok_or_else(|| invalid(line, "expected a rule and a password, like 1-3 a: abcde"))?;
+    let (range, char) = rule
+        .split_once(' ')
+        .ok_or_else(|| invalid(rule, "expected a range and a letter, like 1-3 a"))?;
+    let (min, max) = range
+        .split_once('-')
+        .ok_or_else(|| invalid(range, "expected a range, like 1-3"))?;
+    let (min, max) = (parse::token(input, min)?, parse::token(input, max)?);
+    // part 2 reads the characters at these positions
+    if min == 0 || min > max || max as usize > passwd.len() {
+        return Err(invalid(
+            range,
+            "expected 1 <= min <= max <= the password's length",
+        ));
+    }
+    let &[char] = char.as_bytes() else {
+        return Err(invalid(char, "expected a single letter"));
+    };
+    Ok((Rule { char, min, max }, passwd.as_bytes()))
 }
 
 fn is_valid_part1(r: &Rule, pass: &[u8]) -> bool {
@@ -32,19 +44,28 @@ fn is_valid_part2(r: &Rule, pass: &[u8]) -> bool {
     (char1 == r.char) ^ (char2 == r.char)
 }
 
-pub fn day02() -> (String, String) {
-    let input = parse();
-
-    let mut p1 = 0;
-    let mut p2 = 0;
-    for (r, p) in input {
-        if is_valid_part1(&r, p) {
-            p1 += 1
-        };
-        if is_valid_part2(&r, p) {
-            p2 += 1
-        };
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<(Rule, &'a [u8])>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input.lines().map(|l| parse_line(input, l)).collect()
     }
 
-    (p1.to_string(), p2.to_string())
+    fn part1(passwords: &Self::Parsed<'_>) -> String {
+        passwords
+            .iter()
+            .filter(|(r, p)| is_valid_part1(r, p))
+            .count()
+            .to_string()
+    }
+
+    fn part2(passwords: &Self::Parsed<'_>) -> String {
+        passwords
+            .iter()
+            .filter(|(r, p)| is_valid_part2(r, p))
+            .count()
+            .to_string()
+    }
 }
@@ -1,11 +1,4 @@
-const INPUT: &str = include_str!("../input/day03.txt");
-
-#[derive(Debug)]
-struct Rule {
-    pub char: u8,
-    pub min: u8,
-    pub max: u8,
-}
+use aoc_common::{parse, ParseError, Solution};
 
 struct Pt<T> {
     pub x: T,
@@ -17,11 +10,12 @@ impl<T> Pt<T> {
     }
 }
 
-fn parse() -> Vec<Vec<bool>> {
-    INPUT
-        .lines()
-        .map(|s| s.as_bytes().iter().map(|&c| c == b'#').collect())
-        .collect()
+fn parse(input: &str) -> Result<Vec<Vec<bool>>, ParseError> {
+    let rows = parse::grid(input, "#.")?;
+    Ok(rows
+        .iter()
+        .map(|s| s.bytes().map(|c| c == b'#').collect())
+        .collect())
 }
 
 fn check_slope(map: &[Vec<bool>], slope: &Pt<usize>) -> u32 {
@@ -38,15 +32,30 @@ fn check_slope(map: &[Vec<bool>], slope: &Pt<usize>) -> u32 {
     nb_tree
 }
 
-pub fn day03() -> (String, String) {
-    let input = parse();
-    let p1 = check_slope(&input, &Pt::new(3, 1));
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = Vec<Vec<bool>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> String {
+        check_slope(map, &Pt::new(3, 1)).to_string()
+    }
 
-    let p2 = [Pt::new(1, 1), Pt::new(5, 1), Pt::new(7, 1), Pt::new(1, 2)]
+    fn part2(map: &Self::Parsed<'_>) -> String {
+        [
+            Pt::new(1, 1),
+            Pt::new(3, 1),
+            Pt::new(5, 1),
+            Pt::new(7, 1),
+            Pt::new(1, 2),
+        ]
         .iter()
-        .map(|slope| check_slope(&input, slope))
+        .map(|slope| check_slope(map, slope))
         .product::<u32>()
-        * p1;
-
-    (p1.to_string(), p2.to_string())
+        .to_string()
+    }
 }
@@ -1,18 +1,19 @@
-const INPUT: &str = include_str!("../input/day04.txt");
+use aoc_common::{ParseError, Solution};
 
-struct Passport {
+pub struct Passport {
     present: u8,
     valid: u8,
 }
 impl Passport {
-    pub fn new(data: impl Iterator<Item = &'static str>) -> Self {
+    /// `data` are the `key:value` fields of the passport, slices of `input`
+    pub fn new<'a>(input: &str, data: impl Iterator<Item = &'a str>) -> Result<Self, ParseError> {
         let mut present = 0;
         let mut valid = 0;
 
         for d in data {
-            let mut t = d.split(':');
-            let key = t.next().unwrap();
-            let value = t.next().unwrap();
+            let (key, value) = d
+                .split_once(':')
+                .ok_or_else(|| ParseError::at(input, d, "expected a field, like key:value"))?;
             match key {
                 "byr" => {
                     // byr (Birth Year) - four digits; at least 1920 and at most 2002.
@@ -91,7 +92,7 @@ impl Passport {
                 _ => (), // skip cid
             }
         }
-        Self { valid, present }
+        Ok(Self { valid, present })
     }
     pub fn is_complete(&self) -> bool {
         self.present == 127
@@ -101,26 +102,28 @@ impl Passport {
     }
 }
 
-fn parse() -> Vec<Passport> {
-    INPUT
+fn parse(input: &str) -> Result<Vec<Passport>, ParseError> {
+    input
         .split("\n\n")
-        .map(|s| s.split(|c| c == ' ' || c == '\n').filter(|s| !s.is_empty()))
-        .map(Passport::new)
+        .map(|s| s.split([' ', '\n']).filter(|s| !s.is_empty()))
+        .map(|data| Passport::new(input, data))
         .collect()
 }
 
-pub fn day04() -> (String, String) {
-    let passports = parse();
-    let mut p1 = 0;
-    let mut p2 = 0;
+pub struct Day04;
 
-    for p in passports {
-        if p.is_complete() {
-            p1 += 1
-        };
-        if p.is_valid() {
-            p2 += 1
-        };
+impl Solution for Day04 {
+    type Parsed<'a> = Vec<Passport>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(passports: &Self::Parsed<'_>) -> String {
+        passports.iter().filter(|p| p.is_complete()).count().to_string()
+    }
+
+    fn part2(passports: &Self::Parsed<'_>) -> String {
+        passports.iter().filter(|p| p.is_valid()).count().to_string()
     }
-    (p1.to_string(), p2.to_string())
 }
@@ -1,13 +1,6 @@
-const INPUT: &str = include_str!("../input/day05.txt");
+use aoc_common::{parse, ParseError, Solution};
 use bitvec::prelude::*;
 
-#[derive(Debug)]
-struct Rule {
-    pub char: u8,
-    pub min: u8,
-    pub max: u8,
-}
-
 struct Range {
     min: u32,
     max: u32,
@@ -30,13 +23,20 @@ impl Range {
     }
 }
 
-struct Seat {
+pub struct Seat {
     pub row: u8, // 128 row
     pub col: u8, // 8 col
 }
 
 impl Seat {
-    pub fn new(id: &str) -> Self {
+    /// `id` is a line of `input`
+    pub fn new(input: &str, id: &str) -> Result<Self, ParseError> {
+        let valid = id.len() == 10
+            && id[..7].bytes().all(|c| matches!(c, b'F' | b'B'))
+            && id[7..].bytes().all(|c| matches!(c, b'L' | b'R'));
+        if !valid {
+            return Err(ParseError::at(input, id, "expected 7 F or B then 3 L or R"));
+        }
         let mut row = Range::new(0, 127);
         let mut col = Range::new(0, 7);
         for c in id.as_bytes() {
@@ -48,40 +48,49 @@ impl Seat {
                 _ => unreachable!(),
             }
         }
-        Self {
+        Ok(Self {
             row: row.final_value() as u8,
             col: col.final_value() as u8,
-        }
+        })
     }
     pub fn id(&self) -> u32 {
         self.row as u32 * 8 + self.col as u32
     }
 }
 
-fn parse() -> Vec<Seat> {
-    INPUT.lines().map(Seat::new).collect()
+fn parse(input: &str) -> Result<Vec<Seat>, ParseError> {
+    let seats = input
+        .lines()
+        .map(|l| Seat::new(input, l))
+        .collect::<Result<_, _>>()?;
+    parse::at_least(input, seats, 1, "seat")
 }
 
-pub fn day05() -> (String, String) {
-    let input = parse();
+pub struct Day05;
 
-    let mut seats_taken = bitvec![0; 1024];
+impl Solution for Day05 {
+    type Parsed<'a> = Vec<Seat>;
 
-    let mut p1 = 0;
-    for s in input {
-        let s_id = s.id();
-        if s_id > p1 {
-            p1 = s_id;
-        }
-        seats_taken.set(s_id as usize, true);
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
     }
 
-    let mut p2 = 0;
-    for (idx, s_taken) in seats_taken.iter().enumerate().skip(1) {
-        if !s_taken && seats_taken[idx - 1] && seats_taken[idx + 1] {
-            p2 = idx;
-        }
+    fn part1(seats: &Self::Parsed<'_>) -> String {
+        seats.iter().map(Seat::id).max().unwrap().to_string()
     }
 
-    (p1.to_string(), p2.to_string())
+    fn part2(seats: &Self::Parsed<'_>) -> String {
+        let mut seats_taken = bitvec![0; 1024];
+        for s in seats {
+            seats_taken.set(s.id() as usize, true);
+        }
+
+        let mut p2 = 0;
+        for (idx, s_taken) in seats_taken.iter().enumerate().skip(1).take(1022) {
+            if !s_taken && seats_taken[idx - 1] && seats_taken[idx + 1] {
+                p2 = idx;
+            }
+        }
+        p2.to_string()
+    }
 }
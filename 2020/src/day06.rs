@@ -1,29 +1,45 @@
+use aoc_common::{ParseError, Solution};
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../input/day06.txt");
+pub struct Day06;
 
-pub fn day06() -> (String, String) {
-    let mut count_part1: usize = 0;
-    let mut count_part2: usize = 0;
-    let mut answers: HashMap<u8, usize> = HashMap::new();
-    let mut nb_people: usize = 0;
+impl Solution for Day06 {
+    type Parsed<'a> = Vec<(HashMap<u8, usize>, usize)>;
 
-    for line in INPUT.lines() {
-        if line.is_empty() {
-            count_part1 += answers.len();
-            count_part2 += answers.iter().filter(|(_, &nb)| nb == nb_people).count();
-            answers.drain();
-            nb_people = 0;
-        } else {
-            nb_people += 1;
-            // letters are a-z, no need to handle utf-8
-            for c in line.as_bytes().iter() {
-                answers
-                    .entry(*c)
-                    .and_modify(|count| *count += 1)
-                    .or_insert(1);
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // every group's answers count, with the number of people in the group
+        let mut groups = Vec::new();
+        let mut answers: HashMap<u8, usize> = HashMap::new();
+        let mut nb_people: usize = 0;
+
+        for line in input.lines() {
+            if line.is_empty() {
+                groups.push((std::mem::take(&mut answers), nb_people));
+                nb_people = 0;
+            } else {
+                nb_people += 1;
+                // letters are a-z, no need to handle utf-8
+                for c in line.as_bytes().iter() {
+                    answers
+                        .entry(*c)
+                        .and_modify(|count| *count += 1)
+                        .or_insert(1);
+                }
             }
         }
+        Ok(groups)
+    }
+
+    fn part1(groups: &Self::Parsed<'_>) -> String {
+        let count: usize = groups.iter().map(|(answers, _)| answers.len()).sum();
+        format!("{}", count)
+    }
+
+    fn part2(groups: &Self::Parsed<'_>) -> String {
+        let count: usize = groups
+            .iter()
+            .map(|(answers, nb_people)| answers.iter().filter(|(_, nb)| *nb == nb_people).count())
+            .sum();
+        format!("{}", count)
     }
-    (format!("{}", count_part1), format!("{}", count_part2))
 }
@@ -1,9 +1,8 @@
+use aoc_common::{parse, ParseError, Solution};
 use fnv::FnvHashMap;
 use fnv::FnvHasher;
 use std::hash::Hasher;
 
-const INPUT: &str = include_str!("../input/day07.txt");
-
 type BagMap = FnvHashMap<u64, Vec<(u64, u16)>>;
 
 fn hash_str(s: &str) -> u64 {
@@ -12,29 +11,37 @@ fn hash_str(s: &str) -> u64 {
     hasher.finish()
 }
 
-fn parse() -> BagMap {
+fn parse(input: &str) -> Result<BagMap, ParseError> {
     let mut bags = BagMap::default();
 
-    for line in INPUT.lines() {
-        let mut tmp = line.split(" bags contain ");
-        let parent_color = tmp.next().unwrap();
+    for line in input.lines() {
+        let (parent_color, children_str) = line.split_once(" bags contain ").ok_or_else(|| {
+            ParseError::at(
+                input,
+                line,
+                "expected a rule, like `<color> bags contain ...`",
+            )
+        })?;
         let parent_color_hash = hash_str(parent_color);
-        let children_str = tmp.next().unwrap();
         if children_str.starts_with('n') {
             continue; // 'no other bags.'
         }
 
         for child_str in children_str.split(", ") {
-            let first_non_nb = child_str.find(|c: char| !c.is_numeric()).unwrap();
-            let color_end = child_str.rfind("bag").unwrap();
-            let nb = child_str[..first_non_nb].parse::<u16>().unwrap();
-            let color_hash = hash_str(&child_str[first_non_nb + 1..color_end - 1]);
+            // like `2 muted yellow bags.`
+            let (nb, color) = child_str
+                .trim_end_matches('.')
+                .trim_end_matches(" bags")
+                .trim_end_matches(" bag")
+                .split_once(' ')
+                .ok_or_else(|| ParseError::at(input, child_str, "expected `<n> <color> bags`"))?;
+            let nb = parse::token::<u16>(input, nb)?;
             bags.entry(parent_color_hash)
                 .or_insert_with(Vec::new)
-                .push((color_hash, nb));
+                .push((hash_str(color), nb));
         }
     }
-    bags
+    Ok(bags)
 }
 
 fn contains_bag(bag: u64, color: u64, bag_map: &BagMap) -> bool {
@@ -72,10 +79,20 @@ fn part2(bags: &BagMap, parent_bag: u64) -> usize {
     count
 }
 
-pub fn day07() -> (String, String) {
-    let bag_map = parse();
-    (
-        format!("{}", part1(&bag_map)),
-        format!("{}", part2(&bag_map, hash_str("shiny gold")) - 1),
-    )
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = BagMap;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(bag_map: &Self::Parsed<'_>) -> String {
+        format!("{}", part1(bag_map))
+    }
+
+    fn part2(bag_map: &Self::Parsed<'_>) -> String {
+        format!("{}", part2(bag_map, hash_str("shiny gold")) - 1)
+    }
 }
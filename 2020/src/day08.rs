@@ -1,13 +1,14 @@
-const INPUT: &str = include_str!("../input/day08.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 #[derive(Clone, Copy)]
-enum Inst {
+pub enum Inst {
     Nop,
     Acc,
     Jmp,
 }
 
-struct Instruction {
+#[derive(Clone)]
+pub struct Instruction {
     pub op: Inst,
     pub nb: i32,
 }
@@ -18,16 +19,18 @@ impl Instruction {
     }
 }
 
-impl From<&str> for Instruction {
-    fn from(s: &str) -> Self {
-        let mut t = s.split(' ');
-        let inst_str = t.next().unwrap();
-        let nb = t.next().unwrap().parse().unwrap();
+impl Instruction {
+    /// Parses `line`, a line of `input`
+    pub fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (inst_str, nb) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at(input, line, "expected an instruction, like acc +1"))?;
+        let nb = parse::token(input, nb)?;
         match inst_str {
-            "nop" => Self::new(Inst::Nop, nb),
-            "acc" => Self::new(Inst::Acc, nb),
-            "jmp" => Self::new(Inst::Jmp, nb),
-            _ => unreachable!(),
+            "nop" => Ok(Self::new(Inst::Nop, nb)),
+            "acc" => Ok(Self::new(Inst::Acc, nb)),
+            "jmp" => Ok(Self::new(Inst::Jmp, nb)),
+            _ => Err(ParseError::at(input, inst_str, "expected nop, acc or jmp")),
         }
     }
 }
@@ -64,12 +67,12 @@ impl VM {
     }
 }
 
-fn parse() -> Vec<Instruction> {
-    let mut instructions = Vec::new();
-    INPUT
+fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let code = input
         .lines()
-        .for_each(|l| instructions.push(Instruction::from(l)));
-    instructions
+        .map(|l| Instruction::parse(input, l))
+        .collect::<Result<_, _>>()?;
+    parse::at_least(input, code, 1, "instruction")
 }
 
 fn part_1(code: &[Instruction]) -> i32 {
@@ -87,27 +90,33 @@ fn part_2_invert(instr: &mut Instruction) {
 }
 
 fn part_2(code: &mut [Instruction]) -> i32 {
-    let mut i = 0;
-    loop {
-        let instr = unsafe { code.get_unchecked_mut(i) };
-        if matches!(instr.op, Inst::Jmp | Inst::Nop) {
-            part_2_invert(instr);
+    for i in 0..code.len() {
+        if matches!(code[i].op, Inst::Jmp | Inst::Nop) {
+            part_2_invert(&mut code[i]);
             let mut machine = VM::default();
-            if machine.run(&code) {
+            if machine.run(code) {
                 return machine.accumulator;
             }
-            let instr = unsafe { code.get_unchecked_mut(i) };
-            part_2_invert(instr);
+            part_2_invert(&mut code[i]);
         }
-        i += 1;
     }
+    panic!("no single instruction fixes the program");
 }
 
-pub fn day08() -> (String, String) {
-    let mut instructions = parse();
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
 
-    (
-        format!("{}", part_1(&instructions)),
-        format!("{}", part_2(&mut instructions)),
-    )
+    fn part1(instructions: &Self::Parsed<'_>) -> String {
+        format!("{}", part_1(instructions))
+    }
+
+    fn part2(instructions: &Self::Parsed<'_>) -> String {
+        format!("{}", part_2(&mut instructions.to_vec()))
+    }
 }
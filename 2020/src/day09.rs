@@ -1,8 +1,9 @@
-const INPUT: &str = include_str!("../input/day09.txt");
+use aoc_common::{parse, ParseError, Solution};
+
 const PREAMBLE: usize = 25;
 
-fn parse() -> impl Iterator<Item = usize> {
-    INPUT.lines().map(|s| s.parse().unwrap())
+fn parse(input: &str) -> impl Iterator<Item = Result<usize, ParseError>> + '_ {
+    input.lines().map(move |s| parse::token(input, s))
 }
 
 fn try_sum(preamble: &[usize], target: usize) -> bool {
@@ -41,8 +42,8 @@ fn part_2(numbers: &[usize], p1_result: usize) -> usize {
             running_sum += numbers[end];
             if running_sum == p1_result {
                 let (min, max) = {
-                    let mut mi = std::usize::MAX;
-                    let mut ma = std::usize::MIN;
+                    let mut mi = usize::MAX;
+                    let mut ma = usize::MIN;
                     for &i in &numbers[start..=end] {
                         if i < mi {
                             mi = i;
@@ -63,9 +64,26 @@ fn part_2(numbers: &[usize], p1_result: usize) -> usize {
     unreachable!();
 }
 
-pub fn day09() -> (String, String) {
-    let numbers = parse().collect::<Vec<_>>();
-    let p1 = part_1(&numbers);
-    let p2 = part_2(&numbers, p1);
-    (p1.to_string(), p2.to_string())
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // part 1 looks for a number after the preamble
+        parse::at_least(
+            input,
+            parse(input).collect::<Result<_, _>>()?,
+            PREAMBLE + 1,
+            "numbers",
+        )
+    }
+
+    fn part1(numbers: &Self::Parsed<'_>) -> String {
+        part_1(numbers).to_string()
+    }
+
+    fn part2(numbers: &Self::Parsed<'_>) -> String {
+        part_2(numbers, part_1(numbers)).to_string()
+    }
 }
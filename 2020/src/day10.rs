@@ -1,21 +1,26 @@
-const INPUT: &str = include_str!("../input/day10.txt");
+use aoc_common::{parse, ParseError, Solution};
 
-fn parse() -> impl Iterator<Item = u64> {
-    INPUT.lines().map(|s| s.parse().unwrap())
+fn parse(input: &str) -> impl Iterator<Item = Result<u64, ParseError>> + '_ {
+    input.lines().map(move |s| parse::token(input, s))
 }
 
-pub fn day10() -> (String, String) {
-    let mut jolts = Vec::with_capacity(105);
-    jolts.push(0);
-    jolts.extend(parse());
-    jolts.sort_unstable();
-    jolts.push(jolts.last().unwrap() + 3);
+pub struct Day10;
 
-    let (p1, p2) = {
-        let mut dp = vec![0; jolts.len()];
-        dp[0] = 1;
-        let mut j = 0;
-        let mut sum: u64 = 1;
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let mut jolts = Vec::with_capacity(105);
+        jolts.push(0);
+        for jolt in parse(input) {
+            jolts.push(jolt?);
+        }
+        jolts.sort_unstable();
+        jolts.push(jolts.last().unwrap() + 3);
+        Ok(jolts)
+    }
+
+    fn part1(jolts: &Self::Parsed<'_>) -> String {
         let mut nb_jmp_1 = 0;
         let mut nb_jmp_3 = 0;
         for i in 1..(jolts.len()) {
@@ -24,6 +29,16 @@ pub fn day10() -> (String, String) {
                 3 => nb_jmp_3 += 1,
                 _ => (),
             }
+        }
+        (nb_jmp_1 * nb_jmp_3).to_string()
+    }
+
+    fn part2(jolts: &Self::Parsed<'_>) -> String {
+        let mut dp = vec![0; jolts.len()];
+        dp[0] = 1;
+        let mut j = 0;
+        let mut sum: u64 = 1;
+        for i in 1..(jolts.len()) {
             while jolts[i] - jolts[j] > 3 {
                 sum -= dp[j];
                 j += 1;
@@ -31,8 +46,6 @@ pub fn day10() -> (String, String) {
             dp[i] = sum;
             sum *= 2;
         }
-        (nb_jmp_1 * nb_jmp_3, *dp.last().unwrap())
-    };
-
-    (p1.to_string(), p2.to_string())
+        dp.last().unwrap().to_string()
+    }
 }
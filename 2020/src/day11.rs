@@ -1,4 +1,4 @@
-const INPUT: &str = include_str!("../input/day11.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 const FLOOR: u8 = b'.';
 const OCCUPIED: u8 = b'#';
@@ -15,20 +15,13 @@ const DIRECTIONS: [(isize, isize); 8] = [
     (1, 1),
 ];
 
-#[derive(Clone)]
-struct Map {
+#[derive(Clone, Default)]
+pub struct Map {
     pub seats: Vec<u8>,
     pub width: usize,
     pub height: usize,
 }
 impl Map {
-    pub fn default() -> Self {
-        Self {
-            seats: Vec::new(),
-            width: 0,
-            height: 0,
-        }
-    }
     pub fn accumulate_row(mut self, row: &[u8]) -> Self {
         self.height += 1;
         self.width = row.len();
@@ -103,11 +96,11 @@ impl Map {
     }
 }
 
-fn parse() -> Map {
-    INPUT
-        .lines()
+fn parse(input: &str) -> Result<Map, ParseError> {
+    Ok(parse::grid(input, ".L#")?
+        .into_iter()
         .map(|s| s.as_bytes())
-        .fold(Map::default(), |acc, x| acc.accumulate_row(x))
+        .fold(Map::default(), |acc, x| acc.accumulate_row(x)))
 }
 
 fn run_simulation(
@@ -146,15 +139,24 @@ fn run_simulation(
     seats.iter().map(|s| if *s == b'#' { 1 } else { 0 }).sum()
 }
 
-pub fn day11() -> (String, String) {
-    let map = parse();
-    let map2 = map.clone();
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Map;
 
-    let p1_map = map.seats_indices_part1();
-    let p1 = run_simulation(map.seats, p1_map, 4);
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
 
-    let p2_map = map2.seats_indices_part2();
-    let p2 = run_simulation(map2.seats, p2_map, 5);
+    fn part1(map: &Self::Parsed<'_>) -> String {
+        let map = map.clone();
+        let p1_map = map.seats_indices_part1();
+        run_simulation(map.seats, p1_map, 4).to_string()
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part2(map: &Self::Parsed<'_>) -> String {
+        let map = map.clone();
+        let p2_map = map.seats_indices_part2();
+        run_simulation(map.seats, p2_map, 5).to_string()
+    }
 }
@@ -1,15 +1,16 @@
-const INPUT: &str = include_str!("../input/day12.txt");
 // E S W N
+use aoc_common::{parse, ParseError, Solution};
+
 const DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
 
-struct Ship {
+pub struct Ship {
     p1_dir: (i32, i32),      // pos coeff (y, x)
     p1_pos: (i32, i32),      // y, x
     p2_pos: (i32, i32),      // y, x
     p2_waypoint: (i32, i32), // y, x
 }
-impl Ship {
-    pub fn default() -> Self {
+impl Default for Ship {
+    fn default() -> Self {
         Self {
             p1_dir: (0, 1),
             p1_pos: (0, 0),
@@ -17,6 +18,8 @@ impl Ship {
             p2_waypoint: (-1, 10),
         }
     }
+}
+impl Ship {
     fn forward(&mut self, val: i32) {
         self.p1_pos.0 += self.p1_dir.0 * val;
         self.p1_pos.1 += self.p1_dir.1 * val;
@@ -41,22 +44,29 @@ impl Ship {
         }
     }
     fn rotate_left(&mut self, degrees: usize) {
-        self.rotate(degrees, &|angle: &mut (i32, i32)| {
+        self.rotate(degrees, |angle: &mut (i32, i32)| {
             let tmp = angle.0;
             angle.0 = -angle.1;
             angle.1 = tmp;
         });
     }
     fn rotate_right(&mut self, degrees: usize) {
-        self.rotate(degrees, &|angle: &mut (i32, i32)| {
+        self.rotate(degrees, |angle: &mut (i32, i32)| {
             let tmp = angle.0;
             angle.0 = angle.1;
             angle.1 = -tmp;
         });
     }
-    pub fn read_instr(&mut self, instr: &str) {
-        let what = instr.as_bytes()[0];
-        let val = (&instr[1..]).parse::<u32>().unwrap() as i32;
+    /// Follows `instr`, a line of `input`
+    pub fn read_instr(&mut self, input: &str, instr: &str) -> Result<(), ParseError> {
+        let Some(what) = instr.bytes().next() else {
+            return Err(ParseError::at(
+                input,
+                instr,
+                "expected an instruction, like F10",
+            ));
+        };
+        let val = parse::token::<u16>(input, &instr[1..])? as i32;
         match what {
             b'R' => self.rotate_right(val as usize),
             b'L' => self.rotate_left(val as usize),
@@ -65,8 +75,9 @@ impl Ship {
             b'S' => self.move_direction(1, val),
             b'W' => self.move_direction(2, val),
             b'N' => self.move_direction(3, val),
-            _ => unreachable!(),
+            _ => return Err(ParseError::at(input, instr, "expected one of NSEWLRF")),
         }
+        Ok(())
     }
     pub fn get_manhattan_dist1(&self) -> usize {
         (self.p1_pos.0.abs() + self.p1_pos.1.abs()) as usize
@@ -76,18 +87,28 @@ impl Ship {
     }
 }
 
-fn parse() -> Ship {
-    INPUT.lines().fold(Ship::default(), |mut boat, s| {
-        boat.read_instr(s);
-        boat
-    })
+fn parse(input: &str) -> Result<Ship, ParseError> {
+    let mut boat = Ship::default();
+    for s in input.lines() {
+        boat.read_instr(input, s)?;
+    }
+    Ok(boat)
 }
 
-pub fn day12() -> (String, String) {
-    let boat = parse();
+pub struct Day12;
 
-    let p1 = boat.get_manhattan_dist1();
-    let p2 = boat.get_manhattan_dist2();
+impl Solution for Day12 {
+    type Parsed<'a> = Ship;
 
-    (p1.to_string(), p2.to_string())
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(boat: &Self::Parsed<'_>) -> String {
+        boat.get_manhattan_dist1().to_string()
+    }
+
+    fn part2(boat: &Self::Parsed<'_>) -> String {
+        boat.get_manhattan_dist2().to_string()
+    }
 }
@@ -1,27 +1,29 @@
-const INPUT: &str = include_str!("../input/day13.txt");
+use aoc_common::{parse, ParseError, Solution};
 
-fn parse() -> (i64, Vec<i64>, Vec<i64>) {
-    let mut lines = INPUT.lines();
-    let disponibility = lines.next().unwrap().parse().unwrap();
-    let (residues, buses) = lines
-        .next()
-        .unwrap()
+fn parse(input: &str) -> Result<(i64, Vec<i64>, Vec<i64>), ParseError> {
+    let mut lines = input.lines();
+    let disponibility = parse::token(input, parse::next(input, &mut lines, "the timestamp")?)?;
+    let (mut residues, mut buses) = (Vec::new(), Vec::new());
+    for (dt, s) in parse::next(input, &mut lines, "the buses")?
         .split(',')
         .enumerate()
         .filter(|(_, s)| s != &"x")
-        .map(|(dt, s)| (dt as i64, s.parse::<i64>().unwrap()))
-        // .fold(())
-        .map(|(dt, b_id)| (b_id - dt, b_id))
-        .fold((Vec::new(), Vec::new()), |mut acc, x| {
-            acc.0.push(x.0);
-            acc.1.push(x.1);
-            acc
-        });
-    (disponibility, buses, residues)
+    {
+        let b_id = parse::token::<u32>(input, s)? as i64;
+        if b_id == 0 {
+            return Err(ParseError::at(input, s, "a bus id cannot be 0"));
+        }
+        residues.push(b_id - dt as i64);
+        buses.push(b_id);
+    }
+    if buses.is_empty() {
+        return Err(ParseError::at(input, "", "expected a bus"));
+    }
+    Ok((disponibility, buses, residues))
 }
 
 fn p1(disp: i64, buses: &[i64]) -> i64 {
-    let mut min_wait = std::i64::MAX;
+    let mut min_wait = i64::MAX;
     let mut best_bus = -1;
     for b in buses {
         let wait_time = b - (disp % b);
@@ -58,9 +60,20 @@ fn p2(re: &[i64], mo: &[i64]) -> i64 {
         % prod
 }
 
-pub fn day13() -> (String, String) {
-    let (disp, buses, residues) = parse();
-    let p1 = p1(disp, &buses);
-    let p2 = p2(&residues, &buses);
-    (p1.to_string(), p2.to_string())
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = (i64, Vec<i64>, Vec<i64>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1((disp, buses, _): &Self::Parsed<'_>) -> String {
+        p1(*disp, buses).to_string()
+    }
+
+    fn part2((_, buses, residues): &Self::Parsed<'_>) -> String {
+        p2(residues, buses).to_string()
+    }
 }
@@ -1,6 +1,7 @@
+use aoc_common::{parse, ParseError, Solution};
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../input/day14.txt");
+const MEM_SIZE: usize = 99047;
 
 #[derive(Debug)]
 struct BinaryMask {
@@ -11,7 +12,7 @@ struct BinaryMask {
 impl BinaryMask {
     pub fn default() -> Self {
         Self {
-            zero: std::u64::MAX,
+            zero: u64::MAX,
             one: 0,
             float: Vec::new(),
         }
@@ -37,7 +38,7 @@ impl BinaryMask {
         } else {
             let idx = float_mask[0];
             let val1 = val | (1 << idx);
-            let val2 = val & (std::u64::MAX ^ (1 << idx));
+            let val2 = val & (u64::MAX ^ (1 << idx));
 
             let mut a = Self::_mask_p2_float(&float_mask[1..], val1);
             let mut b = Self::_mask_p2_float(&float_mask[1..], val2);
@@ -53,26 +54,39 @@ impl BinaryMask {
 }
 
 #[derive(Debug)]
-enum Line<'a> {
+pub enum Line<'a> {
     Mask(&'a str),
     MemSet(usize, u64), // address: value
 }
 
-fn parse() -> impl Iterator<Item = Line<'static>> {
-    INPUT.lines().map(|l| {
-        if l.starts_with("mask") {
-            Line::Mask(&l[l.find('=').unwrap() + 2..])
+fn parse(input: &str) -> impl Iterator<Item = Result<Line<'_>, ParseError>> {
+    input.lines().map(move |l| {
+        if let Some(mask) = l.strip_prefix("mask = ") {
+            if mask.len() != 36 {
+                return Err(ParseError::at(input, mask, "expected a mask of 36 bits"));
+            }
+            match mask.find(|c| !"01X".contains(c)) {
+                Some(i) => Err(ParseError::at(input, &mask[i..], "expected 0, 1 or X")),
+                None => Ok(Line::Mask(mask)),
+            }
         } else {
-            let memloc = &l[l.find('[').unwrap() + 1..l.find(']').unwrap()];
-            let val = &l[l.find('=').unwrap() + 2..];
-            Line::MemSet(memloc.parse().unwrap(), val.parse().unwrap())
+            let (memloc, val) = l
+                .strip_prefix("mem[")
+                .and_then(|l| l.split_once("] = "))
+                .ok_or_else(|| ParseError::at(input, l, "expected mask = ... or mem[...] = ..."))?;
+            let memloc = parse::token(input, memloc)?;
+            if memloc >= MEM_SIZE {
+                let reason = format!("addresses are below {MEM_SIZE}");
+                return Err(ParseError::at(input, l, reason));
+            }
+            Ok(Line::MemSet(memloc, parse::token(input, val)?))
         }
     })
 }
 
 fn p1(lines: &[Line]) -> usize {
     let mut mask: BinaryMask = BinaryMask::default();
-    let mut mem = vec![0; 99047];
+    let mut mem = vec![0; MEM_SIZE];
     for l in lines.iter() {
         match l {
             Line::Mask(m) => mask = BinaryMask::from_str(m),
@@ -111,10 +125,20 @@ fn p2(lines: &[Line]) -> u64 {
     mem.drain().map(|(_k, v)| v).sum()
 }
 
-pub fn day14() -> (String, String) {
-    let lines = parse().collect::<Vec<_>>();
+pub struct Day14;
 
-    let p1 = p1(&lines);
-    let p2 = p2(&lines);
-    (p1.to_string(), p2.to_string())
+impl Solution for Day14 {
+    type Parsed<'a> = Vec<Line<'a>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input).collect()
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> String {
+        p1(lines).to_string()
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> String {
+        p2(lines).to_string()
+    }
 }
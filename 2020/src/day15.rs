@@ -1,25 +1,11 @@
-const INPUT: &str = include_str!("../input/day15.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 const ITERATIONS_P1: usize = 2020;
 const ITERATIONS_P2: usize = 30000000;
 
-fn parse() -> (Box<[isize; ITERATIONS_P2]>, usize, usize) {
-    let numbers: Vec<_> = INPUT
-        .trim()
-        .split(',')
-        .map(|x| x.parse().unwrap())
-        .collect();
-    let (&last_number, rest) = numbers.split_last().unwrap();
-    let mut number_timestamps = box [-1; ITERATIONS_P2];
-    for (n, i) in rest.iter().enumerate() {
-        number_timestamps[*i] = n as isize + 1;
-    }
-    (number_timestamps, numbers.len(), last_number)
-}
-
 fn run_until(
     round: &mut usize,
-    nb_timestamps: &mut Box<[isize; ITERATIONS_P2]>,
+    nb_timestamps: &mut [isize],
     last_nb: &mut usize,
     max_iter: usize,
 ) {
@@ -35,28 +21,75 @@ fn run_until(
     }
 }
 
-fn solve() -> (usize, usize) {
-    let (mut number_timestamps, mut round, mut last_number) = parse();
-
-    run_until(
-        &mut round,
-        &mut number_timestamps,
-        &mut last_number,
-        ITERATIONS_P1,
-    );
-    let p1 = last_number;
+/// The number spoken at round `nb_rounds` of the memory game
+fn play(numbers: &[usize], nb_rounds: usize) -> usize {
+    // the starting numbers are spoken first
+    if let Some(&number) = numbers.get(nb_rounds - 1) {
+        return number;
+    }
+    let (&last_number, rest) = numbers.split_last().unwrap();
+    let mut number_timestamps = vec![-1; nb_rounds.max(numbers.iter().max().unwrap() + 1)];
+    for (n, i) in rest.iter().enumerate() {
+        number_timestamps[*i] = n as isize + 1;
+    }
+    let mut round = numbers.len();
+    let mut last_number = last_number;
     run_until(
         &mut round,
         &mut number_timestamps,
         &mut last_number,
-        ITERATIONS_P2,
+        nb_rounds,
     );
-    let p2 = last_number;
-    (p1, p2)
+    last_number
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input
+            .trim()
+            .split(',')
+            .map(|x| match parse::token(input, x)? {
+                // `play` has a slot for every number up to the largest one
+                n if n > ITERATIONS_P2 => Err(ParseError::at(
+                    input,
+                    x,
+                    format!("{n} is over the {ITERATIONS_P2} rounds of the game"),
+                )),
+                n => Ok(n),
+            })
+            .collect()
+    }
+
+    fn part1(numbers: &Self::Parsed<'_>) -> String {
+        play(numbers, ITERATIONS_P1).to_string()
+    }
+
+    fn part2(numbers: &Self::Parsed<'_>) -> String {
+        play(numbers, ITERATIONS_P2).to_string()
+    }
 }
 
-pub fn day15() -> (String, String) {
-    let (p1, p2) = solve();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    (p1.to_string(), p2.to_string())
+    #[test]
+    fn test_parse() {
+        assert_eq!(Day15::parse("0,3,6\n"), Ok(vec![0, 3, 6]));
+        let error = Day15::parse("0,3,99999999999").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 5));
+        assert!(Day15::parse("").is_err());
+    }
+
+    #[test]
+    fn test_play() {
+        assert_eq!(play(&[0, 3, 6], 10), 0);
+        assert_eq!(play(&[0, 3, 6], 2020), 436);
+        let numbers: Vec<_> = (0..3000).rev().collect();
+        assert_eq!(play(&numbers, 2020), 980);
+    }
 }
@@ -1,8 +1,11 @@
-const INPUT: &str = include_str!("../input/day16.txt");
+use aoc_common::{parse, ParseError, Solution};
+
+/// The largest value of the notes, so that sums of values cannot overflow
+const MAX_VALUE: u64 = 1_000_000;
 
 fn p1_bis(
     nearby_tickets: impl Iterator<Item = Vec<u64>>,
-    schema: &[(&'static str, [[u64; 2]; 2])],
+    schema: &[(&str, [[u64; 2]; 2])],
 ) -> (Vec<Vec<u64>>, u64) {
     let mut invalid_sum = 0;
     let valid_tickets = nearby_tickets
@@ -24,7 +27,11 @@ fn p1_bis(
     (valid_tickets, invalid_sum)
 }
 
-fn p2(schema: Vec<(&str, [[u64; 2]; 2])>, ticket: Vec<u64>, nearby: Vec<Vec<u64>>) -> u64 {
+fn p2(
+    schema: &[(&str, [[u64; 2]; 2])],
+    ticket: &[u64],
+    nearby: Vec<Vec<u64>>,
+) -> Result<u64, &'static str> {
     let indexes: Vec<_> = schema
         .iter()
         .map(|(name, _)| name)
@@ -33,22 +40,22 @@ fn p2(schema: Vec<(&str, [[u64; 2]; 2])>, ticket: Vec<u64>, nearby: Vec<Vec<u64>
         .map(|(index, _)| index)
         .collect();
 
-    let confusion_matrix = p2_generate_confusion_matrix(&nearby, &schema);
-    let matching = p2_solve(&confusion_matrix);
+    let confusion_matrix = p2_generate_confusion_matrix(&nearby, schema);
+    let matching = p2_solve(&confusion_matrix).ok_or("the fields do not match one column each")?;
 
-    let ans: u64 = indexes
+    indexes
         .into_iter()
         .map(|i| ticket[matching.iter().position(|&x| x == i).unwrap()])
-        .product();
-    ans
+        .try_fold(1u64, |product, value| product.checked_mul(value))
+        .ok_or("the product of the departure fields overflows")
 }
 
 fn p2_generate_confusion_matrix(
     nearby: &[Vec<u64>],
     schema: &[(&str, [[u64; 2]; 2])],
 ) -> Vec<Vec<bool>> {
-    let columns = p2_transpose(&nearby);
     let m = schema.len();
+    let columns = p2_transpose(nearby, m);
     let mut ans = vec![vec![false; m]; m];
     for i in 0..m {
         for j in 0..m {
@@ -60,9 +67,7 @@ fn p2_generate_confusion_matrix(
     ans
 }
 
-fn p2_transpose(nearby: &[Vec<u64>]) -> Vec<Vec<u64>> {
-    let m = nearby[0].len();
-
+fn p2_transpose(nearby: &[Vec<u64>], m: usize) -> Vec<Vec<u64>> {
     let mut ans = vec![Vec::new(); m];
     for numbers in nearby {
         for (i, &number) in numbers.iter().enumerate() {
@@ -80,7 +85,8 @@ fn p2_check(numbers: &[u64], intervals: &[[u64; 2]]) -> bool {
     })
 }
 
-fn p2_solve(valid: &[Vec<bool>]) -> Vec<usize> {
+/// The field of each column, `None` if a column cannot be told to be a single field
+fn p2_solve(valid: &[Vec<bool>]) -> Option<Vec<usize>> {
     let mut valid = valid.to_owned();
 
     let mut ans = vec![999_999; valid.len()];
@@ -102,57 +108,113 @@ fn p2_solve(valid: &[Vec<bool>]) -> Vec<usize> {
             .filter(|(_, x)| **x)
             .map(|(i, _)| i)
             .collect();
-        if indexes.len() > 1 {
-            unreachable!(format!("{:?}", indexes));
+        if indexes.len() != 1 {
+            return None;
         }
-        for i in 0..valid.len() {
-            valid[i][indexes[0]] = false;
+        for v in valid.iter_mut() {
+            v[indexes[0]] = false;
         }
         ans[i] = indexes[0];
     }
 
-    ans
+    Some(ans)
 }
 
-pub fn day16() -> (String, String) {
-    let mut sections = INPUT.trim().split("\n\n");
-
-    let schema: Vec<_> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| {
-            let mut t = l.split(": ");
-            let name = t.next().unwrap();
-            let mut values = [[0; 2]; 2];
-            for (i, range) in t.next().unwrap().split(" or ").enumerate() {
-                for (j, nb) in range.split('-').enumerate() {
-                    values[i][j] = nb.parse::<u64>().unwrap();
-                }
-            }
-            (name, values)
-        })
-        .collect();
+/// The rules for ticket fields, your ticket and nearby tickets
+pub struct Notes<'a> {
+    schema: Vec<(&'a str, [[u64; 2]; 2])>,
+    my_ticket: Vec<u64>,
+    nearby_tickets: Vec<Vec<u64>>,
+}
 
-    let my_ticket: Vec<u64> = sections
-        .next()
-        .unwrap()
-        .lines()
-        .nth(1)
-        .unwrap()
+/// Parses a value, up to [`MAX_VALUE`]
+fn parse_value(input: &str, value: &str) -> Result<u64, ParseError> {
+    match parse::token(input, value)? {
+        n if n > MAX_VALUE => Err(ParseError::at(
+            input,
+            value,
+            format!("expected a value up to {MAX_VALUE}"),
+        )),
+        n => Ok(n),
+    }
+}
+
+/// Parses a `1-3 or 5-7` pair of ranges
+fn parse_ranges(input: &str, ranges: &str) -> Result<[[u64; 2]; 2], ParseError> {
+    let invalid = || ParseError::at(input, ranges, "expected ranges, like 1-3 or 5-7");
+    let (a, b) = ranges.split_once(" or ").ok_or_else(invalid)?;
+    let mut values = [[0; 2]; 2];
+    for (i, range) in [a, b].iter().enumerate() {
+        let (start, end) = range.split_once('-').ok_or_else(invalid)?;
+        values[i] = [parse_value(input, start)?, parse_value(input, end)?];
+    }
+    Ok(values)
+}
+
+/// Parses a ticket, that must have a value per field
+fn parse_ticket(input: &str, line: &str, fields: usize) -> Result<Vec<u64>, ParseError> {
+    let ticket = line
         .split(',')
-        .map(|nb| nb.parse().unwrap())
-        .collect();
+        .map(|nb| parse_value(input, nb))
+        .collect::<Result<Vec<_>, _>>()?;
+    if ticket.len() != fields {
+        let reason = format!("expected {fields} values, as many as fields");
+        return Err(ParseError::at(input, line, reason));
+    }
+    Ok(ticket)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed<'a> = Notes<'a>;
 
-    let nearby_tickets = sections
-        .next()
-        .unwrap()
-        .lines()
-        .skip(1)
-        .map(|l| l.split(',').map(|nb| nb.parse().unwrap()).collect());
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let mut sections = input.trim().split("\n\n");
 
-    let (nearby_tickets, p1) = p1_bis(nearby_tickets, &schema);
-    let p2 = p2(schema, my_ticket, nearby_tickets);
+        let schema: Vec<_> = parse::next(input, &mut sections, "the fields")?
+            .lines()
+            .map(|l| {
+                let (name, ranges) = l.split_once(": ").ok_or_else(|| {
+                    ParseError::at(input, l, "expected a field, like name: 1-3 or 5-7")
+                })?;
+                Ok((name, parse_ranges(input, ranges)?))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let mut mine = parse::next(input, &mut sections, "your ticket")?
+            .lines()
+            .skip(1);
+        let my_ticket = parse_ticket(
+            input,
+            parse::next(input, &mut mine, "your ticket")?,
+            schema.len(),
+        )?;
+
+        let nearby_tickets = parse::next(input, &mut sections, "the nearby tickets")?
+            .lines()
+            .skip(1)
+            .map(|l| parse_ticket(input, l, schema.len()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Notes {
+            schema,
+            my_ticket,
+            nearby_tickets,
+        })
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part1(notes: &Self::Parsed<'_>) -> String {
+        p1_bis(notes.nearby_tickets.iter().cloned(), &notes.schema)
+            .1
+            .to_string()
+    }
+
+    fn part2(notes: &Self::Parsed<'_>) -> String {
+        let (nearby_tickets, _) = p1_bis(notes.nearby_tickets.iter().cloned(), &notes.schema);
+        match p2(&notes.schema, &notes.my_ticket, nearby_tickets) {
+            Ok(product) => product.to_string(),
+            Err(reason) => reason.to_string(),
+        }
+    }
 }
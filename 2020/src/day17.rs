@@ -1,4 +1,4 @@
-const INPUT: &str = include_str!("../input/day17.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 const MAP_HALF_SIZE: usize = 13;
 const MAP_SIZE: usize = MAP_HALF_SIZE * 2;
@@ -20,10 +20,10 @@ impl Pt3 {
     const fn new(x: Idx, y: Idx, z: Idx) -> Self {
         Self { x, y, z }
     }
-    const fn to_1d(&self) -> usize {
+    const fn to_1d(self) -> usize {
         self.x as usize
-            + self.y as usize * MAP_SIZE as usize
-            + self.z as usize * (MAP_SIZE as usize).pow(2)
+            + self.y as usize * MAP_SIZE
+            + self.z as usize * MAP_SIZE.pow(2)
     }
 }
 
@@ -37,16 +37,19 @@ impl From<usize> for Pt3 {
 }
 
 #[derive(Debug, Clone)]
-struct GameOfLife3D {
+pub struct GameOfLife3D {
     map: BitVec,
 }
 
-impl GameOfLife3D {
-    pub fn default() -> Self {
+impl Default for GameOfLife3D {
+    fn default() -> Self {
         Self {
             map: bitvec![0; MAP_SIZE * MAP_SIZE * MAP_SIZE],
         }
     }
+}
+
+impl GameOfLife3D {
     pub fn from_map(input: &str) -> Self {
         let mut map = Self::default();
         let mut pt = Pt3::new(
@@ -119,11 +122,11 @@ impl Pt4 {
     const fn new(x: Idx, y: Idx, z: Idx, w: Idx) -> Self {
         Self { x, y, z, w }
     }
-    const fn to_1d(&self) -> usize {
+    const fn to_1d(self) -> usize {
         self.x as usize
-            + self.y as usize * MAP_SIZE as usize
-            + self.z as usize * (MAP_SIZE as usize).pow(2)
-            + self.w as usize * (MAP_SIZE as usize).pow(3)
+            + self.y as usize * MAP_SIZE
+            + self.z as usize * MAP_SIZE.pow(2)
+            + self.w as usize * MAP_SIZE.pow(3)
     }
 }
 
@@ -150,17 +153,19 @@ impl Display for Pt4 {
 }
 
 #[derive(Debug, Clone)]
-struct GameOfLife4D {
+pub struct GameOfLife4D {
     map: BitVec,
 }
 
-impl GameOfLife4D {
-    pub fn default() -> Self {
+impl Default for GameOfLife4D {
+    fn default() -> Self {
         Self {
             map: bitvec![0; MAP_SIZE * MAP_SIZE.pow(2) * MAP_SIZE.pow(3)],
         }
     }
+}
 
+impl GameOfLife4D {
     pub fn from_map(input: &str) -> Self {
         let mut map = Self::default();
         let mut pt = Pt4::new(
@@ -238,15 +243,33 @@ impl GameOfLife4D {
     }
 }
 
-pub fn day17() -> (String, String) {
-    let mut gol_3d = GameOfLife3D::from_map(INPUT);
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed<'a> = (GameOfLife3D, GameOfLife4D);
 
-    (0..6).for_each(|_| gol_3d.run_cycle());
-    let p1 = gol_3d.nb_live();
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let rows = parse::grid(input, ".#")?;
+        // the initial slice is put in the middle of the bounded pocket dimension
+        if rows.len() > MAP_HALF_SIZE || rows[0].len() > MAP_HALF_SIZE {
+            let reason = format!("expected at most {MAP_HALF_SIZE}x{MAP_HALF_SIZE} cubes");
+            return Err(ParseError::at(input, input, reason));
+        }
+        Ok((
+            GameOfLife3D::from_map(input),
+            GameOfLife4D::from_map(input),
+        ))
+    }
 
-    let mut gol_4d = GameOfLife4D::from_map(INPUT);
-    (0..6).for_each(|_| gol_4d.run_cycle());
-    let p2 = gol_4d.nb_live();
+    fn part1((gol_3d, _): &Self::Parsed<'_>) -> String {
+        let mut gol_3d = gol_3d.clone();
+        (0..6).for_each(|_| gol_3d.run_cycle());
+        gol_3d.nb_live().to_string()
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part2((_, gol_4d): &Self::Parsed<'_>) -> String {
+        let mut gol_4d = gol_4d.clone();
+        (0..6).for_each(|_| gol_4d.run_cycle());
+        gol_4d.nb_live().to_string()
+    }
 }
@@ -1,8 +1,8 @@
 //
 // This code is disgusting
 //
+use aoc_common::{ParseError, Solution};
 
-const INPUT: &str = include_str!("../input/day18.txt");
 
 use std::io::Write;
 
@@ -34,7 +34,7 @@ fn do_maffs_p1(slice: &mut str) -> i64 {
         match tok {
             "+" => res += next_nb!(it),
             "*" => res *= next_nb!(it),
-            _ => unreachable!(tok),
+            _ => unreachable!("{}", tok),
         };
     }
     res
@@ -121,8 +121,8 @@ fn do_maffs_p2(slice: &mut str) -> i64 {
     res
 }
 
-fn p1() -> i64 {
-    INPUT
+fn p1(input: &str) -> i64 {
+    input
         .lines()
         .map(|line| {
             let mut l2 = line.trim().to_owned();
@@ -131,8 +131,8 @@ fn p1() -> i64 {
         .sum()
 }
 
-fn p2() -> i64 {
-    INPUT
+fn p2(input: &str) -> i64 {
+    input
         .lines()
         .map(|line| {
             let mut l2 = line.trim().to_owned();
@@ -141,9 +141,57 @@ fn p2() -> i64 {
         .sum()
 }
 
-pub fn day18() -> (String, String) {
-    let p1 = p1();
-    let p2 = p2();
+/// Checks that `line` is an expression the evaluators can handle: numbers and operators separated
+/// by spaces, with balanced parentheses
+fn check_expression(input: &str, line: &str) -> Result<(), ParseError> {
+    let mut depth = 0usize;
+    let mut expect_operand = true;
+    for tok in line.trim().split(' ') {
+        let error = |reason| Err(ParseError::at(input, tok, reason));
+        if expect_operand {
+            let nb = tok.trim_start_matches('(');
+            depth += tok.len() - nb.len();
+            let closed = nb.trim_end_matches(')');
+            let closing = nb.len() - closed.len();
+            if closed.is_empty() || !closed.bytes().all(|c| c.is_ascii_digit()) {
+                return error("expected a number");
+            }
+            if closing > depth {
+                return error("unmatched ')'");
+            }
+            depth -= closing;
+        } else if tok != "+" && tok != "*" {
+            return error("expected + or *");
+        }
+        expect_operand = !expect_operand;
+    }
+    if expect_operand {
+        return Err(ParseError::at(input, &line[line.len()..], "expected a number"));
+    }
+    if depth > 0 {
+        return Err(ParseError::at(input, line, "unmatched '('"));
+    }
+    Ok(())
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed<'a> = &'a str;
 
-    (p1.to_string(), p2.to_string())
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // the expressions are evaluated as they are parsed, they are only checked here
+        for line in input.lines() {
+            check_expression(input, line)?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> String {
+        p1(input).to_string()
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> String {
+        p2(input).to_string()
+    }
 }
@@ -1,45 +1,72 @@
-const INPUT: &str = include_str!("../input/day19.txt");
+use aoc_common::{parse, ParseError, Solution};
 
 const TOK_MAX_ID: usize = 150;
 
-fn parse() -> (Vec<Token>, Vec<&'static str>) {
-    let mut sep = INPUT.split("\n\n");
-
-    let rules = {
-        let mut rules = vec![Token::Char(b'\0'); TOK_MAX_ID];
-        sep.next().unwrap().lines().for_each(|line| {
-            let mut sep = line.split(": ");
-            let id: usize = sep.next().unwrap().parse().unwrap();
-            let rules_str = sep.next().unwrap().trim();
-            rules[id] = if rules_str.starts_with('"') {
-                Token::Char(rules_str.as_bytes()[1])
-            } else {
-                let mut rules_sep = rules_str.split('|');
-                let rules0 = rules_sep
-                    .next()
-                    .unwrap()
-                    .trim()
-                    .split(' ')
-                    .map(|nb| nb.parse().unwrap())
-                    .collect();
-                let rules1 = rules_sep.next().map(|r| {
-                    r.trim()
-                        .split(' ')
-                        .map(|nb| nb.parse().unwrap())
-                        .collect::<Vec<_>>()
-                });
-                Token::Rules((rules0, rules1))
-            };
-        });
-        rules
-    };
-    let data = sep.next().unwrap().trim().lines().collect();
+/// Parses a rule id, that must be below [`TOK_MAX_ID`]
+fn parse_id(input: &str, id: &str) -> Result<usize, ParseError> {
+    match parse::token(input, id)? {
+        id if id < TOK_MAX_ID => Ok(id),
+        _ => Err(ParseError::at(
+            input,
+            id,
+            format!("ids are below {TOK_MAX_ID}"),
+        )),
+    }
+}
 
-    (rules, data)
+/// Parses a sequence of rule ids, like `4 1 5`
+fn parse_ids(input: &str, ids: &str) -> Result<Vec<usize>, ParseError> {
+    ids.trim()
+        .split(' ')
+        .map(|nb| parse_id(input, nb))
+        .collect()
+}
+
+fn parse(input: &str) -> Result<(Vec<Token>, Vec<&str>), ParseError> {
+    let mut sep = input.split("\n\n");
+
+    let mut rules = vec![Token::Char(b'\0'); TOK_MAX_ID];
+    for line in parse::next(input, &mut sep, "the rules")?.lines() {
+        let (id, rules_str) = line
+            .split_once(": ")
+            .ok_or_else(|| ParseError::at(input, line, "expected a rule, like 0: 4 1 5"))?;
+        let id = parse_id(input, id)?;
+        let rules_str = rules_str.trim();
+        rules[id] = if rules_str.starts_with('"') {
+            match rules_str.as_bytes() {
+                [b'"', c, b'"'] => Token::Char(*c),
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        rules_str,
+                        "expected a character, like \"a\"",
+                    ))
+                }
+            }
+        } else {
+            let mut rules_sep = rules_str.split('|');
+            let rules0 = parse_ids(input, parse::next(input, &mut rules_sep, "a rule")?)?;
+            let rules1 = rules_sep.next().map(|r| parse_ids(input, r)).transpose()?;
+            if let Some(extra) = rules_sep.next() {
+                return Err(ParseError::at(
+                    input,
+                    extra,
+                    "expected at most two alternatives",
+                ));
+            }
+            Token::Rules((rules0, rules1))
+        };
+    }
+    let data = parse::next(input, &mut sep, "the messages")?
+        .trim()
+        .lines()
+        .collect();
+
+    Ok((rules, data))
 }
 
 #[derive(Clone, Debug)]
-enum Token {
+pub enum Token {
     Char(u8),
     Rules((Vec<usize>, Option<Vec<usize>>)),
 }
@@ -65,8 +92,7 @@ impl Token {
             let tok = &rules[*i];
             tmp_res = tmp_res
                 .drain(..)
-                .map(|tmp_idx| tok.inner_matches(rules, data, tmp_idx))
-                .flatten()
+                .flat_map(|tmp_idx| tok.inner_matches(rules, data, tmp_idx))
                 .collect();
             !tmp_res.is_empty()
         });
@@ -110,22 +136,29 @@ impl Token {
 }
 
 fn p1(rules: &[Token], data: &[&str]) -> u64 {
-    data.iter().filter(|d| rules[0].matches(&rules, d)).count() as u64
+    data.iter().filter(|d| rules[0].matches(rules, d)).count() as u64
 }
 
 fn p2(rules: &mut [Token], data: &[&str]) -> u64 {
     rules[8] = Token::Rules((vec![42], Some(vec![42, 8])));
     rules[11] = Token::Rules((vec![42, 31], Some(vec![42, 11, 31])));
-    data.iter().filter(|d| rules[0].matches(&rules, d)).count() as u64
+    data.iter().filter(|d| rules[0].matches(rules, d)).count() as u64
 }
 
-pub fn day19() -> (String, String) {
-    let (mut rules, data) = parse();
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = (Vec<Token>, Vec<&'a str>);
 
-    // let p1 = "";
-    // let p2 = "";
-    let p1 = p1(&rules, &data);
-    let p2 = p2(&mut rules, &data);
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1((rules, data): &Self::Parsed<'_>) -> String {
+        p1(rules, data).to_string()
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part2((rules, data): &Self::Parsed<'_>) -> String {
+        p2(&mut rules.clone(), data).to_string()
+    }
 }
@@ -2,7 +2,7 @@
 // I cheated a bit on this one
 // No way I was doing the sea monter part without external help
 //
-const INPUT: &str = include_str!("../input/day20.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::ops::{Add, Not};
@@ -149,7 +149,7 @@ type TileId = u64;
 type EdgeChecksum = usize;
 
 #[derive(Clone)]
-struct Tile {
+pub struct Tile {
     id: TileId,
     image: Image,
     edges_checksums: [EdgeChecksum; 4],
@@ -198,20 +198,36 @@ impl Tile {
     }
 }
 
-fn parse_tile(tile_str: &str) -> Tile {
-    let id_str = tile_str.lines().next().unwrap();
-    let id = id_str[5..id_str.len() - 1].parse::<TileId>().unwrap();
+/// Parses `tile_str`, a tile of `input`: a `Tile <id>:` header above 10x10 pixels
+fn parse_tile(input: &str, tile_str: &str) -> Result<Tile, ParseError> {
+    let id_str = tile_str.lines().next().unwrap_or(tile_str);
+    let id = id_str
+        .strip_prefix("Tile ")
+        .and_then(|id| id.strip_suffix(':'))
+        .ok_or_else(|| ParseError::at(input, id_str, "expected a tile header, like Tile 42:"))?;
+    let id = parse::token::<TileId>(input, id)?;
+    let rows: Vec<&str> = tile_str.lines().skip(1).collect();
+    if rows.len() != TILE_SIZE as usize {
+        return Err(ParseError::at(input, id_str, format!("expected {TILE_SIZE} rows of pixels")));
+    }
+    for row in rows {
+        if row.len() != TILE_SIZE as usize {
+            return Err(ParseError::at(input, row, format!("expected {TILE_SIZE} pixels")));
+        }
+        if let Some(i) = row.find(|c| c != '#' && c != '.') {
+            return Err(ParseError::at(input, &row[i..], "expected '#' or '.'"));
+        }
+    }
 
     let pixels: HashMap<Pos, Pixel> = tile_str
         .lines()
         .skip(1)
         .enumerate()
-        .map(move |(y, line)| {
+        .flat_map(move |(y, line)| {
             line.chars()
                 .enumerate()
                 .map(move |(x, c)| ((x as i32, y as i32).into(), c == '#'))
         })
-        .flatten()
         .collect();
 
     let edges_checksums = edges_checksums(&pixels);
@@ -227,25 +243,25 @@ fn parse_tile(tile_str: &str) -> Tile {
         })
         .collect();
 
-    Tile {
+    Ok(Tile {
         id,
         image: Image(pixels).normalize(),
         edges_checksums,
         flipped_edges_checksums,
-    }
+    })
 }
 
 fn edges_checksums(pixels: &HashMap<Pos, Pixel>) -> [EdgeChecksum; 4] {
     let upper_checksum = pixels
         .iter()
         .filter(|(&position, _)| position.y == 0)
-        .map(|(position, &pixel)| (2_usize.pow(position.x as u32) * (pixel as EdgeChecksum)))
+        .map(|(position, &pixel)| 2_usize.pow(position.x as u32) * (pixel as EdgeChecksum))
         .sum::<EdgeChecksum>();
 
     let right_checksum = pixels
         .iter()
         .filter(|(&position, _)| position.x == (TILE_SIZE - 1))
-        .map(|(position, &pixel)| (2_usize.pow(position.y as u32) * (pixel as EdgeChecksum)))
+        .map(|(position, &pixel)| 2_usize.pow(position.y as u32) * (pixel as EdgeChecksum))
         .sum::<EdgeChecksum>();
 
     let lower_checksum = pixels
@@ -292,13 +308,13 @@ fn flipped_edges_checksums(pixels: &HashMap<Pos, Pixel>) -> [EdgeChecksum; 4] {
     let lower_flipped_checksum = pixels
         .iter()
         .filter(|(&position, _)| position.y == (TILE_SIZE - 1))
-        .map(|(position, &pixel)| (2_usize.pow(position.x as u32) * (pixel as EdgeChecksum)))
+        .map(|(position, &pixel)| 2_usize.pow(position.x as u32) * (pixel as EdgeChecksum))
         .sum::<EdgeChecksum>();
 
     let left_flipped_checksum = pixels
         .iter()
         .filter(|(&position, _)| position.x == 0)
-        .map(|(position, &pixel)| (2_usize.pow(position.y as u32) * (pixel as EdgeChecksum)))
+        .map(|(position, &pixel)| 2_usize.pow(position.y as u32) * (pixel as EdgeChecksum))
         .sum::<EdgeChecksum>();
 
     [
@@ -309,12 +325,12 @@ fn flipped_edges_checksums(pixels: &HashMap<Pos, Pixel>) -> [EdgeChecksum; 4] {
     ]
 }
 
-fn parse_tiles(input: &str) -> HashMap<TileId, Tile> {
+fn parse_tiles(input: &str) -> Result<HashMap<TileId, Tile>, ParseError> {
     input
         .split("\n\n")
         .map(|tile_str| {
-            let tile = parse_tile(tile_str);
-            (tile.id, tile)
+            let tile = parse_tile(input, tile_str)?;
+            Ok((tile.id, tile))
         })
         .collect()
 }
@@ -324,12 +340,12 @@ fn parse_edges<'a>(tiles: impl Iterator<Item = &'a Tile>) -> HashMap<EdgeChecksu
 
     for tile in tiles {
         for edge in tile.edges_checksums.iter() {
-            let edge_tiles = edges.entry(*edge).or_insert_with(Vec::new);
+            let edge_tiles = edges.entry(*edge).or_default();
             edge_tiles.push(tile.id);
         }
 
         for edge in tile.flipped_edges_checksums.iter() {
-            let edge_tiles = edges.entry(*edge).or_insert_with(Vec::new);
+            let edge_tiles = edges.entry(*edge).or_default();
             edge_tiles.push(tile.id);
         }
     }
@@ -337,11 +353,19 @@ fn parse_edges<'a>(tiles: impl Iterator<Item = &'a Tile>) -> HashMap<EdgeChecksu
     edges
 }
 
-fn parse(input: &str) -> (HashMap<TileId, Tile>, HashMap<EdgeChecksum, Vec<TileId>>) {
-    let tiles = parse_tiles(input);
+type TilesAndEdges = (HashMap<TileId, Tile>, HashMap<EdgeChecksum, Vec<TileId>>);
+
+fn parse(input: &str) -> Result<TilesAndEdges, ParseError> {
+    let tiles = parse_tiles(input)?;
+    // part 2 assembles the tiles in a square image
+    let side = tiles.len().isqrt();
+    if tiles.is_empty() || side * side != tiles.len() {
+        let reason = format!("expected a square number of tiles, found {}", tiles.len());
+        return Err(ParseError::at(input, "", reason));
+    }
     let edges = parse_edges(tiles.values());
 
-    (tiles, edges)
+    Ok((tiles, edges))
 }
 
 fn part1((_tiles, edges): &(HashMap<TileId, Tile>, HashMap<EdgeChecksum, Vec<TileId>>)) -> u64 {
@@ -431,12 +455,12 @@ fn assemble_image(image_pieces: HashMap<Pos, Image>) -> Image {
     for (large_position, tile_image) in image_pieces.iter() {
         for x in 0..CROPPED_TILE_SIZE {
             for y in 0..CROPPED_TILE_SIZE {
-                let pixel = tile_image.0.get(&(x as i32, y as i32).into()).unwrap();
+                let pixel = tile_image.0.get(&(x, y).into()).unwrap();
 
                 image.0.insert(
                     (
-                        large_position.x * CROPPED_TILE_SIZE + (x - 1) as i32,
-                        large_position.y * CROPPED_TILE_SIZE + (y - 1) as i32,
+                        large_position.x * CROPPED_TILE_SIZE + (x - 1),
+                        large_position.y * CROPPED_TILE_SIZE + (y - 1),
                     )
                         .into(),
                     *pixel,
@@ -456,13 +480,12 @@ fn monster_pixels_positions() -> Vec<Pos> {
     MONSTER_PATTERN
         .lines()
         .enumerate()
-        .map(move |(y, line)| {
+        .flat_map(move |(y, line)| {
             line.chars()
                 .enumerate()
                 .filter(move |(_, c)| *c == '#')
                 .map(move |(x, _)| (x as i32, y as i32).into())
         })
-        .flatten()
         .collect()
 }
 
@@ -525,18 +548,7 @@ fn part2(tiles_and_edges: &(HashMap<TileId, Tile>, HashMap<EdgeChecksum, Vec<Til
     image.0.values().filter(|&value| *value).count() - monsters_count * monster_pixels.len()
 }
 
-pub fn day20() -> (String, String) {
-    let parsed = parse(INPUT);
-    let p1 = part1(&parsed);
-    let p2 = part2(&parsed);
-    (p1.to_string(), p2.to_string())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const TEST_INPUT: &str = concat!(
+const TEST_INPUT: &str = concat!(
         "Tile 2311:\n..##.#..#.\n##..#.....\n#...##..#.\n####.#...#\n##.##.###.\n##...#.###\n.#.#.#..##\n..#....#..\n###...#.#.\n..###..###\n\n",
         "Tile 1951:\n#.##...##.\n#.####...#\n.....#..##\n#...######\n.##.#....#\n.###.#####\n###.##.##.\n.###....#.\n..#.#..#.#\n#...##.#..\n\n",
         "Tile 1171:\n####...##.\n#..##.#..#\n##.#..#.#.\n.###.####.\n..###.####\n.##....##.\n.#...####.\n#.##.####.\n####..#...\n.....##...\n\n",
@@ -548,13 +560,41 @@ mod tests {
         "Tile 3079:\n#.#.#####.\n.#..######\n..#.......\n######....\n####.#..#.\n.#...#.##.\n#.#####.##\n..#.###...\n..#.......\n..#.###..."
     );
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed<'a> = (HashMap<TileId, Tile>, HashMap<EdgeChecksum, Vec<TileId>>);
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("20899048083289"),
+        part2: Some("273"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(tiles_and_edges: &Self::Parsed<'_>) -> String {
+        part1(tiles_and_edges).to_string()
+    }
+
+    fn part2(tiles_and_edges: &Self::Parsed<'_>) -> String {
+        part2(tiles_and_edges).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn part1_example() {
-        assert_eq!(part1(&parse(TEST_INPUT)), 20_899_048_083_289);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 20_899_048_083_289);
     }
 
     #[test]
     fn part2_example() {
-        assert_eq!(part2(&parse(TEST_INPUT)), 273);
+        assert_eq!(part2(&parse(TEST_INPUT).unwrap()), 273);
     }
 }
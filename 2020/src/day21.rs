@@ -1,15 +1,14 @@
+use aoc_common::{Example, ParseError, Solution};
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../input/day21.txt");
-
-struct Food {
-    pub ingredients: Vec<&'static str>,
-    pub allergens: Vec<&'static str>,
+pub struct Food<'a> {
+    pub ingredients: Vec<&'a str>,
+    pub allergens: Vec<&'a str>,
 }
 
-impl Food {
-    pub fn new(ingredients: Vec<&'static str>, allergens: Vec<&'static str>) -> Self {
+impl<'a> Food<'a> {
+    pub fn new(ingredients: Vec<&'a str>, allergens: Vec<&'a str>) -> Self {
         Self {
             ingredients,
             allergens,
@@ -17,26 +16,31 @@ impl Food {
     }
 }
 
-fn parse(input: &'static str) -> Vec<Food> {
+fn parse(input: &str) -> Result<Vec<Food<'_>>, ParseError> {
     input
         .lines()
         .map(|food| {
-            let sep = food.find(" (contains ").unwrap();
-            let ingredients = food[..sep].split(' ').collect();
-            let allergens = food[sep + 11..food.len() - 1].split(", ").collect();
-            Food::new(ingredients, allergens)
+            let (ingredients, allergens) = food
+                .strip_suffix(')')
+                .and_then(|food| food.split_once(" (contains "))
+                .ok_or_else(|| {
+                    ParseError::at(input, food, "expected a food, like a b (contains c, d)")
+                })?;
+            let ingredients = ingredients.split(' ').collect();
+            let allergens = allergens.split(", ").collect();
+            Ok(Food::new(ingredients, allergens))
         })
         .collect()
 }
 
-fn allergen_to_maybe_ingredients(foods: &[Food]) -> HashMap<&str, Vec<&str>> {
+fn allergen_to_maybe_ingredients<'a>(foods: &[Food<'a>]) -> HashMap<&'a str, Vec<&'a str>> {
     let mut allergenics_ingredients = HashMap::new();
     for f in foods {
         for allergen in f.allergens.iter() {
             allergenics_ingredients
                 .entry(*allergen)
                 .and_modify(|sus_ingredients: &mut Vec<&str>| {
-                    sus_ingredients.retain(|ing| f.ingredients.contains(&ing));
+                    sus_ingredients.retain(|ing| f.ingredients.contains(ing));
                 })
                 .or_insert(f.ingredients.clone());
         }
@@ -44,19 +48,16 @@ fn allergen_to_maybe_ingredients(foods: &[Food]) -> HashMap<&str, Vec<&str>> {
     allergenics_ingredients
 }
 
-fn allergens_and_inert_ingredients(foods: &[Food]) -> (HashMap<&str, Vec<&str>>, Vec<&str>) {
-    let mut allergen_ingredients = allergen_to_maybe_ingredients(&foods);
-    let maybe_allergenic_ingredients: HashSet<_> = allergen_ingredients
-        .iter()
-        .map(|(_allergen, ingredients)| ingredients.into_iter())
-        .flatten()
+fn allergens_and_inert_ingredients<'a>(
+    foods: &[Food<'a>],
+) -> (HashMap<&'a str, Vec<&'a str>>, Vec<&'a str>) {
+    let mut allergen_ingredients = allergen_to_maybe_ingredients(foods);
+    let maybe_allergenic_ingredients: HashSet<_> = allergen_ingredients.values().flat_map(|ingredients| ingredients.iter())
         .collect();
     let innert_ingredients: Vec<&str> = foods
         .iter()
-        .map(|f| f.ingredients.iter())
-        .flatten()
-        .filter(|ing| !maybe_allergenic_ingredients.contains(*ing))
-        .map(|i| *i)
+        .flat_map(|f| f.ingredients.iter())
+        .filter(|ing| !maybe_allergenic_ingredients.contains(*ing)).copied()
         .collect();
     allergen_ingredients.iter_mut().for_each(|(_al, ings)| {
         ings.retain(|ing| !innert_ingredients.contains(ing));
@@ -89,7 +90,7 @@ fn part2(mut allergens: HashMap<&str, Vec<&str>>) -> String {
         let mut allergen_to_remove = "";
         for (a, ings) in allergens.iter_mut() {
             ings.retain(|i| *i != last_used);
-            if ings.len() == 1 && new_used == "" {
+            if ings.len() == 1 && new_used.is_empty() {
                 new_used = ings[0];
                 allergen_ingredient.push((*a, ings[0]));
                 allergen_to_remove = a;
@@ -103,37 +104,53 @@ fn part2(mut allergens: HashMap<&str, Vec<&str>>) -> String {
     allergen_ingredient.into_iter().map(|(_a, i)| i).join(",")
 }
 
-pub fn day21() -> (String, String) {
-    let foods = parse(INPUT);
-    let (allergens, innert_ingredients) = allergens_and_inert_ingredients(&foods);
+const TEST_INPUT: &str = concat!(
+        "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\n",
+        "trh fvjkl sbzzf mxmxvkd (contains dairy)\n",
+        "sqjhc fvjkl (contains soy)\n",
+        "sqjhc mxmxvkd sbzzf (contains fish)\n"
+    );
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed<'a> = Vec<Food<'a>>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("5"),
+        part2: Some("mxmxvkd,sqjhc,fvjkl"),
+    }];
 
-    let p1 = part1(&foods, innert_ingredients);
-    let p2 = part2(allergens);
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(foods: &Self::Parsed<'_>) -> String {
+        let (_, innert_ingredients) = allergens_and_inert_ingredients(foods);
+        part1(foods, innert_ingredients).to_string()
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part2(foods: &Self::Parsed<'_>) -> String {
+        let (allergens, _) = allergens_and_inert_ingredients(foods);
+        part2(allergens)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = concat!(
-        "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)\n",
-        "trh fvjkl sbzzf mxmxvkd (contains dairy)\n",
-        "sqjhc fvjkl (contains soy)\n",
-        "sqjhc mxmxvkd sbzzf (contains fish)\n"
-    );
-
     #[test]
     fn test_p1() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(TEST_INPUT).unwrap();
         let (_, innert_ingredients) = allergens_and_inert_ingredients(&parsed);
         assert!(part1(&parsed, innert_ingredients) == 5);
     }
 
     #[test]
     fn test_p2() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(TEST_INPUT).unwrap();
         let (allergens, _) = allergens_and_inert_ingredients(&parsed);
         assert!(part2(allergens) == "mxmxvkd,sqjhc,fvjkl");
     }
@@ -1,39 +1,57 @@
+use aoc_common::{parse, Example, ParseError, Rng, Solution};
 use fnv::FnvHasher;
 use std::collections::{HashSet, VecDeque};
 use std::convert::TryInto;
 use std::hash::{Hash, Hasher};
 
-const INPUT: &str = include_str!("../input/day22.txt");
-
-type Decks = [VecDeque<u8>; 2];
+type Card = u32;
+type Decks = [VecDeque<Card>; 2];
 
 // top of deck == front of vecdeque
-fn parse(input: &str) -> Decks {
-    let vec: Vec<_> = input
+fn parse(input: &str) -> Result<Decks, ParseError> {
+    let mut dealt = HashSet::new();
+    let vec = input
+        .trim_end()
         .split("\n\n")
-        .map(|player| {
-            player
-                .lines()
-                .skip(1)
-                .map(|card| card.parse().unwrap())
+        .enumerate()
+        .map(|(i, player)| {
+            let header = format!("Player {}:", i + 1);
+            let mut lines = player.lines();
+            if lines.next() != Some(header.as_str()) {
+                return Err(ParseError::at(input, player, format!("expected {header}")));
+            }
+            lines
+                .map(|card| match parse::token::<Card>(input, card)? {
+                    0 => Err(ParseError::at(input, card, "expected a card above 0")),
+                    c if !dealt.insert(c) => Err(ParseError::at(
+                        input,
+                        card,
+                        format!("card {c} is dealt twice"),
+                    )),
+                    c => Ok(c),
+                })
                 .collect()
         })
-        .collect();
-    let res: Decks = vec.try_into().unwrap();
-    res
+        .collect::<Result<Vec<_>, _>>()?;
+    vec.try_into()
+        .map_err(|_| ParseError::at(input, input, "expected the decks of two players"))
+}
+
+fn play_round(decks: &mut Decks) {
+    let p1_card = decks[0].pop_front().unwrap();
+    let p2_card = decks[1].pop_front().unwrap();
+    if p1_card > p2_card {
+        decks[0].push_back(p1_card);
+        decks[0].push_back(p2_card);
+    } else {
+        decks[1].push_back(p2_card);
+        decks[1].push_back(p1_card);
+    }
 }
 
 fn part1(mut decks: Decks) -> u64 {
     while !decks[0].is_empty() && !decks[1].is_empty() {
-        let p1_card = decks[0].pop_front().unwrap();
-        let p2_card = decks[1].pop_front().unwrap();
-        if p1_card > p2_card {
-            decks[0].push_back(p1_card);
-            decks[0].push_back(p2_card);
-        } else {
-            decks[1].push_back(p2_card);
-            decks[1].push_back(p1_card);
-        }
+        play_round(&mut decks);
     }
     let winner_deck = if decks[0].is_empty() {
         &mut decks[1]
@@ -65,19 +83,20 @@ fn recursive_combat(decks: &mut Decks) -> usize {
 
         let cards_drawn = [decks[0].pop_front().unwrap(), decks[1].pop_front().unwrap()];
 
-        let winner =
-            if cards_drawn[0] <= decks[0].len() as u8 && cards_drawn[1] <= decks[1].len() as u8 {
-                let mut new_decks = decks.clone();
-                new_decks[0].resize(cards_drawn[0] as usize, 0);
-                new_decks[1].resize(cards_drawn[1] as usize, 0);
-                recursive_combat(&mut new_decks)
+        let winner = if cards_drawn[0] as usize <= decks[0].len()
+            && cards_drawn[1] as usize <= decks[1].len()
+        {
+            let mut new_decks = decks.clone();
+            new_decks[0].resize(cards_drawn[0] as usize, 0);
+            new_decks[1].resize(cards_drawn[1] as usize, 0);
+            recursive_combat(&mut new_decks)
+        } else {
+            if cards_drawn[0] > cards_drawn[1] {
+                0
             } else {
-                if cards_drawn[0] > cards_drawn[1] {
-                    0
-                } else {
-                    1
-                }
-            };
+                1
+            }
+        };
         let looser = (winner + 1) % 2;
         decks[winner].push_back(cards_drawn[winner]);
         decks[winner].push_back(cards_drawn[looser]);
@@ -89,6 +108,18 @@ fn recursive_combat(decks: &mut Decks) -> usize {
     }
 }
 
+/// Whether a game of Combat ends, it has no rule against repeated rounds
+fn combat_ends(mut decks: Decks) -> bool {
+    let mut previous_states = HashSet::new();
+    while !decks[0].is_empty() && !decks[1].is_empty() {
+        if !previous_states.insert(decks_hash(&decks)) {
+            return false;
+        }
+        play_round(&mut decks);
+    }
+    true
+}
+
 fn part2(mut decks: Decks) -> u64 {
     let winner = recursive_combat(&mut decks);
     decks[winner]
@@ -99,42 +130,105 @@ fn part2(mut decks: Decks) -> u64 {
         .sum()
 }
 
-pub fn day22() -> (String, String) {
-    let decks = parse(INPUT);
+const TEST_INPUT: &str = concat!(
+    "Player 1:\n9\n2\n6\n3\n1\n\n",
+    "Player 2:\n5\n8\n4\n7\n10\n"
+);
 
-    let p1 = part1(decks.clone());
-    let p2 = part2(decks);
+pub struct Day22;
 
-    (p1.to_string(), p2.to_string())
+impl Solution for Day22 {
+    type Parsed<'a> = Decks;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("306"),
+        part2: Some("291"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(decks: &Self::Parsed<'_>) -> String {
+        part1(decks.clone()).to_string()
+    }
+
+    fn part2(decks: &Self::Parsed<'_>) -> String {
+        part2(decks.clone()).to_string()
+    }
+
+    /// Two decks of `size` cards, the cards from 1 to 2 * `size` shuffled (the puzzle has 25,
+    /// recursive combat gets slow quickly above)
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let mut cards: Vec<Card> = (1..=2 * size as Card).collect();
+        loop {
+            rng.shuffle(&mut cards);
+            let (one, two) = cards.split_at(size);
+            let decks = [one.iter().copied().collect(), two.iter().copied().collect()];
+            if !combat_ends(decks) {
+                continue;
+            }
+            let deck =
+                |cards: &[Card]| cards.iter().map(|c| format!("{}\n", c)).collect::<String>();
+            return Some(format!(
+                "Player 1:\n{}\nPlayer 2:\n{}",
+                deck(one),
+                deck(two)
+            ));
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = concat!(
-        "Player 1:\n9\n2\n6\n3\n1\n\n",
-        "Player 2:\n5\n8\n4\n7\n10\n"
-    );
+    const INPUT: &str = include_str!("../input/day22.txt");
 
     #[test]
     fn test_p1_example() {
-        let decks = parse(TEST_INPUT);
+        let decks = parse(TEST_INPUT).unwrap();
         assert!(part1(decks) == 306);
     }
     #[test]
     fn test_p1_challenge() {
-        let decks = parse(INPUT);
+        let decks = parse(INPUT).unwrap();
         assert!(part1(decks) == 33559);
     }
     #[test]
     fn test_p2_example() {
-        let decks = parse(TEST_INPUT);
+        let decks = parse(TEST_INPUT).unwrap();
         assert!(part2(decks) == 291);
     }
     #[test]
+    fn test_generate() {
+        let input = Day22::generate(20, &mut Rng::new(22)).unwrap();
+        assert_eq!(
+            Some(&input),
+            Day22::generate(20, &mut Rng::new(22)).as_ref()
+        );
+        let decks = parse(&input).unwrap();
+        assert_eq!(decks[0].len() + decks[1].len(), 40);
+        // the cards are all in the winner's deck
+        let scores = 1..=(1..=40).sum::<u64>() * 40;
+        assert!(scores.contains(&part1(decks.clone())));
+        assert!(scores.contains(&part2(decks)));
+        let decks = parse(&Day22::generate(100, &mut Rng::new(1)).unwrap()).unwrap();
+        assert_eq!((decks[0].len(), decks[1].len()), (100, 100));
+    }
+    #[test]
+    fn test_parse() {
+        assert!(parse(TEST_INPUT).is_ok());
+        assert!(parse(&TEST_INPUT.replace("Player 1:\n", "")).is_err());
+        assert!(parse(&TEST_INPUT.replace("Player 2", "Player 1")).is_err());
+        assert!(parse(&TEST_INPUT.replace("\n8\n", "\n0\n")).is_err());
+        assert!(parse(&TEST_INPUT.replace("\n8\n", "\n9\n")).is_err());
+        assert!(parse(&format!("{TEST_INPUT}\n")).is_ok());
+    }
+    #[test]
     fn test_p2_challenge() {
-        let decks = parse(INPUT);
+        let decks = parse(INPUT).unwrap();
         assert!(part2(decks) == 32789);
     }
 }
@@ -1,5 +1,3 @@
-const INPUT: &str = include_str!("../input/day23.txt");
-
 /// Ring buffer is represented as array with each idx = cup label,
 /// and value under that index/label is the index/label of the next cup.
 /// `[3, 8, 9, 1, 2, 5, 4, 6, 7]` becomes
@@ -7,7 +5,9 @@ const INPUT: &str = include_str!("../input/day23.txt");
 ///  6 => 7, 7 => 3, 8 => 9, 9 => 1]`.
 ///
 /// `self.ring[0]` is the currently selected cup
-struct Cups {
+use aoc_common::{Example, ParseError, Solution};
+
+pub struct Cups {
     ring: Vec<u32>,
 }
 
@@ -23,7 +23,7 @@ impl Cups {
             _ => cups.len(),
         };
         let mut s = Self {
-            ring: vec![std::u32::MAX; nb_cups + 1],
+            ring: vec![u32::MAX; nb_cups + 1],
         };
         //  Set head & body
         s.ring[0] = cups[0];
@@ -41,7 +41,7 @@ impl Cups {
                 next += 1;
             }
         } else {
-            s.ring[*cups.last().unwrap() as usize] = cups[0] as u32;
+            s.ring[*cups.last().unwrap() as usize] = cups[0];
         }
 
         s
@@ -110,10 +110,41 @@ pub fn part2(input: &str) -> usize {
     cups.two_cups_clockwise_of_one()
 }
 
-pub fn day23() -> (String, String) {
-    let p1 = part1(INPUT.trim(), 100);
-    let p2 = part2(INPUT.trim());
-    (p1.to_string(), p2.to_string())
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Parsed<'a> = &'a str;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "389125467",
+        part1: Some("67384529"),
+        part2: Some("149245887792"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // the cups are laid out differently for each part, they are only checked here
+        let cups = input.trim();
+        if cups.len() < 5 {
+            return Err(ParseError::at(input, cups, "expected at least 5 cups"));
+        }
+        // labelled from 1 to the number of cups, each once
+        for (i, ch) in cups.char_indices() {
+            let label = ch.to_digit(10).unwrap_or(0) as usize;
+            if label == 0 || label > cups.len() || cups[..i].contains(ch) {
+                let reason = format!("expected a distinct label from 1 to {}", cups.len());
+                return Err(ParseError::at(input, &cups[i..], reason));
+            }
+        }
+        Ok(cups)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> String {
+        part1(input, 100)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> String {
+        part2(input).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -1,18 +1,17 @@
+use aoc_common::{ParseError, Solution};
 use std::collections::{HashMap, HashSet};
 
-const INPUT: &str = include_str!("../input/day24.txt");
-
 type InnerPt2 = i16;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(align(1))]
-struct Pt2 {
+pub struct Pt2 {
     pub x: InnerPt2,
     pub y: InnerPt2,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
-struct PackedPt2(u32);
+pub struct PackedPt2(u32);
 
 impl PackedPt2 {
     pub fn unpack(self) -> Pt2 {
@@ -55,39 +54,42 @@ impl std::ops::Add<Self> for Pt2 {
     }
 }
 
-fn parse() -> HashSet<PackedPt2> {
+fn parse(input: &str) -> Result<HashSet<PackedPt2>, ParseError> {
     // axial coordinates (https://gamedevelopment.tutsplus.com/tutorials/introduction-to-axial-coordinates-for-hexagonal-tile-based-games--cms-28820)
-    let raw_tiles = INPUT.lines().map(|l| {
+    let raw_tiles = input.lines().map(|l| {
         let lbytes = l.as_bytes();
         let mut pos = Pt2::default();
         let mut i = 0;
         while i < l.len() {
+            let start = i;
+            let error = || ParseError::at(input, &l[start..], "expected e, se, sw, w, nw or ne");
             pos += match lbytes[i] {
                 b0 @ (b'n' | b's') => {
                     i += 1;
-                    match (b0, lbytes[i]) {
-                        (b'n', b'e') => (0, -1),
-                        (b'n', b'w') => (1, -1),
-                        (b's', b'e') => (-1, 1),
-                        (b's', b'w') => (0, 1),
-                        _ => unreachable!(),
+                    match (b0, lbytes.get(i)) {
+                        (b'n', Some(b'e')) => (0, -1),
+                        (b'n', Some(b'w')) => (1, -1),
+                        (b's', Some(b'e')) => (-1, 1),
+                        (b's', Some(b'w')) => (0, 1),
+                        _ => return Err(error()),
                     }
                 }
                 b'e' => (-1, 0),
                 b'w' => (1, 0),
-                _ => unreachable!(),
+                _ => return Err(error()),
             };
             i += 1;
         }
-        pos.pack()
+        Ok(pos.pack())
     });
     let mut tiles = HashSet::new();
     for t in raw_tiles {
+        let t = t?;
         if !tiles.remove(&t) {
             tiles.insert(t);
         }
     }
-    tiles
+    Ok(tiles)
 }
 
 const NEIGHBORS: [Pt2; 6] = [
@@ -141,13 +143,22 @@ fn p1(tiles: &HashSet<PackedPt2>) -> u64 {
     tiles.len() as u64
 }
 
-pub fn day24() -> (String, String) {
-    let tiles = parse();
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Parsed<'a> = HashSet<PackedPt2>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
 
-    let p1 = p1(&tiles);
-    let p2 = p2(tiles);
+    fn part1(tiles: &Self::Parsed<'_>) -> String {
+        p1(tiles).to_string()
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part2(tiles: &Self::Parsed<'_>) -> String {
+        p2(tiles.clone()).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -167,7 +178,7 @@ mod test {
 
     #[test]
     fn correct_output() {
-        let (p1, p2) = day24();
+        let (p1, p2) = Day24::solve(include_str!("../input/day24.txt")).unwrap();
         assert!(p1 == "485");
         assert!(p2 == "3933");
     }
@@ -1,10 +1,29 @@
-const INPUT: &str = include_str!("../input/day25.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
 const BASE_NB: u64 = 7;
 const REMAINDER_NB: u64 = 20201227;
 
-fn parse() -> Vec<u64> {
-    INPUT.lines().map(|l| l.parse().unwrap()).collect()
+fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+    let pubkeys = input
+        .lines()
+        .map(|l| match parse::token(input, l)? {
+            // any other key would never be found
+            key @ 1..REMAINDER_NB => Ok(key),
+            _ => Err(ParseError::at(
+                input,
+                l,
+                format!("expected a key below {REMAINDER_NB}"),
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if pubkeys.len() != 2 {
+        return Err(ParseError::at(
+            input,
+            input,
+            "expected the keys of the card and the door",
+        ));
+    }
+    Ok(pubkeys)
 }
 
 fn calculate_loopsize(pubkey: u64) -> u64 {
@@ -31,13 +50,28 @@ fn calculate_privkey(pubkeys: &[u64]) -> u64 {
     transform(pubkeys[1], card_loopsize)
 }
 
-pub fn day25() -> (String, String) {
-    let pubkeys = parse();
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Parsed<'a> = Vec<u64>;
 
-    let p1 = calculate_privkey(&pubkeys);
-    let p2 = "🌟";
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "5764801\n17807724\n",
+        part1: Some("14897079"),
+        part2: None,
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part1(pubkeys: &Self::Parsed<'_>) -> String {
+        calculate_privkey(pubkeys).to_string()
+    }
+
+    fn part2(_: &Self::Parsed<'_>) -> String {
+        "🌟".to_string()
+    }
 }
 
 #[cfg(test)]
@@ -1,14 +1,17 @@
+use aoc_common::{parse, Example, ParseError, Solution};
 use heapless::Vec;
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("../input/day01.txt");
 const INPUT_LINES: usize = 2000;
 
-fn parse(input: &'static str) -> Vec<u32, INPUT_LINES> {
-    input
-        .split_terminator('\n')
-        .map(|s| s.parse().unwrap())
-        .collect()
+fn parse(input: &str) -> Result<Vec<u32, INPUT_LINES>, ParseError> {
+    let mut depths = Vec::new();
+    for s in input.split_terminator('\n') {
+        depths.push(parse::token(input, s)?).map_err(|_| {
+            ParseError::at(input, s, format!("expected at most {INPUT_LINES} depths"))
+        })?;
+    }
+    Ok(depths)
 }
 
 fn part_1(input: &[u32]) -> String {
@@ -27,29 +30,45 @@ fn part_2(input: &[u32]) -> String {
         .to_string()
 }
 
-pub fn day01() -> (String, String) {
-    let input = parse(INPUT);
+const TEST_INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+pub struct Day01;
 
-    let p1 = part_1(&input);
-    let p2 = part_2(&input);
-    (p1, p2)
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<u32, INPUT_LINES>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("7"),
+        part2: Some("5"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(depths: &Self::Parsed<'_>) -> String {
+        part_1(depths)
+    }
+
+    fn part2(depths: &Self::Parsed<'_>) -> String {
+        part_2(depths)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
-
     #[test]
     fn test_part_1() {
-        let input = parse(TEST_INPUT);
+        let input = parse(TEST_INPUT).unwrap();
         assert_eq!(part_1(&input), "7".to_string());
     }
 
     #[test]
     fn test_part_2() {
-        let input = parse(TEST_INPUT);
+        let input = parse(TEST_INPUT).unwrap();
         assert_eq!(part_2(&input), "5".to_string());
     }
 }
@@ -1,4 +1,4 @@
-const INPUT: &str = include_str!("../input/day02.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
 struct Position {
     /// Forward for both parts
@@ -9,49 +9,74 @@ struct Position {
     part2_y: u32,
 }
 
-fn solve(input: &str) -> (String, String) {
+fn navigate(commands: &[(&str, u32)]) -> Position {
     let mut pos = Position {
         x: 0,
         y_aim: 0,
         part2_y: 0,
     };
 
-    input.split_terminator('\n').for_each(|s| {
-        let mut split = s.split(' ');
-        let dir = split.next().unwrap();
-        let val: u32 = split.next().unwrap().parse().unwrap();
-        match dir {
-            "forward" => {
-                pos.x += val;
-                pos.part2_y += val * pos.y_aim;
-            }
-            "up" => {
-                pos.y_aim -= val;
-            }
-            "down" => {
-                pos.y_aim += val;
-            }
-            _ => panic!(),
+    commands.iter().for_each(|&(dir, val)| match dir {
+        "forward" => {
+            pos.x += val;
+            pos.part2_y += val * pos.y_aim;
         }
+        "up" => {
+            pos.y_aim -= val;
+        }
+        "down" => {
+            pos.y_aim += val;
+        }
+        _ => panic!(),
     });
-    (
-        (pos.x * pos.y_aim).to_string(),
-        (pos.x * pos.part2_y).to_string(),
-    )
+    pos
 }
 
-pub fn day02() -> (String, String) {
-    solve(INPUT)
+const TEST_INPUT: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<(&'a str, u32)>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("150"),
+        part2: Some("900"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input
+            .split_terminator('\n')
+            .map(|s| {
+                let (dir, val) = s.split_once(' ').ok_or_else(|| {
+                    ParseError::at(input, s, "expected a command, like forward 5")
+                })?;
+                if !["forward", "up", "down"].contains(&dir) {
+                    return Err(ParseError::at(input, dir, "expected forward, up or down"));
+                }
+                Ok((dir, parse::token(input, val)?))
+            })
+            .collect()
+    }
+
+    fn part1(commands: &Self::Parsed<'_>) -> String {
+        let pos = navigate(commands);
+        (pos.x * pos.y_aim).to_string()
+    }
+
+    fn part2(commands: &Self::Parsed<'_>) -> String {
+        let pos = navigate(commands);
+        (pos.x * pos.part2_y).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
-
     #[test]
     fn test() {
-        assert_eq!(solve(TEST_INPUT), ("150".to_string(), "900".to_string()));
+        assert_eq!(Day02::solve(TEST_INPUT).unwrap(), ("150".to_string(), "900".to_string()));
     }
 }
@@ -1,24 +1,39 @@
+use aoc_common::{Example, ParseError, Solution};
 use heapless::Vec;
 
-const INPUT: &str = include_str!("../input/day03.txt");
 const MAX_INPUT_LINES: usize = 1000;
 
-struct ParsedDay03 {
+pub struct ParsedDay03 {
     bits: Vec<i32, MAX_INPUT_LINES>,
     bits_len: usize,
 }
 
 impl ParsedDay03 {
-    pub fn new(input: &str) -> Self {
-        let bits_len = input.find('\n').unwrap();
-        let res = input
-            .split_terminator('\n')
-            .map(|line| i32::from_str_radix(line, 2).unwrap())
-            .collect::<Vec<_, MAX_INPUT_LINES>>();
-        Self {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let bits_len = input.find('\n').unwrap_or(input.len());
+        if !(1..i32::BITS as usize).contains(&bits_len) {
+            let reason = format!("expected numbers of 1 to {} bits", i32::BITS - 1);
+            return Err(ParseError::at(input, input, reason));
+        }
+        let mut res = Vec::new();
+        for line in input.split_terminator('\n') {
+            if let Some(i) = line.find(|c| c != '0' && c != '1') {
+                return Err(ParseError::at(input, &line[i..], "expected 0 or 1"));
+            }
+            if line.len() != bits_len {
+                let reason = format!("expected {bits_len} bits, like the first line");
+                return Err(ParseError::at(input, line, reason));
+            }
+            res.push(i32::from_str_radix(line, 2).unwrap())
+                .map_err(|_| {
+                    let reason = format!("expected at most {MAX_INPUT_LINES} numbers");
+                    ParseError::at(input, line, reason)
+                })?;
+        }
+        Ok(Self {
             bits: res,
             bits_len,
-        }
+        })
     }
 }
 
@@ -91,21 +106,42 @@ fn part2(input: &ParsedDay03) -> String {
     (oxygen_generator_rating * co2_scrubber_rating).to_string()
 }
 
-pub fn day03() -> (String, String) {
-    let parsed = ParsedDay03::new(INPUT);
-    (part1(&parsed), part2(&parsed))
+const TEST_INPUT: &str =
+    "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = ParsedDay03;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("198"),
+        part2: Some("230"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        ParsedDay03::new(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        part1(parsed)
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        part2(parsed)
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str =
-        "00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n";
+    const INPUT: &str = include_str!("../input/day03.txt");
 
     #[test]
     fn test_parsing_ok() {
-        let parsed = ParsedDay03::new(TEST_INPUT);
+        let parsed = ParsedDay03::new(TEST_INPUT).unwrap();
         let recreated = parsed
             .bits
             .into_iter()
@@ -118,16 +154,25 @@ mod test {
 
     #[test]
     fn test_part1_test_input() {
-        assert_eq!(part1(&ParsedDay03::new(TEST_INPUT)), "198".to_string());
+        assert_eq!(
+            part1(&ParsedDay03::new(TEST_INPUT).unwrap()),
+            "198".to_string()
+        );
     }
 
     #[test]
     fn test_part1_real_input() {
-        assert_eq!(part1(&ParsedDay03::new(INPUT)), "2583164".to_string());
+        assert_eq!(
+            part1(&ParsedDay03::new(INPUT).unwrap()),
+            "2583164".to_string()
+        );
     }
 
     #[test]
     fn test_part2_test_input() {
-        assert_eq!(part2(&ParsedDay03::new(TEST_INPUT)), "230".to_string());
+        assert_eq!(
+            part2(&ParsedDay03::new(TEST_INPUT).unwrap()),
+            "230".to_string()
+        );
     }
 }
@@ -1,6 +1,6 @@
+use aoc_common::{parse, Example, ParseError, Solution};
 use heapless::Vec as StackVec;
 
-const INPUT: &str = include_str!("../input/day04.txt");
 const MAX_DRAWED: usize = 100;
 const MAX_BOARDS: usize = 100;
 /// Needs for loops in const functions
@@ -74,37 +74,56 @@ impl Board {
     }
 }
 
-struct Parsed {
+#[derive(Clone)]
+pub struct Parsed {
     drawn: StackVec<Int, MAX_DRAWED>,
     boards: StackVec<Board, MAX_BOARDS>,
 }
 
 impl Parsed {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        // -1 marks the drawn numbers
+        let number = |nb| match parse::token(input, nb)? {
+            n if n >= 0 => Ok(n),
+            _ => Err(ParseError::at(input, nb, "expected a positive number")),
+        };
         let mut in_it = input.trim_end().split("\n\n");
-        let drawn = in_it
-            .next()
-            .unwrap()
-            .split(',')
-            .map(|nb| nb.parse().unwrap())
-            .collect();
+        let mut drawn = StackVec::new();
+        for nb in parse::next(input, &mut in_it, "the drawn numbers")?.split(',') {
+            drawn.push(number(nb)?).map_err(|_| {
+                ParseError::at(input, nb, format!("expected at most {MAX_DRAWED} numbers"))
+            })?;
+        }
         let mut boards = StackVec::<Board, MAX_BOARDS>::new();
         for board_str in in_it {
-            boards.push(Board::default()).unwrap();
-            let b = boards.last_mut().unwrap();
-            let mut i = 0;
-            for l in board_str.split('\n') {
-                for nb in l
-                    .split(' ')
-                    .filter(|&s| !s.is_empty())
-                    .map(|s| s.parse().unwrap())
-                {
-                    b.board[i] = nb;
-                    i += 1;
-                }
+            let mut b = Board::default();
+            let mut nbs = board_str.split_whitespace();
+            for cell in b.board.iter_mut() {
+                *cell = number(parse::next(input, &mut nbs, "a number of the board")?)?;
+            }
+            if let Some(nb) = nbs.next() {
+                return Err(ParseError::at(input, nb, "expected a board of 5x5 numbers"));
+            }
+            boards.push(b).map_err(|_| {
+                ParseError::at(
+                    input,
+                    board_str,
+                    format!("expected at most {MAX_BOARDS} boards"),
+                )
+            })?;
+            // part 2 looks for the last board to win, so they all have to
+            let wins = ROW_COLUMNS
+                .iter()
+                .any(|line| line.iter().all(|&i| drawn.contains(&b.board[i])));
+            if !wins {
+                let reason = "expected a board with a row or a column of drawn numbers";
+                return Err(ParseError::at(input, board_str, reason));
             }
         }
-        Self { drawn, boards }
+        if boards.is_empty() {
+            return Err(ParseError::at(input, "", "expected at least one board"));
+        }
+        Ok(Self { drawn, boards })
     }
 }
 
@@ -112,49 +131,31 @@ fn board_score(board: &Board, draw: Int) -> Int {
     board.board.iter().filter(|nb| **nb != -1).sum::<Int>() * draw
 }
 
-fn solve(input: &mut Parsed) -> (String, String) {
-    let mut first_board = None;
-    let mut last_board = None;
-    let mut boards_playing = (0..input.boards.len()).collect::<StackVec<_, MAX_BOARDS>>();
+/// The score of the first board to win, or of the last one when `until_last`
+fn play(input: &Parsed, until_last: bool) -> Int {
+    let mut boards = input.boards.clone();
+    let mut boards_playing = (0..boards.len()).collect::<StackVec<_, MAX_BOARDS>>();
 
-    // assuming all boards won == all numbers drawn
     for &d in input.drawn.iter() {
         let mut boards_won = Vec::with_capacity(boards_playing.len());
         for &board_id in boards_playing.iter() {
-            let b = input.boards.get_mut(board_id).unwrap();
+            let b = boards.get_mut(board_id).unwrap();
             b.set_nb(d);
             if b.has_won() {
-                boards_won.push((board_id, d));
+                boards_won.push(board_id);
             }
         }
-        for (board_id, draw) in boards_won {
-            match first_board {
-                None => first_board = Some((board_id, draw)),
-                _ => last_board = Some((board_id, draw)),
+        for board_id in boards_won {
+            if !until_last || boards_playing.len() == 1 {
+                return board_score(&boards[board_id], d);
             }
             boards_playing.swap_remove(boards_playing.iter().position(|x| *x == board_id).unwrap());
         }
     }
-
-    let first = first_board.unwrap();
-    let last = last_board.unwrap();
-
-    let part1 = (board_score(&input.boards[first.0], first.1)).to_string();
-    let part2 = (board_score(&input.boards[last.0], last.1)).to_string();
-    (part1, part2)
-}
-
-pub fn day04() -> (String, String) {
-    let mut parsed = Parsed::new(INPUT);
-
-    solve(&mut parsed)
+    unreachable!("every board wins, see `Parsed::new`")
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+const TEST_INPUT: &str = r#"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
 22 13 17 11  0
  8  2 23  4 24
@@ -175,15 +176,58 @@ mod test {
  2  0 12  3  7
 "#;
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Parsed;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("4512"),
+        part2: Some("1924"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Parsed::new(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        play(parsed, false).to_string()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        play(parsed, true).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_test_input() {
-        let mut parsed = Parsed::new(TEST_INPUT);
-        assert_eq!(solve(&mut parsed).0, "4512".to_string());
+        let parsed = Parsed::new(TEST_INPUT).unwrap();
+        assert_eq!(play(&parsed, false), 4512);
     }
 
     #[test]
     fn test_part2_test_input() {
-        let mut parsed = Parsed::new(TEST_INPUT);
-        assert_eq!(solve(&mut parsed).1, "1924".to_string());
+        let parsed = Parsed::new(TEST_INPUT).unwrap();
+        assert_eq!(play(&parsed, true), 1924);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Parsed::new("1,2\n").err(),
+            Some(ParseError::new(2, 1, "expected at least one board"))
+        );
+        let board = (1..=25)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(Parsed::new(&format!("1,2,3,4,5\n\n{board}\n")).is_ok());
+        let error = Parsed::new(&format!("1,2,3,4\n\n{board}\n")).err().unwrap();
+        assert_eq!((error.line, error.column), (3, 1));
     }
 }
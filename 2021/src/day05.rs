@@ -1,23 +1,26 @@
+use aoc_common::{parse, Example, ParseError, Rng, Solution};
+use std::collections::HashMap;
 use std::iter;
 
-const INPUT: &str = include_str!("../input/day05.txt");
-const MAX_BOARD_SIZE: usize = 990;
+const MAX_BOARD_SIZE: usize = 1000;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Point {
     x: isize,
     y: isize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Segment {
     a: Point,
     b: Point,
 }
 
+#[derive(Clone)]
 struct Board(Vec<u8>);
 
-struct Parsed {
+#[derive(Clone)]
+pub struct Parsed {
     board: Board,
     segments_straight: Vec<Segment>,
     segments_diagonal: Vec<Segment>,
@@ -52,11 +55,20 @@ impl Point {
         Self { x, y }
     }
 
-    fn parse(def: &str) -> Self {
-        let mut split = def.split(',');
-        let x = split.next().unwrap().parse().unwrap();
-        let y = split.next().unwrap().parse().unwrap();
-        Self::new(x, y)
+    /// Parses `def`, a point of `input` on the board
+    fn parse(input: &str, def: &str) -> Result<Self, ParseError> {
+        let (x, y) = def
+            .split_once(',')
+            .ok_or_else(|| ParseError::at(input, def, "expected a point, like 1,2"))?;
+        let coordinate = |c| match parse::token(input, c)? {
+            c @ 0..MAX_BOARD_SIZE => Ok(c as isize),
+            _ => Err(ParseError::at(
+                input,
+                c,
+                format!("expected a coordinate below {MAX_BOARD_SIZE}"),
+            )),
+        };
+        Ok(Self::new(coordinate(x)?, coordinate(y)?))
     }
 }
 
@@ -70,11 +82,22 @@ impl Segment {
         Segment { a, b }
     }
 
-    fn parse(line: &str) -> Self {
-        let mut pts = line.split(" -> ");
-        let a = Point::parse(pts.next().unwrap());
-        let b = Point::parse(pts.next().unwrap());
-        Self::new(a, b)
+    /// Parses `line`, a line of `input`
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (a, b) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::at(input, line, "expected a segment, like 1,2 -> 3,4"))?;
+        let segment = Self::new(Point::parse(input, a)?, Point::parse(input, b)?);
+        if (segment.a.x - segment.b.x).abs() != (segment.a.y - segment.b.y).abs()
+            && segment.is_diagonal()
+        {
+            return Err(ParseError::at(
+                input,
+                line,
+                "diagonals must be at 45 degrees",
+            ));
+        }
+        Ok(segment)
     }
 
     fn is_diagonal(&self) -> bool {
@@ -94,18 +117,20 @@ impl Segment {
 }
 
 impl Parsed {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
         let (segments_diagonal, segments_straight) = input
             .split_terminator('\n')
-            .map(Segment::parse)
+            .map(|line| Segment::parse(input, line))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .partition(|s| s.is_diagonal());
 
         let board = Board::default();
-        Self {
+        Ok(Self {
             segments_diagonal,
             segments_straight,
             board,
-        }
+        })
     }
 }
 
@@ -127,17 +152,31 @@ fn part2(input: &mut Parsed) -> String {
     input.board.count_overlaps().to_string()
 }
 
-pub fn day05() -> (String, String) {
-    let mut parsed = Parsed::new(INPUT);
-
-    (part1(&mut parsed), part2(&mut parsed))
+/// Counts the points of a map instead of drawing on a fixed size board
+fn reference(input: &str, diagonals: bool) -> usize {
+    let mut vents = HashMap::new();
+    for line in input.lines() {
+        let [x1, y1, x2, y2] = line
+            .split([',', ' ', '-', '>'])
+            .filter(|n| !n.is_empty())
+            .map(|n| n.parse::<i64>().unwrap())
+            .collect::<Vec<_>>()[..]
+        else {
+            unreachable!("checked by the parser");
+        };
+        if x1 != x2 && y1 != y2 && !diagonals {
+            continue;
+        }
+        let (dx, dy) = ((x2 - x1).signum(), (y2 - y1).signum());
+        let length = (x2 - x1).abs().max((y2 - y1).abs());
+        for step in 0..=length {
+            *vents.entry((x1 + dx * step, y1 + dy * step)).or_insert(0) += 1;
+        }
+    }
+    vents.values().filter(|&&n| n > 1).count()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = r#"0,9 -> 5,9
+const TEST_INPUT: &str = r#"0,9 -> 5,9
 8,0 -> 0,8
 9,4 -> 3,4
 2,2 -> 2,1
@@ -149,16 +188,87 @@ mod test {
 5,5 -> 8,2
 "#;
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = Parsed;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("5"),
+        part2: Some("12"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Parsed::new(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        part1(&mut parsed.clone())
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        // part 2 draws the diagonal segments over the straight ones
+        let mut parsed = parsed.clone();
+        part1(&mut parsed);
+        part2(&mut parsed)
+    }
+
+    /// `size` segments in a square window of the board, sometimes reaching its edges
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let side = (2 * size).clamp(10, MAX_BOARD_SIZE) as i64;
+        let origin = rng.range(0..=MAX_BOARD_SIZE as i64 - side);
+        let coordinate = |rng: &mut Rng| origin + rng.range(0..=side - 1);
+        let mut input = String::new();
+        for _ in 0..size {
+            let (x1, y1) = (coordinate(rng), coordinate(rng));
+            let (x2, y2) = match rng.below(3) {
+                0 => (x1, coordinate(rng)),
+                1 => (coordinate(rng), y1),
+                _ => {
+                    let (dx, dy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+                    // the room left before the window's edge in each direction
+                    let room = |c: i64, d: i64| {
+                        if d > 0 {
+                            origin + side - 1 - c
+                        } else {
+                            c - origin
+                        }
+                    };
+                    let length = rng.range(0..=room(x1, dx).min(room(y1, dy)));
+                    (x1 + dx * length, y1 + dy * length)
+                }
+            };
+            input += &format!("{x1},{y1} -> {x2},{y2}\n");
+        }
+        Some(input)
+    }
+
+    fn reference(input: &str, part: u8) -> Option<String> {
+        Some(reference(input, part == 2).to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part1_test_input() {
-        let mut parsed = Parsed::new(TEST_INPUT);
+        let mut parsed = Parsed::new(TEST_INPUT).unwrap();
         assert_eq!(part1(&mut parsed), "5".to_string());
     }
 
     #[test]
     fn test_part2_test_input() {
-        let mut parsed = Parsed::new(TEST_INPUT);
+        let mut parsed = Parsed::new(TEST_INPUT).unwrap();
         part1(&mut parsed);
         assert_eq!(part2(&mut parsed), "12".to_string());
     }
+
+    #[test]
+    fn test_reference() {
+        aoc_common::differential::check::<Day05>(30, 0..20).unwrap();
+        aoc_common::differential::check::<Day05>(500, 0..3).unwrap();
+    }
 }
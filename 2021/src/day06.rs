@@ -1,19 +1,29 @@
-const INPUT: &str = include_str!("../input/day06.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
+
 const NB_DAYS: usize = 9;
 
-struct Fishes {
+#[derive(Clone)]
+pub struct Fishes {
     fishes: [u64; NB_DAYS],
     offset: usize,
 }
 
 impl Fishes {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
         let mut fishes = [0; NB_DAYS];
-        input
-            .trim_end()
-            .split(',')
-            .for_each(|fish| fishes[fish.parse::<usize>().unwrap()] += 1);
-        Self { fishes, offset: 0 }
+        for fish in input.trim_end().split(',') {
+            match parse::token::<usize>(input, fish)? {
+                timer @ 0..NB_DAYS => fishes[timer] += 1,
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        fish,
+                        format!("expected a timer below {NB_DAYS}"),
+                    ))
+                }
+            }
+        }
+        Ok(Self { fishes, offset: 0 })
     }
 
     fn index(&self, i: usize) -> usize {
@@ -37,36 +47,55 @@ impl Fishes {
     }
 }
 
-pub fn day06() -> (String, String) {
-    let mut fishes = Fishes::new(INPUT);
+const TEST_INPUT: &str = r#"3,4,3,1,2"#;
+
+pub struct Day06;
 
-    for _ in 0..80 {
-        fishes.next_day();
+impl Solution for Day06 {
+    type Parsed<'a> = Fishes;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("5934"),
+        part2: Some("26984457539"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Fishes::new(input)
     }
-    let part1 = fishes.sum();
-    for _ in 0..(256 - 80) {
-        fishes.next_day();
+
+    fn part1(fishes: &Self::Parsed<'_>) -> String {
+        let mut fishes = fishes.clone();
+        for _ in 0..80 {
+            fishes.next_day();
+        }
+        fishes.sum().to_string()
     }
-    let part2 = fishes.sum();
 
-    (part1.to_string(), part2.to_string())
+    fn part2(fishes: &Self::Parsed<'_>) -> String {
+        let mut fishes = fishes.clone();
+        for _ in 0..256 {
+            fishes.next_day();
+        }
+        fishes.sum().to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = r#"3,4,3,1,2"#;
+    const INPUT: &str = include_str!("../input/day06.txt");
 
     #[test]
     fn test_parsing_test_input() {
-        let fishes = Fishes::new(TEST_INPUT);
+        let fishes = Fishes::new(TEST_INPUT).unwrap();
         assert_eq!(fishes.fishes, [0, 1, 1, 2, 1, 0, 0, 0, 0]);
     }
 
     #[test]
     fn test_test_input_18_days() {
-        let mut parsed = Fishes::new(TEST_INPUT);
+        let mut parsed = Fishes::new(TEST_INPUT).unwrap();
         for _ in 0..18 {
             parsed.next_day();
         }
@@ -75,7 +104,7 @@ mod test {
 
     #[test]
     fn test_test_input_80_days() {
-        let mut parsed = Fishes::new(TEST_INPUT);
+        let mut parsed = Fishes::new(TEST_INPUT).unwrap();
         for _ in 0..80 {
             parsed.next_day();
         }
@@ -84,7 +113,7 @@ mod test {
 
     #[test]
     fn test_80_days() {
-        let mut parsed = Fishes::new(INPUT);
+        let mut parsed = Fishes::new(INPUT).unwrap();
         for _ in 0..80 {
             parsed.next_day();
         }
@@ -93,7 +122,7 @@ mod test {
 
     #[test]
     fn test_256_days() {
-        let mut parsed = Fishes::new(INPUT);
+        let mut parsed = Fishes::new(INPUT).unwrap();
         for _ in 0..256 {
             parsed.next_day();
         }
@@ -1,18 +1,18 @@
-const INPUT: &str = include_str!("../input/day07.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
-struct Crabs {
+pub struct Crabs {
     crabs: Vec<i32>,
 }
 
 impl Crabs {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
         let mut crabs = input
             .trim_end()
             .split(',')
-            .map(|crab| crab.parse().unwrap())
-            .collect::<Vec<_>>();
+            .map(|crab| parse::token(input, crab))
+            .collect::<Result<Vec<_>, _>>()?;
         crabs.sort_unstable();
-        Self { crabs }
+        Ok(Self { crabs })
     }
 
     fn min_distance_p1(&self) -> i32 {
@@ -64,35 +64,53 @@ impl Crabs {
     }
 }
 
-pub fn day07() -> (String, String) {
-    let crabs = Crabs::new(INPUT);
-    let part1 = crabs.part1();
-    let part2 = crabs.part2();
+const TEST_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
 
-    (part1.to_string(), part2.to_string())
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = Crabs;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("37"),
+        part2: Some("168"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Crabs::new(input)
+    }
+
+    fn part1(crabs: &Self::Parsed<'_>) -> String {
+        crabs.part1().to_string()
+    }
+
+    fn part2(crabs: &Self::Parsed<'_>) -> String {
+        crabs.part2().to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
+    const INPUT: &str = include_str!("../input/day07.txt");
 
     #[test]
     fn test_min_distance_part_1_test_input() {
-        let crabs = Crabs::new(TEST_INPUT);
+        let crabs = Crabs::new(TEST_INPUT).unwrap();
         assert_eq!(crabs.min_distance_p1(), 2);
     }
 
     #[test]
     fn test_fuel_needed_part_1_test_input() {
-        let crabs = Crabs::new(TEST_INPUT);
+        let crabs = Crabs::new(TEST_INPUT).unwrap();
         assert_eq!(crabs.fuel_p1(2), 37);
     }
 
     #[test]
     fn test_min_distance_part_2_test_input() {
-        let crabs = Crabs::new(TEST_INPUT);
+        let crabs = Crabs::new(TEST_INPUT).unwrap();
         let res = crabs.min_distance_p2();
         assert_eq!(res.0, 4);
         assert_eq!(res.1, 5);
@@ -100,7 +118,7 @@ mod test {
 
     #[test]
     fn test_min_distance_part_2() {
-        let crabs = Crabs::new(INPUT);
+        let crabs = Crabs::new(INPUT).unwrap();
         let res = crabs.min_distance_p2();
         assert_eq!(res.0, 446);
         assert_eq!(res.1, 447);
@@ -108,13 +126,13 @@ mod test {
 
     #[test]
     fn test_fuel_needed_part_2_test_input() {
-        let crabs = Crabs::new(TEST_INPUT);
+        let crabs = Crabs::new(TEST_INPUT).unwrap();
         assert_eq!(crabs.fuel_p2(5), 168);
     }
 
     #[test]
     fn test_fuel_needed_part_2() {
-        let crabs = Crabs::new(INPUT);
+        let crabs = Crabs::new(INPUT).unwrap();
         assert_eq!(crabs.fuel_p2(446), 87640209);
     }
 }
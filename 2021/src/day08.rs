@@ -1,7 +1,7 @@
-const INPUT: &str = include_str!("../input/day08.txt");
-
 /// Can be solved in bash in 3ms:
 /// `cut input/day08.txt -d'|' -f2 | tr ' ' '\n' | awk '{ print length }' | grep -E '7|4|2|3' | wc -l`
+use aoc_common::{Example, ParseError, Solution};
+
 fn part_1(input: &str) -> usize {
     input
         .lines()
@@ -34,6 +34,95 @@ fn signal_srt2byte(signal: &str) -> u8 {
         .unwrap()
 }
 
+/// The segments of the digits 0 to 9
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Whether `patterns` are the 10 digits, with their wires mixed up. A wire is told apart from
+/// the others by the number of digits it is in, and whether it is in 1 and in 4.
+fn is_wiring(patterns: &[u8]) -> bool {
+    let signatures = |patterns: &[u8]| {
+        let mut signatures = [(0, false, false); 7];
+        for &p in patterns {
+            let len = p.count_ones();
+            for (wire, signature) in signatures.iter_mut().enumerate() {
+                if p & 1 << wire != 0 {
+                    signature.0 += 1;
+                    signature.1 |= len == 2;
+                    signature.2 |= len == 4;
+                }
+            }
+        }
+        signatures
+    };
+    let digits = DIGITS.map(signal_srt2byte);
+    let mixed = signatures(patterns);
+    let mut wiring = [0; 7];
+    for (wanted, wire) in signatures(&digits).iter().zip(&mut wiring) {
+        match mixed.iter().position(|signature| signature == wanted) {
+            Some(w) => *wire = 1 << w,
+            None => return false,
+        }
+    }
+    // the patterns as sets of 7 bits wide numbers
+    let mut rewired = 0u128;
+    for digit in digits {
+        let mixed = (0..7)
+            .filter(|&i| digit & 1 << i != 0)
+            .fold(0, |acc, i| acc | wiring[i]);
+        rewired |= 1 << mixed;
+    }
+    let mixed = patterns.iter().fold(0u128, |set, &p| set | 1 << p);
+    patterns.len() == 10 && mixed == rewired
+}
+
+/// Checks that `line` has 10 signal patterns, the digits with mixed up wires, and 4 output
+/// digits among them, made of the segments a to g
+fn check_entry(input: &str, line: &str) -> Result<(), ParseError> {
+    let (signals, output) = line
+        .split_once(" | ")
+        .ok_or_else(|| ParseError::at(input, line, "expected signals | output"))?;
+    for (part, len) in [(signals, 10), (output, 4)] {
+        let digits: Vec<&str> = part.split_whitespace().collect();
+        if digits.len() != len {
+            let reason = format!("expected {len} digits");
+            return Err(ParseError::at(input, part, reason));
+        }
+        for digit in digits {
+            let segments = |digit: &str| digit.bytes().all(|c| (b'a'..=b'g').contains(&c));
+            // no segment twice
+            let distinct = |digit| signal_srt2byte(digit).count_ones() as usize == digit.len();
+            if !(2..=7).contains(&digit.len()) || !segments(digit) || !distinct(digit) {
+                return Err(ParseError::at(
+                    input,
+                    digit,
+                    "expected 2 to 7 distinct segments from a to g",
+                ));
+            }
+        }
+    }
+    let patterns: Vec<u8> = signals.split_whitespace().map(signal_srt2byte).collect();
+    if !is_wiring(&patterns) {
+        return Err(ParseError::at(
+            input,
+            signals,
+            "expected the 10 digits, with mixed up wires",
+        ));
+    }
+    if let Some(digit) = output
+        .split_whitespace()
+        .find(|&digit| !patterns.contains(&signal_srt2byte(digit)))
+    {
+        return Err(ParseError::at(
+            input,
+            digit,
+            "expected one of the signal patterns",
+        ));
+    }
+    Ok(())
+}
+
 fn part_2(input: &str) -> usize {
     let mut total_sum = 0;
     for line in input.lines() {
@@ -111,19 +200,8 @@ fn part_2(input: &str) -> usize {
     total_sum
 }
 
-pub fn day08() -> (String, String) {
-    let part1 = part_1(INPUT);
-    let part2 = part_2(INPUT);
-
-    (part1.to_string(), part2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str =
-        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+const TEST_INPUT: &str =
+    "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
 edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
 fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
 fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
@@ -134,6 +212,52 @@ bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbg
 egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
 gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = &'a str;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("26"),
+        part2: Some("61229"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // the notes are decoded as they are parsed, they are only checked here
+        for line in input.lines() {
+            check_entry(input, line)?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> String {
+        part_1(input).to_string()
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> String {
+        part_2(input).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/day08.txt");
+
+    #[test]
+    fn test_parse() {
+        assert!(Day08::parse(TEST_INPUT).is_ok());
+        let line =
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        assert!(Day08::parse(line).is_ok());
+        // an output digit that is not a pattern, and patterns that are not digits
+        assert!(Day08::parse(&line.replace("| cdfeb", "| cdfe")).is_err());
+        assert!(Day08::parse(&line.replace("acedgfb", "acedgf")).is_err());
+        assert!(Day08::parse(&line.replace("eafb", "eafg")).is_err());
+    }
+
     #[test]
     fn test_part_1_test_input() {
         assert_eq!(part_1(TEST_INPUT), 26);
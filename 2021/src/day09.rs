@@ -1,26 +1,27 @@
+use aoc_common::{parse, Example, ParseError, Solution};
 use heapless::Vec as StackVec;
 
-const INPUT: &str = include_str!("../input/day09.txt");
-
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     data: Vec<u8>,
     width: usize,
     height: usize,
 }
 
 impl Map {
-    fn new(input: &str) -> Self {
-        let width = input.find('\n').unwrap();
-        let data: Vec<_> = input
-            .split_terminator('\n')
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let rows = parse::grid(input, "0123456789")?;
+        let width = rows[0].len();
+        let data: Vec<_> = rows
+            .into_iter()
             .flat_map(|line| line.as_bytes().iter().map(|b| b - b'0'))
             .collect();
         let height = data.len() / width;
-        Self {
+        Ok(Self {
             data,
             width,
             height,
-        }
+        })
     }
 
     fn _print(&self) {
@@ -76,7 +77,7 @@ impl Map {
 
     fn recurse_bassin(&mut self, pos: usize) -> u64 {
         let mut sum = 1;
-        self.data[pos as usize] = 10;
+        self.data[pos] = 10;
         for neighbor in self
             .iter_neighbors(pos)
             .collect::<StackVec<usize, 4>>()
@@ -91,13 +92,18 @@ impl Map {
     }
 }
 
-fn solve(mut input: Map) -> (u32, u64) {
-    let mut part1 = 0;
-    let mut low_points_indexes = Vec::new();
-    for (idx, low_point) in input.local_low_points() {
-        part1 += (*low_point + 1) as u32;
-        low_points_indexes.push(idx);
-    }
+fn part1(input: &Map) -> u32 {
+    input
+        .local_low_points()
+        .map(|(_, low_point)| (*low_point + 1) as u32)
+        .sum()
+}
+
+fn part2(mut input: Map) -> u64 {
+    let low_points_indexes = input
+        .local_low_points()
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
     let mut bassins = low_points_indexes
         .into_iter()
         .map(|i| input.recurse_bassin(i))
@@ -105,36 +111,52 @@ fn solve(mut input: Map) -> (u32, u64) {
 
     bassins.sort_unstable();
 
-    let part2 = bassins.into_iter().rev().take(3).product();
-    (part1, part2)
+    bassins.into_iter().rev().take(3).product()
 }
 
-pub fn day09() -> (String, String) {
-    let parsed = Map::new(INPUT);
-    let (part1, part2) = solve(parsed);
+const TEST_INPUT: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+pub struct Day09;
 
-    (part1.to_string(), part2.to_string())
+impl Solution for Day09 {
+    type Parsed<'a> = Map;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("15"),
+        part2: Some("1134"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Map::new(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> String {
+        part1(map).to_string()
+    }
+
+    fn part2(map: &Self::Parsed<'_>) -> String {
+        part2(map.clone()).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = "2199943210
-3987894921
-9856789892
-8767896789
-9899965678";
-
     #[test]
     fn test_part_1_test_input() {
-        let map = Map::new(TEST_INPUT);
-        assert_eq!(solve(map).0, 15);
+        let map = Map::new(TEST_INPUT).unwrap();
+        assert_eq!(part1(&map), 15);
     }
 
     #[test]
     fn test_part_2_test_input() {
-        let map = Map::new(TEST_INPUT);
-        assert_eq!(solve(map).1, 1134);
+        let map = Map::new(TEST_INPUT).unwrap();
+        assert_eq!(part2(map), 1134);
     }
 }
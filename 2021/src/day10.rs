@@ -1,34 +1,56 @@
-const INPUT: &str = include_str!("../input/day10.txt");
+use aoc_common::{Example, ParseError, Solution};
 
-fn solve(input: &str) -> (u64, u64) {
-    let mut score1 = 0;
-    let mut score2 = Vec::new();
-    for line in input.split_terminator('\n') {
-        let mut expected_terminator = Vec::new();
-        let mut line_score1 = 0;
-        for c in line.as_bytes() {
-            match c {
-                b'{' => expected_terminator.push(b'}'),
-                b'[' => expected_terminator.push(b']'),
-                b'<' => expected_terminator.push(b'>'),
-                b'(' => expected_terminator.push(b')'),
-                b'}' | b']' | b'>' | b')' => {
-                    if expected_terminator.pop() != Some(*c) {
-                        line_score1 += match c {
-                            b')' => 3,
-                            b']' => 57,
-                            b'>' => 25137,
-                            b'}' => 1197,
-                            _ => panic!(),
-                        }
-                    }
+/// A line of the navigation subsystem, once its chunks are matched
+pub enum Line {
+    /// The first closing character that does not match
+    Corrupted(u8),
+    /// The closing characters that would complete the line, in order
+    Incomplete(Vec<u8>),
+    Complete,
+}
+
+fn parse_line(line: &str) -> Line {
+    let mut expected_terminator = Vec::new();
+    for c in line.as_bytes() {
+        match c {
+            b'{' => expected_terminator.push(b'}'),
+            b'[' => expected_terminator.push(b']'),
+            b'<' => expected_terminator.push(b'>'),
+            b'(' => expected_terminator.push(b')'),
+            _ => {
+                if expected_terminator.pop() != Some(*c) {
+                    return Line::Corrupted(*c);
                 }
-                _ => panic!(),
             }
         }
-        if line_score1 == 0 {
+    }
+    if expected_terminator.is_empty() {
+        Line::Complete
+    } else {
+        expected_terminator.reverse();
+        Line::Incomplete(expected_terminator)
+    }
+}
+
+fn part_1(lines: &[Line]) -> u64 {
+    lines
+        .iter()
+        .map(|line| match line {
+            Line::Corrupted(b')') => 3,
+            Line::Corrupted(b']') => 57,
+            Line::Corrupted(b'}') => 1197,
+            Line::Corrupted(b'>') => 25137,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn part_2(lines: &[Line]) -> u64 {
+    let mut score2 = Vec::new();
+    for line in lines {
+        if let Line::Incomplete(terminators) = line {
             let mut line_score2 = 0;
-            for term in expected_terminator.into_iter().rev() {
+            for term in terminators {
                 line_score2 *= 5;
                 line_score2 += match term {
                     b')' => 1,
@@ -38,29 +60,30 @@ fn solve(input: &str) -> (u64, u64) {
                     _ => unreachable!(),
                 };
             }
-            if line_score2 > 0 {
-                score2.push(line_score2);
-            }
+            score2.push(line_score2);
         }
-        score1 += line_score1;
     }
     score2.sort_unstable();
-
-    let score2 = score2[score2.len() / 2];
-    (score1, score2)
+    score2[score2.len() / 2]
 }
 
-pub fn day10() -> (String, String) {
-    let (part1, part2) = solve(INPUT);
-
-    (part1.to_string(), part2.to_string())
+fn parse_input(input: &str) -> Result<Vec<Line>, ParseError> {
+    if let Some(i) = input.find(|c| !"()[]{}<>\n".contains(c)) {
+        return Err(ParseError::at_offset(input, i, "expected a bracket"));
+    }
+    let lines: Vec<Line> = input.split_terminator('\n').map(parse_line).collect();
+    // part 2 takes the middle score of the incomplete lines
+    if !lines.iter().any(|line| matches!(line, Line::Incomplete(_))) {
+        return Err(ParseError::at(
+            input,
+            "",
+            "expected at least one incomplete line",
+        ));
+    }
+    Ok(lines)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
+const TEST_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
 [(()[<>])]({[<{<<[]>>(
 {([(<{}[<>[]}>{[]{[(<()>
 (((({<>}<{<{<>}{[]{[]{}
@@ -71,13 +94,50 @@ mod test {
 <{([([[(<>()){}]>(<<{{
 <{([{{}}[<[[[<>{}]]]>[]]";
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<Line>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("26397"),
+        part2: Some("288957"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> String {
+        part_1(lines).to_string()
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> String {
+        part_2(lines).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part_1_test_input() {
-        assert_eq!(solve(TEST_INPUT).0, 26397);
+        assert_eq!(part_1(&parse_input(TEST_INPUT).unwrap()), 26397);
     }
 
     #[test]
     fn test_part_2_test_input() {
-        assert_eq!(solve(TEST_INPUT).1, 288957);
+        assert_eq!(part_2(&parse_input(TEST_INPUT).unwrap()), 288957);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(parse_input("").is_err());
+        assert_eq!(
+            parse_input("()\n(]\n").err().unwrap(),
+            ParseError::new(3, 1, "expected at least one incomplete line")
+        );
     }
 }
@@ -1,6 +1,8 @@
-const INPUT: &str = include_str!("../input/day11.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
+use std::collections::HashSet;
 
-struct Octopuses {
+#[derive(Clone)]
+pub struct Octopuses {
     octopuses: Vec<u8>,
     width: usize,
     height: usize,
@@ -8,26 +10,20 @@ struct Octopuses {
 }
 
 impl Octopuses {
-    pub fn new(input: &str) -> Octopuses {
-        let width = input.find('\n').unwrap();
-        let octopuses: Vec<u8> = input
-            .as_bytes()
-            .iter()
-            .filter_map(|c| {
-                if c.is_ascii_digit() {
-                    Some(c - b'0')
-                } else {
-                    None
-                }
-            })
+    pub fn new(input: &str) -> Result<Octopuses, ParseError> {
+        let rows = parse::grid(input, "0123456789")?;
+        let width = rows[0].len();
+        let octopuses: Vec<u8> = rows
+            .into_iter()
+            .flat_map(|row| row.bytes().map(|c| c - b'0'))
             .collect();
         let height = octopuses.len() / width;
-        Octopuses {
+        Ok(Octopuses {
             octopuses,
             width,
             height,
             step_nb: 0,
-        }
+        })
     }
 
     fn pos_1d(&self, x: usize, y: usize) -> usize {
@@ -84,9 +80,16 @@ impl Octopuses {
         nb_flashes
     }
 
-    pub fn part_2(&mut self) -> u32 {
-        while self.next_step() != (self.width * self.height) as u32 {}
-        self.step_nb as u32
+    /// The first step all the octopuses flash, `None` if the energy levels come back to
+    /// where they were before that
+    pub fn part_2(&mut self) -> Option<u32> {
+        let mut seen = HashSet::new();
+        while self.next_step() != (self.width * self.height) as u32 {
+            if !seen.insert(self.octopuses.clone()) {
+                return None;
+            }
+        }
+        Some(self.step_nb as u32)
     }
 
     pub fn _print(&self) {
@@ -101,18 +104,7 @@ impl Octopuses {
     }
 }
 
-pub fn day11() -> (String, String) {
-    let mut parsed = Octopuses::new(INPUT);
-    let (part1, part2) = (parsed.part_1(), parsed.part_2());
-
-    (part1.to_string(), part2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = "5483143223
+const TEST_INPUT: &str = "5483143223
 2745854711
 5264556173
 6141336146
@@ -123,9 +115,42 @@ mod test {
 4846848554
 5283751526";
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Octopuses;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("1656"),
+        part2: Some("195"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Octopuses::new(input)
+    }
+
+    fn part1(octopuses: &Self::Parsed<'_>) -> String {
+        octopuses.clone().part_1().to_string()
+    }
+
+    fn part2(octopuses: &Self::Parsed<'_>) -> String {
+        match octopuses.clone().part_2() {
+            Some(step) => step.to_string(),
+            None => "the octopuses never all flash at once".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/day11.txt");
+
     #[test]
     fn test_part_1_test_input_2_steps() {
-        let mut parsed = Octopuses::new(TEST_INPUT);
+        let mut parsed = Octopuses::new(TEST_INPUT).unwrap();
         let mut nb_flashes = 0;
         nb_flashes += parsed.next_step();
         nb_flashes += parsed.next_step();
@@ -134,25 +159,28 @@ mod test {
 
     #[test]
     fn test_part_1_test_input_100_steps() {
-        let mut parsed = Octopuses::new(TEST_INPUT);
+        let mut parsed = Octopuses::new(TEST_INPUT).unwrap();
         assert_eq!(parsed.part_1(), 1656);
     }
 
     #[test]
     fn test_part_2_test_input() {
-        let mut parsed = Octopuses::new(TEST_INPUT);
-        assert_eq!(parsed.part_2(), 195);
+        let mut parsed = Octopuses::new(TEST_INPUT).unwrap();
+        assert_eq!(parsed.part_2(), Some(195));
+        // the top half of the example never synchronizes
+        let half = TEST_INPUT.lines().take(5).collect::<Vec<_>>().join("\n");
+        assert_eq!(Octopuses::new(&half).unwrap().part_2(), None);
     }
 
     #[test]
     fn test_part_1() {
-        let mut parsed = Octopuses::new(INPUT);
+        let mut parsed = Octopuses::new(INPUT).unwrap();
         assert_eq!(parsed.part_1(), 1793);
     }
 
     #[test]
     fn test_part_2() {
-        let mut parsed = Octopuses::new(INPUT);
-        assert_eq!(parsed.part_2(), 247);
+        let mut parsed = Octopuses::new(INPUT).unwrap();
+        assert_eq!(parsed.part_2(), Some(247));
     }
 }
@@ -1,15 +1,15 @@
+use aoc_common::{Example, ParseError, Solution};
 use std::intrinsics::{likely, unlikely};
 
-const INPUT: &str = include_str!("../input/day12.txt");
-
-struct Cave {
-    name: &'static str,
+#[derive(Clone)]
+struct Cave<'a> {
+    name: &'a str,
     links: Vec<usize>,
     is_big: bool,
 }
 
-impl Cave {
-    fn new(name: &'static str) -> Self {
+impl<'a> Cave<'a> {
+    fn new(name: &'a str) -> Self {
         debug_assert!(name.to_uppercase() == name || name.to_lowercase() == name);
         debug_assert!(!name.is_empty());
         Cave {
@@ -20,14 +20,15 @@ impl Cave {
     }
 }
 
-struct Map {
-    caves: Vec<Cave>,
+#[derive(Clone)]
+pub struct Map<'a> {
+    caves: Vec<Cave<'a>>,
     start: usize,
     end: usize,
 }
 
-impl Map {
-    pub fn new(input: &'static str) -> Self {
+impl<'a> Map<'a> {
+    pub fn new(input: &'a str) -> Result<Self, ParseError> {
         let mut map = Self {
             caves: Vec::new(),
             start: 0,
@@ -37,19 +38,46 @@ impl Map {
         for line in input.split_terminator('\n') {
             let link = line
                 .split_once('-')
-                .expect("Invalid input: could not split on '-'");
+                .ok_or_else(|| ParseError::at(input, line, "expected a link, like start-A"))?;
+            for name in [link.0, link.1] {
+                let lower = name.bytes().all(|c| c.is_ascii_lowercase());
+                if name.is_empty() || !(lower || name.bytes().all(|c| c.is_ascii_uppercase())) {
+                    return Err(ParseError::at(
+                        input,
+                        name,
+                        "expected a lowercase or uppercase name",
+                    ));
+                }
+            }
             let link_room = (
                 map.get_or_create_room(link.0),
                 map.get_or_create_room(link.1),
             );
+            // the paths would go back and forth between them forever
+            if map.caves[link_room.0].is_big && map.caves[link_room.1].is_big {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "two big caves cannot be linked",
+                ));
+            }
             map.caves[link_room.0].links.push(link_room.1);
             map.caves[link_room.1].links.push(link_room.0);
         }
+        for name in ["start", "end"] {
+            if !map.caves.iter().any(|c| c.name == name) {
+                return Err(ParseError::at(
+                    input,
+                    "",
+                    format!("missing the {name} cave"),
+                ));
+            }
+        }
 
-        map
+        Ok(map)
     }
 
-    fn get_or_create_room(&mut self, name: &'static str) -> usize {
+    fn get_or_create_room(&mut self, name: &'a str) -> usize {
         let i = self
             .caves
             .iter()
@@ -76,24 +104,20 @@ impl Map {
     }
 }
 
-struct TraversalData {
-    map: Map,
+struct TraversalData<'a> {
+    map: Map<'a>,
     visited: Vec<usize>,
     visited_twice: Option<usize>,
 }
 
-impl TraversalData {
-    fn new(map: Map) -> Self {
+impl<'a> TraversalData<'a> {
+    fn new(map: Map<'a>) -> Self {
         Self {
             visited: Vec::with_capacity(map.caves.len() * 2),
             visited_twice: None,
             map,
         }
     }
-    fn reset(&mut self) {
-        self.visited.clear();
-        self.visited_twice = None;
-    }
     fn without_double_visits(&mut self) {
         self.visited_twice = Some(usize::MAX);
     }
@@ -116,33 +140,14 @@ impl TraversalData {
             total += self.traverse(*c);
         }
         self.visited.pop();
-        if self.visited_twice.contains(&id) {
+        if self.visited_twice == Some(id) {
             self.visited_twice = None;
         }
         total
     }
 }
 
-pub fn day12() -> (String, String) {
-    // Part 1 is very fast
-    // Part 2 in horribly slow
-
-    let parsed = Map::new(INPUT);
-    let mut ctx = TraversalData::new(parsed);
-    // no double visit for part 1
-    ctx.without_double_visits();
-    let start = ctx.map.start;
-    let part1 = ctx.traverse(start);
-    ctx.reset();
-    let part2 = ctx.traverse(start);
-    (part1.to_string(), part2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const SMALL_TEST_INPUT: &str = "start-A
+const SMALL_TEST_INPUT: &str = "start-A
 start-b
 A-c
 A-b
@@ -150,7 +155,7 @@ b-d
 A-end
 b-end";
 
-    const MEDIUM_TEST_INPUT: &str = "dc-end
+const MEDIUM_TEST_INPUT: &str = "dc-end
 HN-start
 start-kj
 dc-start
@@ -161,7 +166,7 @@ kj-sa
 kj-HN
 kj-dc";
 
-    const LARGE_TEST_INPUT: &str = "fs-end
+const LARGE_TEST_INPUT: &str = "fs-end
 he-DX
 fs-he
 start-DX
@@ -180,8 +185,54 @@ zg-he
 pj-fs
 start-RW";
 
-    fn test_part_1(input: &'static str) -> i32 {
-        let parsed = Map::new(input);
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Map<'a>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: SMALL_TEST_INPUT,
+            part1: Some("10"),
+            part2: Some("36"),
+        },
+        Example {
+            input: MEDIUM_TEST_INPUT,
+            part1: Some("19"),
+            part2: Some("103"),
+        },
+        Example {
+            input: LARGE_TEST_INPUT,
+            part1: Some("226"),
+            part2: Some("3509"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Map::new(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> String {
+        // Part 1 is very fast
+        let mut ctx = TraversalData::new(map.clone());
+        // no double visit for part 1
+        ctx.without_double_visits();
+        ctx.traverse(map.start).to_string()
+    }
+
+    fn part2(map: &Self::Parsed<'_>) -> String {
+        // Part 2 in horribly slow
+        let mut ctx = TraversalData::new(map.clone());
+        ctx.traverse(map.start).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_part_1(input: &str) -> i32 {
+        let parsed = Map::new(input).unwrap();
         let mut ctx = TraversalData::new(parsed);
         // no double visit for part 1
         ctx.without_double_visits();
@@ -189,8 +240,8 @@ start-RW";
         ctx.traverse(start)
     }
 
-    fn test_part_2(input: &'static str) -> i32 {
-        let parsed = Map::new(input);
+    fn test_part_2(input: &str) -> i32 {
+        let parsed = Map::new(input).unwrap();
         let mut ctx = TraversalData::new(parsed);
         let start = ctx.map.start;
         ctx.traverse(start)
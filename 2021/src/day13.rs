@@ -1,9 +1,6 @@
-use bitvec::prelude::*;
-use itertools::{Either, Itertools};
+use aoc_common::{ocr, parse, Example, ParseError, Solution};
 
-const INPUT: &str = include_str!("../input/day13.txt");
-
-struct Parsed {
+pub struct Parsed {
     dots: Vec<(usize, usize)>,
     folds_x: Vec<usize>,
     folds_y: Vec<usize>,
@@ -11,47 +8,54 @@ struct Parsed {
 }
 
 impl Parsed {
-    pub fn new(input: &str) -> Self {
-        let (dots, folds) = input
-            .rsplit_once("\n\n")
-            .expect("delimiter btween dots and folds not found");
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (dots, folds) = input.rsplit_once("\n\n").ok_or_else(|| {
+            ParseError::at(
+                input,
+                input,
+                "expected dots and folds, separated by an empty line",
+            )
+        })?;
         let dots = dots
             .split('\n')
             .map(|line| {
-                let (x, y) = line.split_once(',').expect("invalid dot");
-                let x = x.parse::<usize>().expect("invalid dot");
-                let y = y.parse::<usize>().expect("invalid dot");
-                (x, y)
+                let (x, y) = line
+                    .split_once(',')
+                    .ok_or_else(|| ParseError::at(input, line, "expected a dot, like 6,10"))?;
+                Ok((
+                    parse::token::<u32>(input, x)? as usize,
+                    parse::token::<u32>(input, y)? as usize,
+                ))
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>, _>>()?;
         let mut first_fold = None;
-        let (folds_x, folds_y) = folds
-            .split_terminator('\n')
-            .partition_map::<Vec<_>, Vec<_>, _, _, _>(|line| {
-                let (_, fold_str) = line.rsplit_once(' ').expect("invalid fold");
-                let (axis, pos) = fold_str.split_once('=').expect("invalid fold");
-                let axis = axis.chars().next().expect("invalid fold");
-                let pos = pos.parse::<usize>().expect("invalid fold");
-                if first_fold.is_none() {
-                    first_fold = Some(axis);
-                }
-                match axis {
-                    'x' => Either::Left(pos),
-                    'y' => Either::Right(pos),
-                    _ => panic!("invalid fold"),
-                }
-            });
+        let (mut folds_x, mut folds_y) = (Vec::new(), Vec::new());
+        for line in folds.split_terminator('\n') {
+            let (axis, pos) = line
+                .strip_prefix("fold along ")
+                .and_then(|fold| fold.split_once('='))
+                .ok_or_else(|| {
+                    ParseError::at(input, line, "expected a fold, like fold along y=7")
+                })?;
+            let pos = parse::token::<usize>(input, pos)?;
+            match axis {
+                "x" => folds_x.push(pos),
+                "y" => folds_y.push(pos),
+                _ => return Err(ParseError::at(input, axis, "expected x or y")),
+            }
+            first_fold.get_or_insert(if axis == "x" { 'x' } else { 'y' });
+        }
 
-        Parsed {
+        Ok(Parsed {
             dots,
             folds_x,
             folds_y,
-            first_fold: first_fold.expect("no folds ?!"),
-        }
+            first_fold: first_fold.ok_or_else(|| ParseError::at(input, "", "expected a fold"))?,
+        })
     }
 }
 
-fn _print_page(page: &BitSlice, width: usize, height: usize) {
+fn _print_page(page: &[bool], width: usize, height: usize) {
     println!("Page:");
     for y in 0..height {
         for x in 0..width {
@@ -63,17 +67,17 @@ fn _print_page(page: &BitSlice, width: usize, height: usize) {
 
 fn part_1(input: &Parsed) -> usize {
     let mut dots = Vec::new();
-    let fold_x = input.folds_x[0];
-    let fold_y = input.folds_y[0];
 
     input.dots.iter().cloned().for_each(|(mut x, mut y)| {
         match input.first_fold {
             'x' => {
+                let fold_x = input.folds_x[0];
                 if x > fold_x {
                     x = 2 * fold_x - x;
                 }
             }
             'y' => {
+                let fold_y = input.folds_y[0];
                 if y > fold_y {
                     y = 2 * fold_y - y;
                 }
@@ -88,10 +92,14 @@ fn part_1(input: &Parsed) -> usize {
     dots.len()
 }
 
-fn part_2(input: &Parsed) -> (BitArray<[usize; 4]>, &str) {
-    const PAGE_H: usize = 6;
-    const PAGE_W: usize = 39;
-    let mut page = bitarr![0; PAGE_H * PAGE_W];
+/// The code read on the folded page
+fn part_2(input: &Parsed) -> String {
+    // the page ends at the smallest folds
+    let width = input.folds_x.iter().copied().min();
+    let height = input.folds_y.iter().copied().min();
+    let width = width.unwrap_or_else(|| input.dots.iter().map(|d| d.0 + 1).max().unwrap_or(0));
+    let height = height.unwrap_or_else(|| input.dots.iter().map(|d| d.1 + 1).max().unwrap_or(0));
+    let mut page = vec![false; width * height];
 
     input.dots.iter().cloned().for_each(|(mut x, mut y)| {
         input.folds_x.iter().for_each(|&fx| {
@@ -104,25 +112,17 @@ fn part_2(input: &Parsed) -> (BitArray<[usize; 4]>, &str) {
                 y = 2 * fy - y;
             }
         });
-        page.set(x + y * PAGE_W, true);
+        // a dot on a fold line is not on the page
+        if x < width && y < height {
+            page[x + y * width] = true;
+        }
     });
-    // _print_page(&page, PAGE_W, PAGE_H);
-    // fixme: hardcoded value because we don't have character recognition
-    (page, "EFLFJGRF")
-}
+    // _print_page(&page, width, height);
 
-pub fn day13() -> (String, String) {
-    let parsed = Parsed::new(INPUT);
-    let part1 = part_1(&parsed);
-    let part2 = part_2(&parsed);
-    (part1.to_string(), part2.1.to_string())
+    ocr::read(width, |x, y| x < width && y < height && page[x + y * width])
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = "6,10
+const TEST_INPUT: &str = "6,10
 0,14
 9,10
 0,3
@@ -144,15 +144,51 @@ mod test {
 fold along y=7
 fold along x=5";
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = Parsed;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("17"),
+        part2: None,
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Parsed::new(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        part_1(parsed).to_string()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        part_2(parsed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/day13.txt");
+
     #[test]
     fn test_part_1_test_input() {
-        let parsed = Parsed::new(TEST_INPUT);
+        let parsed = Parsed::new(TEST_INPUT).unwrap();
         assert_eq!(part_1(&parsed), 17);
     }
 
     #[test]
     fn test_part_1() {
-        let parsed = Parsed::new(INPUT);
+        let parsed = Parsed::new(INPUT).unwrap();
         assert_eq!(part_1(&parsed), 631);
     }
+
+    #[test]
+    fn test_part_2() {
+        let parsed = Parsed::new(INPUT).unwrap();
+        assert_eq!(part_2(&parsed), "EFLFJGRF");
+    }
 }
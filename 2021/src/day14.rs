@@ -1,19 +1,43 @@
+use aoc_common::{Example, ParseError, Solution};
 use heapless::FnvIndexMap;
 
-const INPUT: &str = include_str!("../input/day14.txt");
-
+#[derive(Clone)]
 struct Rule {
     pattern: [u8; 2],
     result: u8,
 }
 
 impl Rule {
-    pub fn from_line(line: &str) -> Self {
-        let (pattern, result) = line.split_once(" -> ").expect("invalid rule");
-        let pattern = pattern.as_bytes();
-        let pattern = [pattern[0], pattern[1]];
-        let result = result.bytes().next().expect("invalid rule");
-        Self { pattern, result }
+    /// Parses `line`, a line of `input`
+    pub fn from_line(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (pattern, result) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::at(input, line, "expected a rule, like CH -> B"))?;
+        check_elements(input, pattern)?;
+        check_elements(input, result)?;
+        match (pattern.as_bytes(), result.as_bytes()) {
+            (&[a, b], &[result]) => Ok(Self {
+                pattern: [a, b],
+                result,
+            }),
+            _ => Err(ParseError::at(
+                input,
+                line,
+                "expected a pair of elements and an element",
+            )),
+        }
+    }
+}
+
+/// Checks that `elements` are uppercase letters
+fn check_elements(input: &str, elements: &str) -> Result<(), ParseError> {
+    match elements.find(|c: char| !c.is_ascii_uppercase()) {
+        Some(i) => Err(ParseError::at(
+            input,
+            &elements[i..],
+            "expected an element, like N",
+        )),
+        None => Ok(()),
     }
 }
 
@@ -29,36 +53,67 @@ where
     }
 }
 
-struct FastPolymerizer {
+#[derive(Clone)]
+pub struct FastPolymerizer {
     polymer: FnvIndexMap<[u8; 2], u64, 128>,
     first_char: u8,
     rules: Vec<Rule>,
 }
 
 impl FastPolymerizer {
-    pub fn new(input: &str) -> Self {
-        let (polymer, rules) = input
-            .split_once("\n\n")
-            .expect("delimiter between polymer and rules not found");
-        let first_char = polymer.bytes().next().expect("empty polymer");
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (polymer, rules) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::at(
+                input,
+                input,
+                "expected a polymer and rules, separated by an empty line",
+            )
+        })?;
+        check_elements(input, polymer)?;
+        let first_char = polymer
+            .bytes()
+            .next()
+            .ok_or_else(|| ParseError::at(input, polymer, "expected a polymer"))?;
+        let rules = rules
+            .split_terminator('\n')
+            .map(|line| Ok((line, Rule::from_line(input, line)?)))
+            .collect::<Result<Vec<_>, _>>()?;
+        // only the pairs with a rule are counted
+        let has_rule = |pair| rules.iter().any(|(_, r)| r.pattern == pair);
+        for (i, elems) in polymer.as_bytes().windows(2).enumerate() {
+            if !has_rule([elems[0], elems[1]]) {
+                return Err(ParseError::at(
+                    input,
+                    &polymer[i..i + 2],
+                    "no rule for this pair",
+                ));
+            }
+        }
         let mut polymer_map = FnvIndexMap::new();
+        for (line, r) in rules.iter() {
+            if !has_rule([r.pattern[0], r.result]) || !has_rule([r.result, r.pattern[1]]) {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "no rule for the pairs this one creates",
+                ));
+            }
+            if polymer_map.insert(r.pattern, 0).is_err() {
+                let reason = format!("expected at most {} rules", polymer_map.capacity());
+                return Err(ParseError::at(input, line, reason));
+            }
+        }
         for elems in polymer.as_bytes().windows(2) {
             let key = [elems[0], elems[1]];
             fnv_map_inc(&mut polymer_map, &key, 1);
         }
-        let mut rules = rules
-            .split_terminator('\n')
-            .map(Rule::from_line)
-            .collect::<Vec<_>>();
-        rules
-            .iter()
-            .for_each(|r| fnv_map_inc(&mut polymer_map, &r.pattern, 0));
+        let mut rules = rules.into_iter().map(|(_, r)| r).collect::<Vec<_>>();
         rules.sort_by_key(|rule| rule.pattern);
-        Self {
+        Ok(Self {
             polymer: polymer_map,
             rules,
             first_char,
-        }
+        })
     }
 
     pub fn next_step(&mut self) {
@@ -91,8 +146,8 @@ impl FastPolymerizer {
         }
         fnv_map_inc(&mut counts, &self.first_char, 1);
 
-        let mut min = std::u64::MAX;
-        let mut max = std::u64::MIN;
+        let mut min = u64::MAX;
+        let mut max = u64::MIN;
 
         for elem_count in counts.values().filter(|&&x| x != 0) {
             min = std::cmp::min(min, *elem_count);
@@ -126,22 +181,7 @@ impl FastPolymerizer {
     }
 }
 
-pub fn day14() -> (String, String) {
-    let mut parsed = FastPolymerizer::new(INPUT);
-    parsed.run(10);
-
-    let part1 = parsed.most_common_minus_least_common();
-    parsed.run(30);
-    let part2 = parsed.most_common_minus_least_common();
-
-    (part1.to_string(), part2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = "NNCB
+const TEST_INPUT: &str = "NNCB
 
 CH -> B
 HH -> N
@@ -160,23 +200,57 @@ BC -> B
 CC -> N
 CN -> C";
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed<'a> = FastPolymerizer;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("1588"),
+        part2: Some("2188189693529"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        FastPolymerizer::new(input)
+    }
+
+    fn part1(polymerizer: &Self::Parsed<'_>) -> String {
+        let mut polymerizer = polymerizer.clone();
+        polymerizer.run(10);
+        polymerizer.most_common_minus_least_common().to_string()
+    }
+
+    fn part2(polymerizer: &Self::Parsed<'_>) -> String {
+        let mut polymerizer = polymerizer.clone();
+        polymerizer.run(40);
+        polymerizer.most_common_minus_least_common().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/day14.txt");
+
     #[test]
     fn test_part_1_test_input() {
-        let mut parsed = FastPolymerizer::new(TEST_INPUT);
+        let mut parsed = FastPolymerizer::new(TEST_INPUT).unwrap();
         parsed.run(10);
         assert_eq!(parsed.most_common_minus_least_common(), 1588);
     }
 
     #[test]
     fn test_part_1() {
-        let mut parsed = FastPolymerizer::new(INPUT);
+        let mut parsed = FastPolymerizer::new(INPUT).unwrap();
         parsed.run(10);
         assert_eq!(parsed.most_common_minus_least_common(), 3906);
     }
 
     #[test]
     fn test_part_2_test_input() {
-        let mut parsed = FastPolymerizer::new(TEST_INPUT);
+        let mut parsed = FastPolymerizer::new(TEST_INPUT).unwrap();
         parsed.run(40);
         assert_eq!(parsed.most_common_minus_least_common(), 2188189693529);
     }
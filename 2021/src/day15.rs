@@ -1,18 +1,17 @@
+use aoc_common::{parse, Example, ParseError, Rng, Solution};
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 use std::intrinsics::likely;
 
-const INPUT: &str = include_str!("../input/day15.txt");
-
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct State {
+pub struct State {
     pos: u32,
     cost: u32,
 }
 
 impl PartialOrd for State {
     fn partial_cmp(&self, other: &State) -> Option<Ordering> {
-        Some(other.cost.cmp(&self.cost))
+        Some(self.cmp(other))
     }
 }
 
@@ -28,25 +27,27 @@ impl State {
     }
 }
 
-struct Map {
+#[derive(Clone)]
+pub struct Map {
     width: u32,
     height: u32,
     data: Vec<u8>,
 }
 
 impl Map {
-    pub fn from_str(input: &str) -> Self {
-        let width = input.find('\n').expect("invalid map") as u32;
-        let data: Vec<u8> = input
-            .split_terminator('\n')
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let rows = parse::grid(input, "123456789")?;
+        let width = rows[0].len() as u32;
+        let data: Vec<u8> = rows
+            .into_iter()
             .flat_map(|line| line.bytes().map(|c| c - b'0'))
             .collect();
         let height = data.len() as u32 / width;
-        Self {
+        Ok(Self {
             width,
             height,
             data,
-        }
+        })
     }
 
     pub fn extend_5_times(&mut self) {
@@ -69,7 +70,7 @@ impl Map {
     fn pos2d(&self, pos1d: u32) -> (u32, u32) {
         let x = pos1d % self.width;
         let y = pos1d / self.width;
-        (x as u32, y as u32)
+        (x, y)
     }
     fn pos1d(&self, x: u32, y: u32) -> usize {
         (y * self.width + x) as usize
@@ -131,22 +132,7 @@ impl Map {
     }
 }
 
-pub fn day15() -> (String, String) {
-    let mut parsed = Map::from_str(INPUT);
-    let part1 = parsed.djikstra();
-    parsed.extend_5_times();
-    let part2 = parsed.djikstra();
-    let part1 = part1.cost;
-    let part2 = part2.cost;
-
-    (part1.to_string(), part2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = "1163751742
+const TEST_INPUT: &str = "1163751742
 1381373672
 2136511328
 3694931569
@@ -157,32 +143,133 @@ mod test {
 1293138521
 2311944581";
 
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Map;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("40"),
+        part2: Some("315"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Map::parse(input)
+    }
+
+    fn part1(map: &Self::Parsed<'_>) -> String {
+        map.djikstra().cost.to_string()
+    }
+
+    fn part2(map: &Self::Parsed<'_>) -> String {
+        let mut map = map.clone();
+        map.extend_5_times();
+        map.djikstra().cost.to_string()
+    }
+
+    /// A `size` x `size` risk map
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let mut input = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            input.extend((0..size).map(|_| char::from(b'1' + rng.below(9) as u8)));
+            input.push('\n');
+        }
+        Some(input)
+    }
+
+    /// Relaxes every risk until none changes, on a map tiled by hand
+    fn reference(input: &str, part: u8) -> Option<String> {
+        let tile: Vec<Vec<u32>> = input
+            .lines()
+            .map(|l| l.bytes().map(|b| (b - b'0') as u32).collect())
+            .collect();
+        let tiles = if part == 1 { 1 } else { 5 };
+        let (height, width) = (tile.len() * tiles, tile[0].len() * tiles);
+        let risk = |x: usize, y: usize| {
+            let (h, w) = (tile.len(), tile[0].len());
+            (tile[y % h][x % w] + (x / w + y / h) as u32 - 1) % 9 + 1
+        };
+        let mut total = vec![vec![u32::MAX; width]; height];
+        total[0][0] = 0;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for y in 0..height {
+                for x in 0..width {
+                    let neighbours = [
+                        (x.wrapping_sub(1), y),
+                        (x + 1, y),
+                        (x, y.wrapping_sub(1)),
+                        (x, y + 1),
+                    ];
+                    for (nx, ny) in neighbours {
+                        if nx < width && ny < height && total[ny][nx] != u32::MAX {
+                            let through = total[ny][nx] + risk(x, y);
+                            if through < total[y][x] {
+                                total[y][x] = through;
+                                changed = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Some(total[height - 1][width - 1].to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/day15.txt");
+
     #[test]
     fn test_part_1_test_input() {
-        let parsed = Map::from_str(TEST_INPUT);
+        let parsed = Map::parse(TEST_INPUT).unwrap();
         let final_state = parsed.djikstra();
         assert_eq!(final_state.cost, 40);
     }
     #[test]
     fn test_part_1() {
-        let parsed = Map::from_str(INPUT);
+        let parsed = Map::parse(INPUT).unwrap();
         let final_state = parsed.djikstra();
         assert_eq!(final_state.cost, 373);
     }
 
     #[test]
     fn test_part_2_test_input() {
-        let mut parsed = Map::from_str(TEST_INPUT);
+        let mut parsed = Map::parse(TEST_INPUT).unwrap();
         parsed.extend_5_times();
         parsed._print();
         let final_state = parsed.djikstra();
         assert_eq!(final_state.cost, 315);
     }
 
+    #[test]
+    fn test_generate() {
+        let input = Day15::generate(30, &mut Rng::new(15)).unwrap();
+        assert_eq!(
+            Some(&input),
+            Day15::generate(30, &mut Rng::new(15)).as_ref()
+        );
+        let parsed = Map::parse(&input).unwrap();
+        assert_eq!(input.lines().count(), 30);
+        // at least the risk of a straight path, at most the risk of the worst one
+        let cost = parsed.djikstra().cost as usize;
+        assert!((58..=58 * 9).contains(&cost));
+    }
+
+    #[test]
+    fn test_reference() {
+        aoc_common::differential::check::<Day15>(8, 0..30).unwrap();
+    }
+
     #[test]
     #[cfg_attr(not(feature = "expensive_tests"), ignore)]
     fn test_part_2() {
-        let mut parsed = Map::from_str(INPUT);
+        let mut parsed = Map::parse(INPUT).unwrap();
         parsed.extend_5_times();
         let final_state = parsed.djikstra();
         assert_eq!(final_state.cost, 2868);
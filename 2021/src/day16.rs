@@ -1,9 +1,7 @@
-use itertools::Itertools;
+use aoc_common::{Example, ParseError, Solution};
 use std::fmt;
 use std::ops::{BitOr, Shl};
 
-const INPUT: &str = include_str!("../input/day16.txt");
-
 trait PeekableIterator: std::iter::Iterator {
     fn peek(&mut self) -> Option<&Self::Item>;
 }
@@ -15,7 +13,7 @@ impl<I: std::iter::Iterator> PeekableIterator for std::iter::Peekable<I> {
 }
 
 #[derive(Debug, Clone)]
-struct Packet {
+pub struct Packet {
     version: u32,
     value: u64,
 }
@@ -32,19 +30,40 @@ where
     bits
 }
 
+/// Reads the next `n` bits, that must be there
+fn read_bits<T>(iter: &mut impl Iterator<Item = u8>, n: usize) -> Result<T, &'static str>
+where
+    T: num::Integer + num::NumCast + BitOr<T, Output = T> + Shl<T, Output = T> + fmt::Display,
+{
+    let mut count = 0;
+    let bits = bytes_to_bits(iter.take(n).inspect(|_| count += 1));
+    match count == n {
+        true => Ok(bits),
+        false => Err(END_OF_TRANSMISSION),
+    }
+}
+
+const END_OF_TRANSMISSION: &str = "unexpected end of the transmission";
+
 fn parse_operator_ops(
     iter: &mut impl PeekableIterator<Item = u8>,
     max_nb: Option<usize>,
     type_id: u8,
-) -> Packet {
+) -> Result<Packet, &'static str> {
     let mut ops = match max_nb {
         Some(nb) => Vec::with_capacity(nb),
         None => Vec::new(),
     };
     while iter.peek().is_some() && (max_nb.is_none() || ops.len() < ops.capacity()) {
-        ops.push(Packet::from_iterator(iter));
+        ops.push(Packet::from_iterator(iter)?);
+    }
+    if max_nb.is_some_and(|nb| ops.len() < nb) {
+        return Err(END_OF_TRANSMISSION);
+    }
+    if (5..=7).contains(&type_id) && ops.len() != 2 {
+        return Err("expected two sub-packets to compare");
     }
-    match type_id {
+    Ok(match type_id {
         0 => ops.into_iter().fold(Packet::default(), |acc, x| Packet {
             version: acc.version + x.version,
             value: acc.value + x.value,
@@ -97,50 +116,50 @@ fn parse_operator_ops(
                 value: (ops[0].value == ops[1].value) as u64,
             }
         }
-        x => panic!("Unknown operation type id: {}", x),
-    }
+        _ => return Err("unknown packet type"),
+    })
 }
 
-fn value_from_value_iter(iter: &mut impl PeekableIterator<Item = u8>) -> u64 {
+fn value_from_value_iter(iter: &mut impl PeekableIterator<Item = u8>) -> Result<u64, &'static str> {
     let mut val = 0;
     let mut done = false;
     while !done {
-        done = iter
-            .next()
-            .expect("Unexpected end of stream while reading value group prefix bit")
-            == b'0';
-        val = (val << 4) | bytes_to_bits::<u64, _>(iter.by_ref().take(4));
+        done = iter.next().ok_or(END_OF_TRANSMISSION)? == b'0';
+        val = (val << 4) | read_bits::<u64>(iter, 4)?;
     }
-    val
+    Ok(val)
 }
 
 fn packet_from_operator_iter(
     iter: &mut impl PeekableIterator<Item = u8>,
     type_id: u8,
     version: u32,
-) -> Packet {
-    let length_type_id = iter.next().expect("expected length type id");
+) -> Result<Packet, &'static str> {
+    let length_type_id = iter.next().ok_or(END_OF_TRANSMISSION)?;
 
     let packet = match length_type_id {
         b'1' => {
-            let length = bytes_to_bits(iter.by_ref().take(11));
-            parse_operator_ops(iter, Some(length), type_id)
+            let length = read_bits(iter, 11)?;
+            parse_operator_ops(iter, Some(length), type_id)?
         }
         b'0' => {
-            let length: isize = bytes_to_bits(iter.by_ref().take(15));
+            let length: isize = read_bits(iter, 15)?;
             // error[E0275]: overflow evaluating the requirement `Peekable<std::str::Bytes>: Iterator`
             // let mut it = iter.take(length as usize).peekable();
             #[allow(clippy::needless_collect)]
             let col = iter.take(length as usize).collect::<Vec<_>>();
+            if col.len() < length as usize {
+                return Err(END_OF_TRANSMISSION);
+            }
             let mut it = col.into_iter().peekable();
-            parse_operator_ops(&mut it, None, type_id)
+            parse_operator_ops(&mut it, None, type_id)?
         }
         _ => unreachable!(),
     };
-    Packet {
+    Ok(Packet {
         value: packet.value,
         version: packet.version + version,
-    }
+    })
 }
 
 impl Packet {
@@ -151,55 +170,140 @@ impl Packet {
         }
     }
 
-    fn from_iterator(iter: &mut impl PeekableIterator<Item = u8>) -> Self {
-        let version = bytes_to_bits(iter.take(3));
-        let packet_type = bytes_to_bits(iter.take(3));
+    fn from_iterator(iter: &mut impl PeekableIterator<Item = u8>) -> Result<Self, &'static str> {
+        let version = read_bits(iter, 3)?;
+        let packet_type = read_bits(iter, 3)?;
         match packet_type {
-            4 => Packet {
+            4 => Ok(Packet {
                 version,
-                value: value_from_value_iter(iter),
-            },
+                value: value_from_value_iter(iter)?,
+            }),
             id => packet_from_operator_iter(iter, id, version),
         }
     }
 }
 
-fn parse(input: &'static str) -> Packet {
-    let bin = input
-        .trim()
-        .chars()
-        .map(|c| match c {
-            '0' => "0000",
-            '1' => "0001",
-            '2' => "0010",
-            '3' => "0011",
-            '4' => "0100",
-            '5' => "0101",
-            '6' => "0110",
-            '7' => "0111",
-            '8' => "1000",
-            '9' => "1001",
-            'A' => "1010",
-            'B' => "1011",
-            'C' => "1100",
-            'D' => "1101",
-            'E' => "1110",
-            'F' => "1111",
-            _ => panic!("invalid character"),
+fn parse(input: &str) -> Result<Packet, ParseError> {
+    let hex = input.trim();
+    let bin = hex
+        .char_indices()
+        .map(|(i, c)| {
+            Ok(match c {
+                '0' => "0000",
+                '1' => "0001",
+                '2' => "0010",
+                '3' => "0011",
+                '4' => "0100",
+                '5' => "0101",
+                '6' => "0110",
+                '7' => "0111",
+                '8' => "1000",
+                '9' => "1001",
+                'A' => "1010",
+                'B' => "1011",
+                'C' => "1100",
+                'D' => "1101",
+                'E' => "1110",
+                'F' => "1111",
+                _ => {
+                    return Err(ParseError::at(
+                        input,
+                        &hex[i..],
+                        "expected a hexadecimal digit",
+                    ))
+                }
+            })
         })
+        .collect::<Result<Vec<_>, _>>()?
         .join("");
 
     let mut iter = bin.bytes().peekable();
-    Packet::from_iterator(&mut iter)
+    Packet::from_iterator(&mut iter).map_err(|reason| {
+        // where the decoding stopped
+        let read = (bin.len() - iter.len()) / 4;
+        ParseError::at(input, &hex[read.min(hex.len())..], reason)
+    })
 }
 
-pub fn day16() -> (String, String) {
-    let parsed = parse(INPUT);
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed<'a> = Packet;
 
-    let part1 = parsed.version;
-    let part2 = parsed.value;
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: "8A004A801A8002F478",
+            part1: Some("16"),
+            part2: None,
+        },
+        Example {
+            input: "620080001611562C8802118E34",
+            part1: Some("12"),
+            part2: None,
+        },
+        Example {
+            input: "C0015000016115A2E0802F182340",
+            part1: Some("23"),
+            part2: None,
+        },
+        Example {
+            input: "A0016C880162017C3686B18A3D4780",
+            part1: Some("31"),
+            part2: None,
+        },
+        Example {
+            input: "C200B40A82",
+            part1: None,
+            part2: Some("3"),
+        },
+        Example {
+            input: "04005AC33890",
+            part1: None,
+            part2: Some("54"),
+        },
+        Example {
+            input: "880086C3E88112",
+            part1: None,
+            part2: Some("7"),
+        },
+        Example {
+            input: "CE00C43D881120",
+            part1: None,
+            part2: Some("9"),
+        },
+        Example {
+            input: "D8005AC2A8F0",
+            part1: None,
+            part2: Some("1"),
+        },
+        Example {
+            input: "F600BC2D8F",
+            part1: None,
+            part2: Some("0"),
+        },
+        Example {
+            input: "9C005AC2F8F0",
+            part1: None,
+            part2: Some("0"),
+        },
+        Example {
+            input: "9C0141080250320F1802104A08",
+            part1: None,
+            part2: Some("1"),
+        },
+    ];
 
-    (part1.to_string(), part2.to_string())
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(packet: &Self::Parsed<'_>) -> String {
+        packet.version.to_string()
+    }
+
+    fn part2(packet: &Self::Parsed<'_>) -> String {
+        packet.value.to_string()
+    }
 }
 
 #[cfg(test)]
@@ -209,46 +313,46 @@ mod test {
     #[test]
     fn test_value_packet_parsing() {
         let mut it = "110100101111111000101000".bytes().peekable();
-        let packet = Packet::from_iterator(&mut it);
+        let packet = Packet::from_iterator(&mut it).unwrap();
         assert_eq!(packet.version, 6);
         assert_eq!(packet.value, 2021);
     }
 
     #[test]
     fn test_part_1_test_input() {
-        let parsed = parse("8A004A801A8002F478");
+        let parsed = parse("8A004A801A8002F478").unwrap();
         assert_eq!(parsed.version, 16);
-        let parsed = parse("620080001611562C8802118E34");
+        let parsed = parse("620080001611562C8802118E34").unwrap();
         assert_eq!(parsed.version, 12);
-        let parsed = parse("A0016C880162017C3686B18A3D4780");
+        let parsed = parse("A0016C880162017C3686B18A3D4780").unwrap();
         assert_eq!(parsed.version, 31);
     }
 
     #[test]
     fn test_part_2_test_intput() {
         // sum
-        let parsed = parse("C200B40A82");
+        let parsed = parse("C200B40A82").unwrap();
         assert_eq!(parsed.value, 3);
         // product
-        let parsed = parse("04005AC33890");
+        let parsed = parse("04005AC33890").unwrap();
         assert_eq!(parsed.value, 54);
         // min
-        let parsed = parse("880086C3E88112");
+        let parsed = parse("880086C3E88112").unwrap();
         assert_eq!(parsed.value, 7);
         // max
-        let parsed = parse("CE00C43D881120");
+        let parsed = parse("CE00C43D881120").unwrap();
         assert_eq!(parsed.value, 9);
         // less than
-        let parsed = parse("D8005AC2A8F0");
+        let parsed = parse("D8005AC2A8F0").unwrap();
         assert_eq!(parsed.value, 1);
         // greater than
-        let parsed = parse("F600BC2D8F");
+        let parsed = parse("F600BC2D8F").unwrap();
         assert_eq!(parsed.value, 0);
         // equal to
-        let parsed = parse("9C005AC2F8F0");
+        let parsed = parse("9C005AC2F8F0").unwrap();
         assert_eq!(parsed.value, 0);
         // sum, prod, equal
-        let parsed = parse("9C0141080250320F1802104A08");
+        let parsed = parse("9C0141080250320F1802104A08").unwrap();
         assert_eq!(parsed.value, 1);
     }
 }
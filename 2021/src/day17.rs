@@ -1,7 +1,7 @@
-const INPUT: &str = include_str!("../input/day17.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
 #[derive(Debug)]
-struct Parsed {
+pub struct Parsed {
     left: i64,
     right: i64,
     up: i64,
@@ -12,24 +12,47 @@ type Point = (i64, i64);
 type Velocity = (i64, i64);
 
 impl Parsed {
-    fn from_input(input: &str) -> Self {
-        let val_str = input.trim_end().split_once(": ").unwrap().1;
-        let (x_str, y_str) = val_str.split_once(", ").unwrap();
-        let (xa, xb) = x_str[2..].split_once("..").unwrap();
-        let (ya, yb) = y_str[2..].split_once("..").unwrap();
-        let left = xa.parse().unwrap();
-        let right = xb.parse().unwrap();
-        let up = yb.parse().unwrap();
-        let down = ya.parse().unwrap();
-        debug_assert!(left < right);
-        debug_assert!(up > down);
-
-        Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        let line = input.trim_end();
+        let (x_str, y_str) = line
+            .strip_prefix("target area: x=")
+            .and_then(|area| area.split_once(", y="))
+            .ok_or_else(|| {
+                ParseError::at(input, line, "expected target area: x=<a>..<b>, y=<c>..<d>")
+            })?;
+        let range = |range: &str| {
+            let (a, b) = range
+                .split_once("..")
+                .ok_or_else(|| ParseError::at(input, range, "expected a range, like 20..30"))?;
+            Ok::<_, ParseError>((
+                parse::token::<i64>(input, a)?,
+                parse::token::<i64>(input, b)?,
+            ))
+        };
+        let (left, right) = range(x_str)?;
+        let (down, up) = range(y_str)?;
+        // the probe is shot from above and left of the target
+        if !(0 < left && left <= right) {
+            return Err(ParseError::at(
+                input,
+                x_str,
+                "expected a range of positive x",
+            ));
+        }
+        if !(down <= up && up < 0) {
+            return Err(ParseError::at(
+                input,
+                y_str,
+                "expected a range of negative y",
+            ));
+        }
+
+        Ok(Self {
             left,
             right,
             up,
             down,
-        }
+        })
     }
     fn contains(&self, pt: &Point) -> bool {
         self.left <= pt.0 && pt.0 <= self.right && pt.1 <= self.up && pt.1 >= self.down
@@ -56,40 +79,55 @@ fn simulate_point(initial_velocity: Velocity, bounds: &Parsed) -> Option<i64> {
     None
 }
 
-fn find_hits(bounds: &Parsed) -> (i64, usize) {
-    let mut max_y = i64::MIN;
-    let mut nb_hits = 0;
-
-    for vx in 0..=bounds.right {
+/// The highest point of every shot that hits the target
+fn hits(bounds: &Parsed) -> impl Iterator<Item = i64> + '_ {
+    (0..=bounds.right).flat_map(move |vx| {
         let vy_range = bounds.down..=(-bounds.down);
-        for vy in vy_range {
-            if let Some(y) = simulate_point((vx, vy), bounds) {
-                max_y = max_y.max(y);
-                nb_hits += 1;
-            }
-        }
-    }
-    (max_y, nb_hits)
+        vy_range.filter_map(move |vy| simulate_point((vx, vy), bounds))
+    })
+}
+
+fn part_1(bounds: &Parsed) -> i64 {
+    hits(bounds).max().unwrap_or(i64::MIN)
 }
 
-fn solve(input: &Parsed) -> (i64, usize) {
-    find_hits(input)
+fn part_2(bounds: &Parsed) -> usize {
+    hits(bounds).count()
 }
 
-pub fn day17() -> (String, String) {
-    let parsed = Parsed::from_input(INPUT);
-    let (part1, part2) = solve(&parsed);
+pub struct Day17;
 
-    (part1.to_string(), part2.to_string())
+impl Solution for Day17 {
+    type Parsed<'a> = Parsed;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "target area: x=20..30, y=-10..-5",
+        part1: Some("45"),
+        part2: Some("112"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Parsed::from_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        part_1(parsed).to_string()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        part_2(parsed).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    const INPUT: &str = include_str!("../input/day17.txt");
+
     #[test]
     fn test_simulate_point() {
-        let p = Parsed::from_input("target area: x=20..30, y=-10..-5");
+        let p = Parsed::from_input("target area: x=20..30, y=-10..-5").unwrap();
         let res = simulate_point((7, 2), &p);
         assert!(res.is_some());
         let res = simulate_point((6, 3), &p);
@@ -107,29 +145,25 @@ mod test {
 
     #[test]
     fn test_part1_test_input() {
-        let p = Parsed::from_input("target area: x=20..30, y=-10..-5");
-        let (p1, _) = solve(&p);
-        assert_eq!(p1, 45);
+        let p = Parsed::from_input("target area: x=20..30, y=-10..-5").unwrap();
+        assert_eq!(part_1(&p), 45);
     }
 
     #[test]
     fn test_part1() {
-        let p = Parsed::from_input(INPUT);
-        let (p1, _) = solve(&p);
-        assert_eq!(p1, 12246);
+        let p = Parsed::from_input(INPUT).unwrap();
+        assert_eq!(part_1(&p), 12246);
     }
 
     #[test]
     fn test_part2() {
-        let p = Parsed::from_input(INPUT);
-        let (_, p2) = solve(&p);
-        assert_eq!(p2, 3528);
+        let p = Parsed::from_input(INPUT).unwrap();
+        assert_eq!(part_2(&p), 3528);
     }
 
     #[test]
     fn test_part2_test_input() {
-        let p = Parsed::from_input("target area: x=20..30, y=-10..-5");
-        let (_, p2) = solve(&p);
-        assert_eq!(p2, 112);
+        let p = Parsed::from_input("target area: x=20..30, y=-10..-5").unwrap();
+        assert_eq!(part_2(&p), 112);
     }
 }
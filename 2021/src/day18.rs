@@ -1,6 +1,4 @@
-const INPUT: &str = include_str!("../input/day18.txt");
-
-trait Nesting: std::fmt::Display {}
+use aoc_common::{parse, Example, ParseError, Solution};
 
 #[derive(Debug, Clone)]
 struct NumComp {
@@ -10,7 +8,7 @@ struct NumComp {
 }
 
 #[derive(Debug, Clone)]
-struct Num {
+pub struct Num {
     inner: Vec<NumComp>,
 }
 
@@ -170,23 +168,56 @@ fn part2(input: Vec<Num>) -> u32 {
     max_magnitude
 }
 
-fn parse(input: &str) -> Vec<Num> {
-    input.split_terminator('\n').map(Num::from_input).collect()
-}
-
-pub fn day18() -> (String, String) {
-    let parsed = parse(INPUT);
-    let part1 = part1(&parsed);
-    let part2 = part2(parsed);
+/// Checks that `line` is a pair of regular numbers (digits) or pairs, nested at most 4 times
+fn check_number(input: &str, line: &str) -> Result<(), ParseError> {
+    fn element(line: &[u8], i: &mut usize, depth: usize) -> Result<(), &'static str> {
+        match line.get(*i) {
+            Some(b'0'..=b'9') if depth > 0 => *i += 1,
+            Some(b'[') if depth < 4 => {
+                *i += 1;
+                element(line, i, depth + 1)?;
+                expect(line, i, b',')?;
+                element(line, i, depth + 1)?;
+                expect(line, i, b']')?;
+            }
+            Some(b'[') => return Err("pairs are nested at most 4 times"),
+            _ if depth == 0 => return Err("expected a pair"),
+            _ => return Err("expected a digit or a pair"),
+        }
+        Ok(())
+    }
+    fn expect(line: &[u8], i: &mut usize, c: u8) -> Result<(), &'static str> {
+        match line.get(*i) == Some(&c) {
+            true => {
+                *i += 1;
+                Ok(())
+            }
+            false if c == b',' => Err("expected ','"),
+            false => Err("expected ']'"),
+        }
+    }
 
-    (part1.to_string(), part2.to_string())
+    let mut i = 0;
+    element(line.as_bytes(), &mut i, 0)
+        .and(match i == line.len() {
+            true => Ok(()),
+            false => Err("expected the end of the number"),
+        })
+        .map_err(|reason| ParseError::at(input, &line[i..], reason))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+fn parse(input: &str) -> Result<Vec<Num>, ParseError> {
+    let numbers = input
+        .split_terminator('\n')
+        .map(|line| {
+            check_number(input, line)?;
+            Ok(Num::from_input(line))
+        })
+        .collect::<Result<_, _>>()?;
+    parse::at_least(input, numbers, 1, "number")
+}
 
-    const TEST_INPUT: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+const TEST_INPUT: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
 [[[5,[2,8]],4],[5,[[9,9],0]]]
 [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
 [[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
@@ -197,6 +228,34 @@ mod test {
 [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
 [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed<'a> = Vec<Num>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("4140"),
+        part2: Some("3993"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(numbers: &Self::Parsed<'_>) -> String {
+        part1(numbers).to_string()
+    }
+
+    fn part2(numbers: &Self::Parsed<'_>) -> String {
+        part2(numbers.clone()).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_num_parsing() {
         let s = "[[[[4,3],4],4],[7,[[8,4],9]]]";
@@ -263,11 +322,11 @@ mod test {
 
     #[test]
     fn test_part1_test_input() {
-        assert_eq!(part1(&parse(TEST_INPUT)), 4140);
+        assert_eq!(part1(&parse(TEST_INPUT).unwrap()), 4140);
     }
 
     #[test]
     fn test_part2_test_input() {
-        assert_eq!(part2(parse(TEST_INPUT)), 3993);
+        assert_eq!(part2(parse(TEST_INPUT).unwrap()), 3993);
     }
 }
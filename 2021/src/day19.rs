@@ -1,3 +1,4 @@
+use aoc_common::{parse, Example, ParseError, Solution};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 
@@ -12,8 +13,6 @@ use nom::{
 
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("../input/day19.txt");
-
 // The threshold for number of overlapping probes is 12, this translates to n*(n-1)/2 egdes.
 const ALIGNMENT_THRESHOLD: u32 = 12;
 const EDGE_THRESHOLD: u32 = ALIGNMENT_THRESHOLD * (ALIGNMENT_THRESHOLD - 1) / 2;
@@ -26,15 +25,20 @@ struct Scanner {
 
 #[derive(Debug, Clone)]
 /// Wrapper around [`Scanner`] with additional info about its state
-struct ScannerInfo {
+pub struct ScannerInfo {
     scanner: Scanner,
     inner_distances: Vec<u32>, // needs to be sorted by dist for optimal performance
     position: Option<Vector3<i32>>,
     orientation: Option<Matrix3<i32>>,
 }
 
+/// The answer when a scanner cannot be aligned with the others
+const NOT_ALIGNED: &str = "the scanners cannot all be aligned";
+
 fn part1(scanners: Vec<ScannerInfo>) -> String {
-    let aligned = align_scanners(scanners);
+    let Some(aligned) = align_scanners(scanners) else {
+        return NOT_ALIGNED.to_string();
+    };
     // Check Number of Beacons
     let mut beacons = Vec::new();
     for scanner_info in aligned.values() {
@@ -51,7 +55,9 @@ fn part1(scanners: Vec<ScannerInfo>) -> String {
 }
 
 fn part2(scanners: Vec<ScannerInfo>) -> String {
-    let aligned = align_scanners(scanners);
+    let Some(aligned) = align_scanners(scanners) else {
+        return NOT_ALIGNED.to_string();
+    };
 
     let mut scanner_positions = Vec::new();
     for scanner_info in aligned.values() {
@@ -149,8 +155,8 @@ fn align_scanner(s1: &mut ScannerInfo, s2: &ScannerInfo) -> bool {
     true
 }
 
-/// Aligns all scanners
-fn align_scanners(scanners: Vec<ScannerInfo>) -> HashMap<u32, ScannerInfo> {
+/// Aligns all scanners, `None` if some do not overlap with the others
+fn align_scanners(scanners: Vec<ScannerInfo>) -> Option<HashMap<u32, ScannerInfo>> {
     let mut unaligned = HashMap::new();
     let mut visited = HashMap::new();
 
@@ -183,12 +189,8 @@ fn align_scanners(scanners: Vec<ScannerInfo>) -> HashMap<u32, ScannerInfo> {
         }
         visited.insert(scanner_info.scanner.id, scanner_info);
     }
-    assert_eq!(
-        unaligned.len(),
-        0,
-        "There are still unaligned scanners left over"
-    );
-    visited
+    // There may still be unaligned scanners left over
+    unaligned.is_empty().then_some(visited)
 }
 
 /// Uses the distances between beacons to find potential other scanners, which overlap their regions with
@@ -306,7 +308,7 @@ fn possible_orientations() -> Vec<Matrix3<i32>> {
     let matrices: Vec<Matrix3<i32>> = vec![vec![1, 0, 0], vec![0, 1, 0], vec![0, 0, 1]]
         .into_iter()
         .permutations(3)
-        .map(|e| Matrix3::from_iterator(e.concat().into_iter()))
+        .map(|e| Matrix3::from_iterator(e.concat()))
         .flat_map(|m| {
             let mut m2 = m;
             multiply_row(&mut m2, 0, -1);
@@ -353,53 +355,58 @@ fn compare_vector(v1: &Vector3<i32>, v2: &Vector3<i32>) -> Ordering {
 }
 
 // --- PARSING ---
-fn parse_scanner_id(input: &str) -> u32 {
+fn parse_scanner_id(input: &str, heading: &str) -> Result<u32, ParseError> {
     let mut parser = preceded::<&str, _, _, Error<&str>, _, _>(
         tag("--- scanner "),
         terminated(complete::u32, tag(" ---")),
     );
-    parser(input).unwrap().1
+    match parser(heading) {
+        Ok(("", id)) => Ok(id),
+        _ => Err(ParseError::at(
+            input,
+            heading,
+            "expected --- scanner <id> ---",
+        )),
+    }
 }
 
-fn parse_beacon(input: &str) -> Vector3<i32> {
-    let mut coords = input.trim().split(',');
-    let x = coords.next().unwrap().parse().unwrap();
-    let y = coords.next().unwrap().parse().unwrap();
-    let z = coords.next().unwrap().parse().unwrap();
-    Vector3::from_iterator(vec![x, y, z].into_iter())
+fn parse_beacon(input: &str, beacon: &str) -> Result<Vector3<i32>, ParseError> {
+    let mut coords = beacon.trim().split(',');
+    let mut coord = || parse::token(input, parse::next(input, &mut coords, "a coordinate")?);
+    let (x, y, z) = (coord()?, coord()?, coord()?);
+    if let Some(extra) = coords.next() {
+        return Err(ParseError::at(input, extra, "expected 3 coordinates"));
+    }
+    Ok(Vector3::from_iterator(vec![x, y, z]))
 }
 
-fn parse(input: &str) -> Vec<Scanner> {
+fn parse(input: &str) -> Result<Vec<Scanner>, ParseError> {
     let scans = input.split("\n\n");
     let mut scanners = Vec::new();
 
     for scan in scans {
         let mut lines = scan.lines();
-        let heading = lines.next().unwrap();
-        let id = parse_scanner_id(heading);
+        let heading = parse::next(input, &mut lines, "a scanner")?;
+        let id = parse_scanner_id(input, heading)?;
+        // the scanners are aligned from scanner 0, and found by id
+        if id as usize != scanners.len() {
+            let reason = format!("expected scanner {}", scanners.len());
+            return Err(ParseError::at(input, heading, reason));
+        }
         let mut beacons = Vec::new();
         for beacon in lines {
-            beacons.push(parse_beacon(beacon));
+            beacons.push(parse_beacon(input, beacon)?);
+        }
+        if beacons.is_empty() {
+            return Err(ParseError::at(input, heading, "expected a beacon"));
         }
         beacons.sort_by(compare_vector);
         scanners.push(Scanner { id, beacons });
     }
-    scanners
-}
-
-pub fn day19() -> (String, String) {
-    let parsed: Vec<_> = parse(INPUT).into_iter().map(analyze_scanner).collect();
-    let p1 = part1(parsed.clone());
-    let p2 = part2(parsed);
-    (p1, p2)
+    Ok(scanners)
 }
 
-#[cfg(test)]
-mod tests {
-
-    use super::*;
-
-    const TEST_INPUT: &str = "--- scanner 0 ---
+const TEST_INPUT: &str = "--- scanner 0 ---
 404,-588,-901
 528,-643,409
 -838,591,734
@@ -536,10 +543,39 @@ mod tests {
 -652,-548,-490
 30,-46,-14";
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = Vec<ScannerInfo>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("79"),
+        part2: Some("3621"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Ok(parse(input)?.into_iter().map(analyze_scanner).collect())
+    }
+
+    fn part1(scanners: &Self::Parsed<'_>) -> String {
+        part1(scanners.clone())
+    }
+
+    fn part2(scanners: &Self::Parsed<'_>) -> String {
+        part2(scanners.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
     #[test]
     fn test_manhattan_dist() {
-        let v1 = Vector3::from_iterator(vec![0, 2, -2].into_iter());
-        let v2 = Vector3::from_iterator(vec![0, -2, 1].into_iter());
+        let v1 = Vector3::from_iterator(vec![0, 2, -2]);
+        let v2 = Vector3::from_iterator(vec![0, -2, 1]);
         assert_eq!(manhattan_distance(&v1, &v2), 7);
     }
 
@@ -554,9 +590,9 @@ mod tests {
 
     #[test]
     fn test_utilities() {
-        let v1 = Vector3::from_iterator(vec![0, 2, -2].into_iter());
-        let v2 = Vector3::from_iterator(vec![0, 2, -2].into_iter());
-        let v3 = Vector3::from_iterator(vec![-1, 2, -2].into_iter());
+        let v1 = Vector3::from_iterator(vec![0, 2, -2]);
+        let v2 = Vector3::from_iterator(vec![0, 2, -2]);
+        let v3 = Vector3::from_iterator(vec![-1, 2, -2]);
         let vec1 = vec![v3, v2];
         let vec2 = vec![v1];
         assert_eq!(equal_vector_count(&vec1, &vec2), 1);
@@ -564,13 +600,22 @@ mod tests {
 
     #[test]
     fn test_parsing() {
-        let parsed = parse(TEST_INPUT);
+        let parsed = parse(TEST_INPUT).unwrap();
         assert_eq!(parsed.len(), 5);
         assert_eq!(parsed[0].id, 0);
         assert_eq!(parsed[0].beacons.len(), 25);
         assert_eq!(
             parsed[1].beacons[0],
-            Vector3::from_iterator(vec![-500, -761, 534].into_iter())
+            Vector3::from_iterator(vec![-500, -761, 534])
         );
     }
+
+    #[test]
+    fn test_unaligned() {
+        // scanner 2 cut down to 2 beacons, too few to overlap with the others
+        let cut = TEST_INPUT.find("-784,533,-524").unwrap();
+        let scanners = Day19::parse(&TEST_INPUT[..cut]).unwrap();
+        assert_eq!(Day19::part1(&scanners), NOT_ALIGNED);
+        assert_eq!(Day19::part2(&scanners), NOT_ALIGNED);
+    }
 }
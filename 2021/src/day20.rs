@@ -1,8 +1,7 @@
+use aoc_common::{Example, ParseError, Solution};
 use bitvec::prelude::*;
 use itertools::Itertools;
 
-const INPUT: &str = include_str!("../input/day20.txt");
-
 #[derive(Debug, Clone)]
 struct Image {
     img: BitVec,
@@ -78,31 +77,47 @@ impl std::fmt::Display for Image {
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub struct Input {
     algorithm: BitVec,
     image: Image,
 }
 
 impl Input {
-    pub fn from_str(s: &str) -> Self {
-        let mut b_it = s.as_bytes().iter();
-        let algorithm = b_it
-            .by_ref()
-            .take_while(|&&c| c != b'\n')
-            .map(|&c| c == b'#')
-            .collect::<BitVec>();
-        let sep = *b_it.next().unwrap();
-        debug_assert_eq!(sep, b'\n');
-        let image_size = b_it.clone().take_while(|&&c| c != b'\n').count();
-        let input_image = b_it
-            .filter(|&&c| c != b'\n')
-            .map(|&c| c == b'#')
-            .collect::<BitVec>();
-        debug_assert_eq!(input_image.len(), image_size * image_size);
-        Self {
+    pub fn parse<'a>(s: &'a str) -> Result<Self, ParseError> {
+        let pixels = |line: &'a str| match line.find(|c| c != '#' && c != '.') {
+            Some(i) => Err(ParseError::at(s, &line[i..], "expected '#' or '.'")),
+            None => Ok(line.bytes().map(|c| c == b'#')),
+        };
+        let (algorithm, image) = s.split_once("\n\n").ok_or_else(|| {
+            ParseError::at(
+                s,
+                s,
+                "expected an algorithm and an image, separated by an empty line",
+            )
+        })?;
+        // a pixel and its 8 neighbors index the algorithm
+        if algorithm.len() != 512 {
+            return Err(ParseError::at(
+                s,
+                algorithm,
+                "expected an algorithm of 512 pixels",
+            ));
+        }
+        let algorithm = pixels(algorithm)?.collect::<BitVec>();
+        let rows = image.lines().collect::<Vec<_>>();
+        let image_size = rows.len();
+        let mut input_image = BitVec::new();
+        for row in rows {
+            if row.len() != image_size {
+                let reason = format!("expected a square image, of {image_size} pixels per row");
+                return Err(ParseError::at(s, row, reason));
+            }
+            input_image.extend(pixels(row)?);
+        }
+        Ok(Self {
             algorithm,
             image: Image::from_bitvec(input_image, image_size),
-        }
+        })
     }
 
     pub fn enhance_image(&mut self) {
@@ -122,24 +137,7 @@ impl Input {
     }
 }
 
-pub fn day20() -> (String, String) {
-    let mut parsed = Input::from_str(INPUT);
-    parsed.enhance_image();
-    parsed.enhance_image();
-    let p1 = parsed.image.img.count_ones().to_string();
-    for _ in 0..(50 - 2) {
-        parsed.enhance_image();
-    }
-    let p2 = parsed.image.img.count_ones().to_string();
-    (p1, p2)
-}
-
-#[cfg(test)]
-mod tests {
-
-    use super::*;
-
-    const TEST_INPUT: &str =
+const TEST_INPUT: &str =
         "..#.#..#####.#.#.#.###.##.....###.##.#..###.####..#####..#....#..#..##..###..######.###...####..#..#####..##..#.#####...##.#.#..#.##..#.#......#.###.######.###.####...#.##.##..#..#..#####.....#.#....###..#.##......#.....#..#..#..##..#...##.######.####.####.#.#...#.......#..#.#.#...####.##.#......#..#...##.#.##..#...##.#.##..###.#......#.#.......#.#.#.####.###.##...#.....####.#..#..#.##.#....##..#.####....##...##..#...#......#.#.......#.......##..####..#...#.#.#...##..#.#..###..#####........#..####......#..#\n\
         \n\
         #..#.\n\
@@ -148,17 +146,54 @@ mod tests {
         ..#..\n\
         ..###";
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed<'a> = Input;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("35"),
+        part2: Some("3351"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Input::parse(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        let mut parsed = parsed.clone();
+        for _ in 0..2 {
+            parsed.enhance_image();
+        }
+        parsed.image.img.count_ones().to_string()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        let mut parsed = parsed.clone();
+        for _ in 0..50 {
+            parsed.enhance_image();
+        }
+        parsed.image.img.count_ones().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
     #[test]
     fn test_parsing() {
         println!("{}", TEST_INPUT);
-        let parsed = Input::from_str(TEST_INPUT);
+        let parsed = Input::parse(TEST_INPUT).unwrap();
         let raw_image = TEST_INPUT.split("\n\n").nth(1).expect("WTF");
         assert_eq!(parsed.image.to_string(), raw_image);
     }
 
     #[test]
     fn test_image_enhance() {
-        let mut parsed = Input::from_str(TEST_INPUT);
+        let mut parsed = Input::parse(TEST_INPUT).unwrap();
         parsed.enhance_image();
         assert_eq!(
             parsed.image.to_string(),
@@ -1,5 +1,5 @@
+use aoc_common::{Example, ParseError, Solution};
 use fnv::FnvHashMap;
-use itertools::Itertools;
 use nom::{
     bytes::complete::tag,
     character::complete,
@@ -7,8 +7,6 @@ use nom::{
     sequence::{preceded, terminated},
 };
 
-const INPUT: &str = include_str!("../input/day21.txt");
-
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Player {
     position: u8,
@@ -16,7 +14,7 @@ struct Player {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Game {
+pub struct Game {
     players: [Player; 2],
     // Only used in part 2:
     whosturn: u8,
@@ -29,7 +27,7 @@ struct Game {
 const HISTOGRAM: [(u8, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
 
 impl Game {
-    pub fn from_str(s: &str) -> Self {
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
         let mut parser = preceded::<&str, _, _, Error<&str>, _, _>(
             tag("Player "),
             nom::sequence::pair(
@@ -37,24 +35,36 @@ impl Game {
                 complete::u8,
             ),
         );
-        if let Some((p1, p2)) = s
+        let players = s
             .lines()
-            .map(|l| {
-                let raw_data = parser(l).expect("Invalid input");
+            .enumerate()
+            .map(|(i, l)| {
+                let (id, position) = match parser(l) {
+                    Ok(("", raw_data)) => raw_data,
+                    _ => {
+                        let reason = "expected Player <n> starting position: <position>";
+                        return Err(ParseError::at(s, l, reason));
+                    }
+                };
+                if id as usize != i + 1 {
+                    return Err(ParseError::at(s, l, format!("expected player {}", i + 1)));
+                }
+                if !(1..=10).contains(&position) {
+                    return Err(ParseError::at(s, l, "expected a position from 1 to 10"));
+                }
                 // extrernally positions are 1-10, internally are 0-9
-                Player {
-                    position: raw_data.1 .1 - 1,
+                Ok(Player {
+                    position: position - 1,
                     score: 0,
-                }
+                })
             })
-            .collect_tuple()
-        {
-            Self {
-                players: [p1, p2],
+            .collect::<Result<Vec<_>, _>>()?;
+        match <[Player; 2]>::try_from(players) {
+            Ok(players) => Ok(Self {
+                players,
                 whosturn: 0,
-            }
-        } else {
-            panic!("Invalid number of players !");
+            }),
+            Err(_) => Err(ParseError::at(s, s, "expected two players")),
         }
     }
 
@@ -115,24 +125,40 @@ impl Game {
     }
 }
 
-pub fn day21() -> (String, String) {
-    let mut parsed = Game::from_str(INPUT);
-    // println!("{:?}", parsed);
-    let p1 = parsed.clone().part1().to_string();
-    let p2 = parsed.part2().to_string();
-    (p1, p2)
+const TEST_INPUT: &str = "Player 1 starting position: 4\n\
+        Player 2 starting position: 8";
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Parsed<'a> = Game;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("739785"),
+        part2: Some("444356092776315"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Game::parse(input)
+    }
+
+    fn part1(game: &Self::Parsed<'_>) -> String {
+        game.clone().part1().to_string()
+    }
+
+    fn part2(game: &Self::Parsed<'_>) -> String {
+        game.clone().part2().to_string()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = "Player 1 starting position: 4\n\
-        Player 2 starting position: 8";
-
     #[test]
     fn test_parsing() {
-        let parsed = Game::from_str(TEST_INPUT);
+        let parsed = Game::parse(TEST_INPUT).unwrap();
 
         assert_eq!(parsed.players.len(), 2);
         assert_eq!(parsed.players[0].position, 3);
@@ -143,13 +169,13 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let mut parsed = Game::from_str(TEST_INPUT);
+        let mut parsed = Game::parse(TEST_INPUT).unwrap();
         assert_eq!(parsed.part1(), 739785);
     }
 
     #[test]
     fn test_part2() {
-        let mut parsed = Game::from_str(TEST_INPUT);
+        let mut parsed = Game::parse(TEST_INPUT).unwrap();
         assert_eq!(parsed.part2(), 444356092776315);
     }
 }
@@ -1,4 +1,4 @@
-const INPUT: &str = include_str!("../input/day22.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
 #[derive(Debug, Clone)]
 struct Segment {
@@ -49,23 +49,41 @@ struct Cuboid {
 }
 
 impl Cuboid {
-    fn from_input(line: &str) -> Self {
-        let mut l = line;
-        let on = l.starts_with("on");
-        l = &l[l.find(' ').unwrap() + 1..];
-        let bounds = l
-            .split(',')
-            .flat_map(move |val| {
-                let val = &val[2..];
-                val.split("..")
-                    .map(move |bound| bound.parse::<i64>().expect("Invalid bound"))
-            })
-            .collect::<Vec<_>>();
-        Self {
+    /// Parses `line`, a line of `input`
+    fn from_input(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (on, l) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at(input, line, "expected on or off, then a cuboid"))?;
+        let on = match on {
+            "on" => true,
+            "off" => false,
+            _ => return Err(ParseError::at(input, on, "expected on or off")),
+        };
+        let mut bounds = [0; 6];
+        let mut ranges = l.split(',');
+        for (i, axis) in ["x=", "y=", "z="].into_iter().enumerate() {
+            let range = parse::next(input, &mut ranges, "a range")?;
+            let (start, end) = range
+                .strip_prefix(axis)
+                .and_then(|range| range.split_once(".."))
+                .ok_or_else(|| {
+                    ParseError::at(input, range, format!("expected a range, like {axis}-5..5"))
+                })?;
+            bounds[2 * i] = parse::token(input, start)?;
+            bounds[2 * i + 1] = parse::token(input, end)?;
+            if bounds[2 * i] > bounds[2 * i + 1] {
+                return Err(ParseError::at(
+                    input,
+                    range,
+                    "expected a start before the end",
+                ));
+            }
+        }
+        Ok(Self {
             start: Point::new(bounds[0], bounds[2], bounds[4]),
             end: Point::new(bounds[1], bounds[3], bounds[5]),
             is_on: on,
-        }
+        })
     }
 
     /// initialization cuboids are the ones inside -50..50
@@ -120,18 +138,18 @@ impl Cuboid {
     }
 }
 
-#[derive(Debug)]
-struct Parsed {
+#[derive(Debug, Clone)]
+pub struct Parsed {
     cuboids: Vec<Cuboid>,
 }
 
 impl Parsed {
-    fn from_input(input: &str) -> Self {
+    fn from_input(input: &str) -> Result<Self, ParseError> {
         let cuboids = input
             .split_terminator('\n')
-            .map(Cuboid::from_input)
-            .collect::<Vec<_>>();
-        Self { cuboids }
+            .map(|line| Cuboid::from_input(input, line))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { cuboids })
     }
 
     /// Only keep init cuboids (the ones from part 1)
@@ -167,24 +185,12 @@ impl Parsed {
     }
 }
 
-pub fn day22() -> (String, String) {
-    let mut parsed = Parsed::from_input(INPUT);
-    let part2 = parsed.number_active();
-    parsed.only_init();
-    let part1 = parsed.number_active();
-
-    (part1.to_string(), part2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const SMALL_TEST_INPUT: &str = "on x=10..12,y=10..12,z=10..12
+const SMALL_TEST_INPUT: &str = "on x=10..12,y=10..12,z=10..12
 on x=11..13,y=11..13,z=11..13
 off x=9..11,y=9..11,z=9..11
 on x=10..10,y=10..10,z=10..10";
-    const TEST_INPUT: &str = "on x=-20..26,y=-36..17,z=-47..7
+
+const TEST_INPUT: &str = "on x=-20..26,y=-36..17,z=-47..7
 on x=-20..33,y=-21..23,z=-26..28
 on x=-22..28,y=-29..23,z=-38..16
 on x=-46..7,y=-6..46,z=-50..-1
@@ -206,7 +212,8 @@ off x=18..30,y=-20..-8,z=-3..13
 on x=-41..9,y=-7..43,z=-33..15
 on x=-54112..-39298,y=-85059..-49293,z=-27449..7877
 on x=967..23432,y=45373..81175,z=27513..53682";
-    const PART2_TEST_INPUT: &str = "on x=-5..47,y=-31..22,z=-19..33
+
+const PART2_TEST_INPUT: &str = "on x=-5..47,y=-31..22,z=-19..33
 on x=-44..5,y=-27..21,z=-14..35
 on x=-49..-1,y=-11..42,z=-10..38
 on x=-20..34,y=-40..6,z=-44..1
@@ -267,9 +274,55 @@ off x=-70369..-16548,y=22648..78696,z=-1892..86821
 on x=-53470..21291,y=-120233..-33476,z=-44150..38147
 off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
 
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Parsed<'a> = Parsed;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: SMALL_TEST_INPUT,
+            part1: Some("39"),
+            part2: None,
+        },
+        Example {
+            input: TEST_INPUT,
+            part1: Some("590784"),
+            part2: None,
+        },
+        Example {
+            input: PART2_TEST_INPUT,
+            part1: Some("474140"),
+            part2: Some("2758514936282235"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Parsed::from_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed<'_>) -> String {
+        let mut parsed = parsed.clone();
+        parsed.only_init();
+        parsed.number_active().to_string()
+    }
+
+    fn part2(parsed: &Self::Parsed<'_>) -> String {
+        parsed.number_active().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cuboid(line: &str) -> Cuboid {
+        Cuboid::from_input(line, line).unwrap()
+    }
+
     #[test]
     fn test_cuboid_parsing() {
-        let c = Cuboid::from_input("on x=23778..52633,y=52727..68692,z=-56606..-20022");
+        let c = cuboid("on x=23778..52633,y=52727..68692,z=-56606..-20022");
         assert_eq!(c.start.x, 23778);
         assert_eq!(c.start.y, 52727);
         assert_eq!(c.start.z, -56606);
@@ -280,11 +333,11 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
 
     #[test]
     fn test_intersection() {
-        let c1 = Cuboid::from_input("on x=1..2,y=1..2,z=1..2");
-        let c2 = Cuboid::from_input("on x=3..4,y=3..4,z=3..4");
+        let c1 = cuboid("on x=1..2,y=1..2,z=1..2");
+        let c2 = cuboid("on x=3..4,y=3..4,z=3..4");
         assert_eq!(c1.intersect(&c2), None);
-        let c1 = Cuboid::from_input("on x=1..2,y=1..2,z=1..2");
-        let c2 = Cuboid::from_input("on x=2..3,y=2..3,z=2..3");
+        let c1 = cuboid("on x=1..2,y=1..2,z=1..2");
+        let c2 = cuboid("on x=2..3,y=2..3,z=2..3");
         assert_eq!(
             c1.intersect(&c2).unwrap(),
             Cuboid {
@@ -298,23 +351,23 @@ off x=-93533..-4276,y=-16170..68771,z=-104985..-24507";
     #[test]
     fn test_very_small_input() {
         const INPUT: &str = "on x=10..12,y=10..12,z=10..12\non x=11..13,y=11..13,z=11..13";
-        let parsed = Parsed::from_input(INPUT);
+        let parsed = Parsed::from_input(INPUT).unwrap();
         let active = parsed.number_active();
         assert_eq!(active, 27 + 19);
     }
 
     #[test]
     fn test_number_active() {
-        let mut parsed = Parsed::from_input(SMALL_TEST_INPUT);
+        let mut parsed = Parsed::from_input(SMALL_TEST_INPUT).unwrap();
         parsed.only_init();
         let active = parsed.number_active();
         assert_eq!(active, 39, "Failed part 1 small test input");
-        let mut parsed = Parsed::from_input(TEST_INPUT);
+        let mut parsed = Parsed::from_input(TEST_INPUT).unwrap();
         parsed.only_init();
         let active = parsed.number_active();
         assert_eq!(active, 590784, "Failed part 1 normal test input");
 
-        let parsed = Parsed::from_input(PART2_TEST_INPUT);
+        let parsed = Parsed::from_input(PART2_TEST_INPUT).unwrap();
         let active = parsed.number_active();
         assert_eq!(active, 2758514936282235, "Failed part 2 normal test input");
     }
@@ -1,10 +1,8 @@
+use aoc_common::{Example, ParseError, Solution};
 use fnv::FnvHashMap;
 use heapless::Vec as StackVec;
 use itertools::Itertools;
 
-const INPUT_P1: &str = include_str!("../input/day23_1.txt");
-const INPUT_P2: &str = include_str!("../input/day23_2.txt");
-
 const WEIGHT: [i64; 4] = [1, 10, 100, 1000];
 const BUF_WEIGHT: [i64; 7] = [0, 1, 3, 5, 7, 9, 10];
 
@@ -17,18 +15,17 @@ fn buf_traversal_cost(i: usize, j: usize, c: u8) -> i64 {
 }
 
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-struct State<const ROOM_SIZE: usize> {
+pub struct State<const ROOM_SIZE: usize> {
     rooms: [StackVec<u8, ROOM_SIZE>; 4],
     buffer: [u8; 7],
 }
 
 impl<const ROOM_SIZE: usize> State<ROOM_SIZE> {
-    fn from_input(input: &str) -> Self {
-        debug_assert!(input.starts_with("#############\n#"));
-        let raw_buffer = &input.as_bytes()[15..26];
-        let mut raw_rows = input[28..].lines().rev();
-        let term_row = raw_rows.next().unwrap();
-        debug_assert_eq!(term_row, "  #########  ");
+    /// The state of the rows of a diagram checked by [`check_diagram`], or unfolded by [`unfold`]
+    fn from_rows(rows: &[&str]) -> Self {
+        let raw_buffer = &rows[1].as_bytes()[1..12];
+        // the rooms, from the bottom
+        let raw_rows = rows[2..rows.len() - 1].iter().rev();
 
         let mut buffer = [b'.'; 7];
         {
@@ -68,19 +65,19 @@ impl<const ROOM_SIZE: usize> State<ROOM_SIZE> {
         match ROOM_SIZE {
             2 => State {
                 rooms: [
-                    StackVec::from_slice(&[b'A', b'A']).unwrap(),
-                    StackVec::from_slice(&[b'B', b'B']).unwrap(),
-                    StackVec::from_slice(&[b'C', b'C']).unwrap(),
-                    StackVec::from_slice(&[b'D', b'D']).unwrap(),
+                    StackVec::from_slice(b"AA").unwrap(),
+                    StackVec::from_slice(b"BB").unwrap(),
+                    StackVec::from_slice(b"CC").unwrap(),
+                    StackVec::from_slice(b"DD").unwrap(),
                 ],
                 buffer: [b'.'; 7],
             },
             4 => State {
                 rooms: [
-                    StackVec::from_slice(&[b'A', b'A', b'A', b'A']).unwrap(),
-                    StackVec::from_slice(&[b'B', b'B', b'B', b'B']).unwrap(),
-                    StackVec::from_slice(&[b'C', b'C', b'C', b'C']).unwrap(),
-                    StackVec::from_slice(&[b'D', b'D', b'D', b'D']).unwrap(),
+                    StackVec::from_slice(b"AAAA").unwrap(),
+                    StackVec::from_slice(b"BBBB").unwrap(),
+                    StackVec::from_slice(b"CCCC").unwrap(),
+                    StackVec::from_slice(b"DDDD").unwrap(),
                 ],
                 buffer: [b'.'; 7],
             },
@@ -138,7 +135,7 @@ impl<const ROOM_SIZE: usize> State<ROOM_SIZE> {
             if self.buffer[i] == b'.' {
                 continue;
             }
-            let r = (self.buffer[i] as u8 - b'A') as usize;
+            let r = (self.buffer[i] - b'A') as usize;
             if !self.is_valid_room(r) {
                 continue;
             }
@@ -240,42 +237,132 @@ fn solve<const ROOM_SIZE: usize>(input: State<ROOM_SIZE>, expected: State<ROOM_S
     *costs.get(&expected).unwrap()
 }
 
-pub fn day23() -> (String, String) {
-    let part1 = solve(State::<2>::from_input(INPUT_P1), State::final_state());
-    let part2 = solve(State::<4>::from_input(INPUT_P2), State::final_state());
+/// The diagram of the burrow, `?` being an amphipod
+const DIAGRAM: [&str; 5] = [
+    "#############",
+    "#...........#",
+    "###?#?#?#?###",
+    "  #?#?#?#?#",
+    "  #########",
+];
+
+/// Checks that `input` is [`DIAGRAM`], with two amphipods of each type. Returns its rows,
+/// without their trailing spaces.
+fn check_diagram(input: &str) -> Result<[&str; 5], ParseError> {
+    let mut lines = input.lines();
+    let mut rows = [""; 5];
+    for (expected, row) in DIAGRAM.into_iter().zip(&mut rows) {
+        let line = lines.next().unwrap_or(&input[input.len()..]).trim_end();
+        for (i, (c, e)) in line.bytes().zip(expected.bytes()).enumerate() {
+            match (c, e) {
+                (b'A'..=b'D', b'?') => (),
+                (c, e) if c == e => (),
+                (_, b'?') => return Err(ParseError::at(input, &line[i..], "expected A, B, C or D")),
+                (_, e) => {
+                    let reason = format!("expected {:?}", e as char);
+                    return Err(ParseError::at(input, &line[i..], reason));
+                }
+            }
+        }
+        if line.len() != expected.len() {
+            let reason = format!("expected {expected:?}");
+            return Err(ParseError::at(input, line, reason));
+        }
+        *row = line;
+    }
+    if let Some(line) = lines.find(|l| !l.trim().is_empty()) {
+        return Err(ParseError::at(input, line, "expected the end of the diagram"));
+    }
+    for amphipod in b'A'..=b'D' {
+        if input.bytes().filter(|&c| c == amphipod).count() != 2 {
+            let reason = format!("expected two amphipods {}", amphipod as char);
+            return Err(ParseError::at(input, input, reason));
+        }
+    }
+    Ok(rows)
+}
+
+/// Inserts the two folded rows of the diagram for part 2
+fn unfold(rows: [&str; 5]) -> [&str; 7] {
+    let [top, hallway, first, last, bottom] = rows;
+    [top, hallway, first, "  #D#C#B#A#", "  #D#B#A#C#", last, bottom]
+}
+
+const TEST_INPUT_P1: &str =
+    "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#A#  \n  #########  ";
+
+pub struct Day23;
 
-    (part1.to_string(), part2.to_string())
+impl Solution for Day23 {
+    type Parsed<'a> = (State<2>, State<4>);
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT_P1,
+        part1: Some("12521"),
+        part2: Some("44169"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let rows = check_diagram(input)?;
+        Ok((State::from_rows(&rows), State::from_rows(&unfold(rows))))
+    }
+
+    fn part1((folded, _): &Self::Parsed<'_>) -> String {
+        solve(folded.clone(), State::final_state()).to_string()
+    }
+
+    fn part2((_, unfolded): &Self::Parsed<'_>) -> String {
+        solve(unfolded.clone(), State::final_state()).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    const TEST_INPUT_P1: &str =
-        "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#A#  \n  #########  ";
     const TEST_INPUT_P2: &str =
         "#############\n#...........#\n###B#C#B#D###\n  #D#C#B#A#  \n  #D#B#A#C#  \n  #A#D#C#A#  \n  #########  ";
 
+    fn from_input<const ROOM_SIZE: usize>(input: &str) -> State<ROOM_SIZE> {
+        State::from_rows(&input.lines().collect::<Vec<_>>())
+    }
+
     #[test]
     fn test_parsing_p1() {
-        let s = State::<2>::from_input(TEST_INPUT_P1);
+        let s = from_input::<2>(TEST_INPUT_P1);
         assert_eq!(s.to_string(), TEST_INPUT_P1);
     }
 
     #[test]
     fn test_parsing_p2() {
-        let s = State::<4>::from_input(TEST_INPUT_P2);
+        let s = from_input::<4>(TEST_INPUT_P2);
         assert_eq!(s.to_string(), TEST_INPUT_P2);
     }
 
+    #[test]
+    fn test_unfold() {
+        let rows = check_diagram(TEST_INPUT_P1).unwrap();
+        assert_eq!(State::<4>::from_rows(&unfold(rows)).to_string(), TEST_INPUT_P2);
+    }
+
+    #[test]
+    fn test_parse() {
+        // trailing blank lines, and CRLF line endings
+        let (folded, unfolded) = Day23::parse(&format!("{TEST_INPUT_P1}\n\n")).unwrap();
+        assert_eq!(folded.to_string(), TEST_INPUT_P1);
+        assert_eq!(unfolded.to_string(), TEST_INPUT_P2);
+        let (folded, _) = Day23::parse(&TEST_INPUT_P1.replace('\n', "\r\n")).unwrap();
+        assert_eq!(folded.to_string(), TEST_INPUT_P1);
+    }
+
     #[test]
     fn test_part_1() {
-        let input = State::<2>::from_input(TEST_INPUT_P1);
+        let input = from_input::<2>(TEST_INPUT_P1);
         assert_eq!(solve(input, State::final_state()), 12521);
     }
 
     #[test]
     fn test_part_2() {
-        let input = State::<4>::from_input(TEST_INPUT_P2);
+        let input = from_input::<4>(TEST_INPUT_P2);
         assert_eq!(solve(input, State::final_state()), 44169);
     }
 }
@@ -1,21 +1,64 @@
-use anyhow::{anyhow, Result};
-use fnv::FnvHashMap;
-use nom::{
-    bytes::complete::tag,
-    character::complete,
-    error::Error,
-    sequence::{terminated, tuple},
-};
-use std::hint::unreachable_unchecked;
+use aoc_common::{parse, ParseError, Rng, Solution};
+use fnv::{FnvHashMap, FnvHashSet};
 
-const INPUT: &str = include_str!("../input/day24.txt");
+/// The MONAD reads the 14 digits in as many blocks, identical but for 3 constants (`{}`)
+const BLOCK: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z {}", "add x {}", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y {}",
+    "mul y x", "add z y",
+];
+const DIGITS: usize = 14;
 
-struct Input {
+/// The constants of a block of the MONAD
+#[derive(Clone, Copy)]
+pub struct Block {
+    x_add: i64,
+    y_add: u64,
+    z_div: u64,
+}
+
+/// Reads the constants of each block, the solver relies on the MONAD having this exact structure
+fn parse_blocks(input: &str) -> Result<Vec<Block>, ParseError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    if lines.len() != DIGITS * BLOCK.len() {
+        let reason = format!("expected {DIGITS} blocks of {} instructions", BLOCK.len());
+        return Err(ParseError::at(input, "", reason));
+    }
+    lines
+        .chunks(BLOCK.len())
+        .map(|block| {
+            let mut constants = Vec::with_capacity(3);
+            for (&line, expected) in block.iter().zip(BLOCK) {
+                let matches = match expected.strip_suffix("{}") {
+                    Some(prefix) => line.strip_prefix(prefix).map(|c| constants.push(c)).is_some(),
+                    None => line == expected,
+                };
+                if !matches {
+                    let reason = format!("expected {}", expected.replace("{}", "<n>"));
+                    return Err(ParseError::at(input, line, reason));
+                }
+            }
+            let z_div = parse::token(input, constants[0])?;
+            if z_div != 1 && z_div != 26 {
+                return Err(ParseError::at(input, constants[0], "expected 1 or 26"));
+            }
+            Ok(Block {
+                x_add: parse::token(input, constants[1])?,
+                y_add: parse::token(input, constants[2])?,
+                z_div,
+            })
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+pub struct Input {
     values: Vec<i64>,
     instructions: Vec<Instruction>,
 }
 
 /// Stores indexes to values in Input.values
+#[derive(Clone)]
 enum Instruction {
     Inp(u8),
     Add((u8, u8)),
@@ -26,42 +69,33 @@ enum Instruction {
 }
 
 impl Input {
-    fn from_str(s: &'static str) -> Result<Self> {
+    fn from_str(s: &str) -> Result<Self, ParseError> {
         // x, y, z, w are 0..4
         let mut values = vec![0; 4];
         let mut instructions = vec![];
+        let register = |r| match r {
+            "x" => Some(0),
+            "y" => Some(1),
+            "z" => Some(2),
+            "w" => Some(3),
+            _ => None,
+        };
         for l in s.lines() {
-            let (s, instruction) =
-                terminated::<_, _, _, Error<&str>, _, _>(complete::alpha0, tag(" "))(l)?;
+            let mut args = l.split(' ');
+            let instruction = parse::next(s, &mut args, "an instruction")?;
+            let a = parse::next(s, &mut args, "a variable")?;
+            let a = register(a)
+                .ok_or_else(|| ParseError::at(s, a, "expected a variable: w, x, y or z"))?;
             let instr = if instruction == "inp" {
-                match s {
-                    "x" => Instruction::Inp(0),
-                    "y" => Instruction::Inp(1),
-                    "z" => Instruction::Inp(2),
-                    "w" => Instruction::Inp(3),
-                    _ => return Err(anyhow!("Invalid input register `{}`", s)),
-                }
+                Instruction::Inp(a)
             } else {
-                let (b, (a, _)) = tuple::<_, _, Error<&str>, _>((complete::alpha1, tag(" ")))(s)?;
-                let a = match a {
-                    "x" => 0,
-                    "y" => 1,
-                    "z" => 2,
-                    "w" => 3,
-                    _ => {
-                        return Err(anyhow!(
-                            "The first arg to an instruction must be a variable"
-                        ))
-                    }
-                };
-                let b = match b {
-                    "x" => 0,
-                    "y" => 1,
-                    "z" => 2,
-                    "w" => 3,
-                    _ => {
-                        values.push(b.parse::<i64>()?);
-                        (values.len() - 1) as u8
+                let b = parse::next(s, &mut args, "a variable or a number")?;
+                let b = match register(b) {
+                    Some(b) => b,
+                    None => {
+                        values.push(parse::token::<i64>(s, b)?);
+                        u8::try_from(values.len() - 1)
+                            .map_err(|_| ParseError::at(s, b, "too many numbers"))?
                     }
                 };
                 match instruction {
@@ -70,9 +104,15 @@ impl Input {
                     "mod" => Instruction::Mod((a, b)),
                     "div" => Instruction::Div((a, b)),
                     "eql" => Instruction::Eql((a, b)),
-                    _ => return Err(anyhow!("Invalid instruction `{}`", instruction)),
+                    _ => {
+                        let reason = "expected inp, add, mul, div, mod or eql";
+                        return Err(ParseError::at(s, instruction, reason));
+                    }
                 }
             };
+            if let Some(extra) = args.next() {
+                return Err(ParseError::at(s, extra, "unexpected operand"));
+            }
             instructions.push(instr);
         }
         Ok(Input {
@@ -145,11 +185,7 @@ impl Input {
 
 /// returns the value of z
 fn day24_input_step(input: u64, mut z: i64, x_add: i64, y_add: u64, z_div: u64) -> i64 {
-    if (z_div != 1 && z_div != 26) || input < 1 || input > 9 {
-        unsafe {
-            unreachable_unchecked();
-        }
-    }
+    debug_assert!((z_div == 1 || z_div == 26) && (1..=9).contains(&input));
     let eq = (z % 26 + x_add) as u64 == input;
     z /= z_div as i64; // either do nothing or pop the last element (/26)
     if !eq {
@@ -160,21 +196,34 @@ fn day24_input_step(input: u64, mut z: i64, x_add: i64, y_add: u64, z_div: u64)
 }
 
 
-fn day24_input_solver() -> (u64, u64) {
-    let x_add = [14, 15, 13, -10, 14, -3, -14, 12, 14, 12, -6, -6, -2, -9];
-    let y_add = [8, 11, 2, 11, 1, 5, 10, 6, 1, 11, 9, 14, 11, 2];
-    let z_div = [1, 1, 1, 26, 1, 26, 26, 1, 1, 1, 26, 26, 26, 26];
-
+/// Bounds of z after each block: a block divides z by 26 at most, so z can only get back to 0
+/// if it is below 26 to the power of the divisions by 26 left
+fn z_bounds(z_divs: impl DoubleEndedIterator<Item = u64>) -> Vec<i64> {
+    let mut pops = 0;
+    let mut bounds = z_divs
+        .rev()
+        .map(|z_div| {
+            let bound = 26i64.checked_pow(pops).unwrap_or(i64::MAX);
+            pops += (z_div == 26) as u32;
+            bound
+        })
+        .collect::<Vec<_>>();
+    bounds.reverse();
+    bounds
+}
 
+fn day24_input_solver(blocks: &[Block]) -> (u64, u64) {
     let mut z_backlog = FnvHashMap::default();
+    let bounds = z_bounds(blocks.iter().map(|b| b.z_div));
 
-    z_backlog.insert(0, [box [0u8; 14], box [0u8; 14]]);
-    for idx in 0..14 {
+    z_backlog.insert(0, [Box::new([0u8; 14]), Box::new([0u8; 14])]);
+    for (idx, block) in blocks.iter().enumerate() {
         let mut new_z_backlog = FnvHashMap::<i64, [Box<[u8; 14]>; 2]>::default();
         for (z, mut paths) in z_backlog.into_iter() {
             for input in 1..10u8 {
-                let new_z = day24_input_step(input as u64, z, x_add[idx], y_add[idx], z_div[idx]);
-                if !(0..1_000_000).contains(&new_z) {
+                let new_z =
+                    day24_input_step(input as u64, z, block.x_add, block.y_add, block.z_div);
+                if !(0..bounds[idx]).contains(&new_z) {
                     continue;
                 }
                 paths[0][idx] = input;
@@ -211,26 +260,127 @@ fn day24_input_solver() -> (u64, u64) {
         })
 }
 
+/// Searches the model numbers digit by digit, running each block on the ALU, and remembering the
+/// (block, z) that cannot lead to a valid number
+fn reference(input: &str, digits: &[u8]) -> u64 {
+    let lines = input.lines().collect::<Vec<_>>();
+    let blocks = lines
+        .chunks(BLOCK.len())
+        .map(|block| Input::from_str(&block.join("\n")).unwrap())
+        .collect::<Vec<_>>();
+    let bounds = z_bounds(lines.chunks(BLOCK.len()).map(|block| match block[4] {
+        "div z 26" => 26,
+        _ => 1,
+    }));
+
+    fn search(
+        blocks: &mut [Input],
+        bounds: &[i64],
+        digits: &[u8],
+        z: i64,
+        dead: &mut FnvHashSet<(usize, i64)>,
+    ) -> Option<u64> {
+        let idx = DIGITS - blocks.len();
+        let Some((block, rest)) = blocks.split_first_mut() else {
+            return (z == 0).then_some(0);
+        };
+        if dead.contains(&(idx, z)) {
+            return None;
+        }
+        for &digit in digits {
+            block.reset();
+            block.values[2] = z;
+            block._run_vm(vec![digit]);
+            let new_z = block.values[2];
+            if !(0..bounds[idx]).contains(&new_z) {
+                continue;
+            }
+            if let Some(number) = search(rest, bounds, digits, new_z, dead) {
+                return Some(digit as u64 * 10u64.pow(rest.len() as u32) + number);
+            }
+        }
+        dead.insert((idx, z));
+        None
+    }
+
+    let mut dead = FnvHashSet::default();
+    search(&mut blocks.clone(), &bounds, digits, 0, &mut dead).expect("no valid model number")
+}
+
+pub struct Day24;
 
-pub fn day24() -> (String, String) {
-    let mut monad = Input::from_str(INPUT).unwrap();
-    let (part2, part1) = day24_input_solver();
-    debug_assert!({
-        monad.reset();
-        monad.run(part1)
-    });
-    debug_assert!({
-        monad.reset();
-        monad.run(part2)
-    });
+impl Solution for Day24 {
+    type Parsed<'a> = (Input, Vec<Block>);
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        Ok((Input::from_str(input)?, parse_blocks(input)?))
+    }
+
+    fn part1((monad, blocks): &Self::Parsed<'_>) -> String {
+        let (_, part1) = day24_input_solver(blocks);
+        debug_assert!({
+            let mut monad = monad.clone();
+            monad.reset();
+            monad.run(part1)
+        });
+        part1.to_string()
+    }
+
+    fn part2((monad, blocks): &Self::Parsed<'_>) -> String {
+        let (part2, _) = day24_input_solver(blocks);
+        debug_assert!({
+            let mut monad = monad.clone();
+            monad.reset();
+            monad.run(part2)
+        });
+        part2.to_string()
+    }
 
-    (part1.to_string(), part2.to_string())
+    /// A MONAD whose blocks push digits on z, seen as a stack in base 26, and pop them checking
+    /// each against the digit of the block that pushed it, nesting at most 5 deep
+    fn generate(_size: usize, rng: &mut Rng) -> Option<String> {
+        let mut pushed = Vec::new();
+        let mut input = String::new();
+        for idx in 0..DIGITS {
+            let pops_left = DIGITS - idx - pushed.len();
+            let push = pushed.is_empty()
+                || (pushed.len() < 5 && pops_left > pushed.len() && rng.one_in(2));
+            let (z_div, x_add, y_add) = if push {
+                let y_add = rng.range(0..=16);
+                pushed.push(y_add);
+                // z % 26 + x_add is above 9, no digit matches
+                (1, rng.range(10..=16), y_add)
+            } else {
+                // the digit popped is the pushed one, offset in a way that some digits satisfy
+                let offset = rng.range(-8..=8);
+                (26, offset - pushed.pop().unwrap(), rng.range(0..=16))
+            };
+            let mut constants = [z_div, x_add, y_add].into_iter();
+            for line in BLOCK {
+                match line.strip_suffix("{}") {
+                    Some(prefix) => input += &format!("{prefix}{}\n", constants.next().unwrap()),
+                    None => input += &format!("{line}\n"),
+                }
+            }
+        }
+        Some(input)
+    }
+
+    fn reference(input: &str, part: u8) -> Option<String> {
+        let digits = match part {
+            1 => [9, 8, 7, 6, 5, 4, 3, 2, 1],
+            _ => [1, 2, 3, 4, 5, 6, 7, 8, 9],
+        };
+        Some(reference(input, &digits).to_string())
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    const INPUT: &str = include_str!("../input/day24.txt");
+
     #[test]
     fn test_negate() {
         let test_neg = "inp x\nmul x -1";
@@ -275,15 +425,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_reference() {
+        // the generated MONADs nest deeper than the puzzle's
+        aoc_common::differential::check::<Day24>(0, 0..5).unwrap();
+        let parsed = Day24::parse(INPUT).unwrap();
+        assert_eq!(Day24::reference(INPUT, 1), Some(Day24::part1(&parsed)));
+        assert_eq!(Day24::reference(INPUT, 2), Some(Day24::part2(&parsed)));
+    }
+
     #[test]
     fn test_part1_reversed_engineered_program() {
         let mut input = Input::from_str(INPUT).unwrap();
+        let blocks = parse_blocks(INPUT).unwrap();
         let test_values = [
             [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
             [1, 2, 3, 7, 5, 6, 7, 8, 9, 2, 3, 4, 5, 6],
         ];
         for test_val in test_values.iter() {
-            let reg_z = part1_reversed_engineered_program(test_val);
+            let reg_z = test_val.iter().zip(&blocks).fold(0, |z, (&v, b)| {
+                day24_input_step(v, z, b.x_add, b.y_add, b.z_div)
+            });
             input._run_vm(test_val.iter().rev().map(|&v| v as u8).collect());
             assert_eq!(input.values[2], reg_z, "input: {:?}", test_val);
             input.reset();
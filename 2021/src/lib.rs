@@ -0,0 +1,6 @@
+#![feature(core_intrinsics)]
+#![allow(internal_features)]
+
+// `pub mod dayNN;` for every `src/dayNN.rs`, `SOLUTIONS` and what the runner uses of a year,
+// see `aoc_common::build`
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
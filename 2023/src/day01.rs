@@ -1,4 +1,4 @@
-const INPUT: &str = include_str!("../input/day01.txt");
+use aoc_common::{Example, ParseError, Solution};
 
 const fn add_num_to_tmp(num: u32, tmp: u32) -> u32 {
     if tmp == 0 {
@@ -8,60 +8,91 @@ const fn add_num_to_tmp(num: u32, tmp: u32) -> u32 {
     }
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    const TEXT_DIGITS: [&[u8]; 9] = [
-        b"one", b"two", b"three", b"four", b"five", b"six", b"seven", b"eight", b"nine",
-    ];
+const TEXT_DIGITS: [&[u8]; 9] = [
+    b"one", b"two", b"three", b"four", b"five", b"six", b"seven", b"eight", b"nine",
+];
+
+/// The calibration value of `line`, its first and last digits, spelled out ones included when
+/// `with_text`
+fn calibration_value(line: &str, with_text: bool) -> u32 {
+    let mut tmp = 0;
+    let bline = line.as_bytes();
 
-    let mut sum_p1 = 0;
-    let mut sum_p2 = 0;
-
-    for line in input.lines() {
-        let mut tmp_p1 = 0;
-        let mut tmp_p2 = 0;
-        let bline = line.as_bytes();
-
-        for i in 0..bline.len() {
-            if bline[i].is_ascii_digit() {
-                let num = (bline[i] - b'0') as u32;
-                tmp_p1 = add_num_to_tmp(num, tmp_p1);
-                tmp_p2 = add_num_to_tmp(num, tmp_p2);
-            } else {
-                for (j, t) in TEXT_DIGITS.iter().enumerate() {
-                    if bline[i..].starts_with(t) {
-                        let num = (j + 1) as u32;
-                        tmp_p2 = add_num_to_tmp(num, tmp_p2);
-                        break;
-                    }
+    for i in 0..bline.len() {
+        if bline[i].is_ascii_digit() {
+            let num = (bline[i] - b'0') as u32;
+            tmp = add_num_to_tmp(num, tmp);
+        } else if with_text {
+            for (j, t) in TEXT_DIGITS.iter().enumerate() {
+                if bline[i..].starts_with(t) {
+                    let num = (j + 1) as u32;
+                    tmp = add_num_to_tmp(num, tmp);
+                    break;
                 }
             }
         }
-        sum_p1 += tmp_p1;
-        sum_p2 += tmp_p2;
     }
+    tmp
+}
 
-    (sum_p1, sum_p2)
+fn part_1(lines: &[&str]) -> u32 {
+    lines
+        .iter()
+        .map(|line| calibration_value(line, false))
+        .sum()
 }
 
-pub fn day01() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+fn part_2(lines: &[&str]) -> u32 {
+    lines.iter().map(|line| calibration_value(line, true)).sum()
+}
+
+const TEST_INPUT_P1: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+
+const TEST_INPUT_P2: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = Vec<&'a str>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: TEST_INPUT_P1,
+            part1: Some("142"),
+            part2: None,
+        },
+        Example {
+            input: TEST_INPUT_P2,
+            part1: None,
+            part2: Some("281"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // a line without digits is worth 0
+        Ok(input.lines().collect())
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> String {
+        part_1(lines).to_string()
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> String {
+        part_2(lines).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT_P1: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
-    const TEST_INPUT_P2: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
-
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT_P1).0, 142);
+        assert_eq!(part_1(&TEST_INPUT_P1.lines().collect::<Vec<_>>()), 142);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT_P2).1, 281);
+        assert_eq!(part_2(&TEST_INPUT_P2.lines().collect::<Vec<_>>()), 281);
     }
 }
@@ -1,51 +1,72 @@
-const INPUT: &str = include_str!("../input/day02.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
+use indoc::indoc;
 
-fn solve(input: &str, game_parameters: &[u32; 3]) -> (u32, u32) {
-    let mut sum_p1 = 0;
-    let mut sum_p2 = 0;
+/// A game's id and the cubes of each color (red, green, blue) in its sets
+pub struct Game {
+    id: u32,
+    sets: Vec<[u32; 3]>,
+}
 
-    for game in input.lines() {
-        let (header, mut values) = game.split_once(':').unwrap();
-        let game_number: u32 = header[5..].parse().unwrap();
-        values = &values[1..];
-        let mut is_p1_possible = true;
-        let mut min_parameters: [u32; 3] = [0, 0, 0];
-        for cube_set in values.split(';') {
-            for color_num in cube_set.split(',') {
-                let (num, color) = color_num.trim().split_once(' ').unwrap();
-                let color_index = match color {
-                    "red" => 0,
-                    "green" => 1,
-                    "blue" => 2,
-                    _ => unreachable!(),
-                };
-                let num = num.parse::<u32>().unwrap();
-                if num > game_parameters[color_index] {
-                    is_p1_possible = false;
-                }
-                min_parameters[color_index] = min_parameters[color_index].max(num);
-            }
+/// Parses a game like `Game 1: 3 blue, 4 red; 1 red, 2 green`
+fn parse_game(input: &str, line: &str) -> Result<Game, ParseError> {
+    let invalid = |token, reason| ParseError::at(input, token, reason);
+    let (header, sets) = line
+        .split_once(": ")
+        .ok_or_else(|| invalid(line, "expected a game, like Game 1: 3 blue, 4 red"))?;
+    let id = header
+        .strip_prefix("Game ")
+        .ok_or_else(|| invalid(header, "expected Game <id>"))?;
+    let id = parse::token::<u32>(input, id)?;
+    let mut game = Game {
+        id,
+        sets: Vec::new(),
+    };
+    for set in sets.split("; ") {
+        let mut cubes_set = [0; 3];
+        for cubes in set.split(", ") {
+            let (num, color) = cubes.split_once(' ').ok_or_else(|| {
+                invalid(cubes, "expected a number of cubes and a color, like 3 blue")
+            })?;
+            let color_index = match color {
+                "red" => 0,
+                "green" => 1,
+                "blue" => 2,
+                _ => return Err(invalid(color, "expected red, green or blue")),
+            };
+            cubes_set[color_index] += parse::token::<u32>(input, num)?;
         }
-        if is_p1_possible {
-            sum_p1 += game_number;
-        }
-        sum_p2 += min_parameters.into_iter().product::<u32>();
+        game.sets.push(cubes_set);
     }
-
-    (sum_p1, sum_p2)
+    Ok(game)
 }
 
-pub fn day02() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT, &[12, 13, 14]);
-    (sum_p1.to_string(), sum_p2.to_string())
+fn part_1(games: &[Game], game_parameters: &[u32; 3]) -> u32 {
+    games
+        .iter()
+        .filter(|game| {
+            game.sets
+                .iter()
+                .all(|set| set.iter().zip(game_parameters).all(|(num, max)| num <= max))
+        })
+        .map(|game| game.id)
+        .sum()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
+fn part_2(games: &[Game]) -> u32 {
+    let mut sum_p2 = 0;
+    for game in games {
+        let mut min_parameters: [u32; 3] = [0, 0, 0];
+        for set in &game.sets {
+            for (min, num) in min_parameters.iter_mut().zip(set) {
+                *min = (*min).max(*num);
+            }
+        }
+        sum_p2 += min_parameters.into_iter().product::<u32>();
+    }
+    sum_p2
+}
 
-    const TEST_INPUT: &str = indoc! {r#"
+const TEST_INPUT: &str = indoc! {r#"
         Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
         Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
         Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
@@ -53,13 +74,41 @@ mod test {
         Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
     "#};
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<Game>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("8"),
+        part2: Some("2286"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        input.lines().map(|line| parse_game(input, line)).collect()
+    }
+
+    fn part1(games: &Self::Parsed<'_>) -> String {
+        part_1(games, &[12, 13, 14]).to_string()
+    }
+
+    fn part2(games: &Self::Parsed<'_>) -> String {
+        part_2(games).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT, &[12, 13, 14]).0, 8);
+        assert_eq!(part_1(&Day02::parse(TEST_INPUT).unwrap(), &[12, 13, 14]), 8);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT, &[0, 0, 0]).1, 2286);
+        assert_eq!(part_2(&Day02::parse(TEST_INPUT).unwrap()), 2286);
     }
 }
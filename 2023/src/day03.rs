@@ -1,4 +1,5 @@
-const INPUT: &str = include_str!("../input/day03.txt");
+use aoc_common::{Example, ParseError, Solution};
+use indoc::indoc;
 
 fn is_symbol(c: &u8) -> bool {
     !c.is_ascii_digit() && *c != b'.'
@@ -18,15 +19,29 @@ fn parse_num_p2(line: &[u8], coord: usize) -> u32 {
         .unwrap()
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let mut sum_p1 = 0;
-    let mut sum_p2 = 0;
+/// The rows of the schematic, a rectangle of digits, '.' and symbols (the neighbors of a
+/// number are looked for in the rows above and below)
+fn parse_schematic(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    let width = input.lines().next().map_or(0, str::len);
+    for row in input.lines() {
+        if let Some(i) = row.find(|c: char| !c.is_ascii_graphic()) {
+            let reason = "expected digits, '.' or symbols";
+            return Err(ParseError::at(input, &row[i..], reason));
+        }
+        if row.len() != width {
+            let reason = format!("expected {width} columns, like the first row");
+            return Err(ParseError::at(input, row, reason));
+        }
+    }
+    Ok(input.lines().map(|l| l.as_bytes()).collect())
+}
 
-    let lines = input.lines().map(|l| l.as_bytes()).collect::<Vec<_>>();
+fn part_1(lines: &[&[u8]]) -> u32 {
+    let mut sum_p1 = 0;
 
     for i in 0..lines.len() {
         let mut j = 0;
-        'chars: while j < lines[i].len() {
+        while j < lines[i].len() {
             if lines[i][j].is_ascii_digit() {
                 let start = j;
                 while j < lines[i].len() && lines[i][j].is_ascii_digit() {
@@ -46,55 +61,57 @@ fn solve(input: &str) -> (u32, u32) {
                         .unwrap();
                     sum_p1 += num;
                 }
-                continue 'chars;
-            } else if lines[i][j] == b'*' {
-                let mut coord_0 = None;
-                let mut coord_1 = None;
-
-                for u in i.saturating_sub(1)..=(i + 1).min(lines.len() - 1) {
-                    let mut v = j.saturating_sub(1);
-                    while v <= (j + 1).min(lines[i].len() - 1) {
-                        if lines[u][v].is_ascii_digit() {
-                            if coord_0.is_none() {
-                                coord_0 = Some((u, v));
-                            } else if coord_1.is_none() {
-                                coord_1 = Some((u, v));
-                            } else {
-                                j += 1;
-                                continue 'chars;
-                            }
-                            while v < lines[i].len() && lines[u][v].is_ascii_digit() {
-                                v += 1;
-                            }
-                            continue;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    sum_p1
+}
+
+fn part_2(lines: &[&[u8]]) -> u32 {
+    let mut sum_p2 = 0;
+
+    for i in 0..lines.len() {
+        'chars: for j in 0..lines[i].len() {
+            if lines[i][j] != b'*' {
+                continue;
+            }
+            let mut coord_0 = None;
+            let mut coord_1 = None;
+
+            for u in i.saturating_sub(1)..=(i + 1).min(lines.len() - 1) {
+                let mut v = j.saturating_sub(1);
+                while v <= (j + 1).min(lines[i].len() - 1) {
+                    if lines[u][v].is_ascii_digit() {
+                        if coord_0.is_none() {
+                            coord_0 = Some((u, v));
+                        } else if coord_1.is_none() {
+                            coord_1 = Some((u, v));
+                        } else {
+                            continue 'chars;
+                        }
+                        while v < lines[i].len() && lines[u][v].is_ascii_digit() {
+                            v += 1;
                         }
-                        v += 1;
+                        continue;
                     }
+                    v += 1;
                 }
-                if coord_0.is_some() && coord_1.is_some() {
-                    let num1 = parse_num_p2(lines[coord_0.unwrap().0], coord_0.unwrap().1);
-                    let num2 = parse_num_p2(lines[coord_1.unwrap().0], coord_1.unwrap().1);
-                    sum_p2 += num1 * num2;
-                }
             }
-            j += 1;
+            if let (Some(coord_0), Some(coord_1)) = (coord_0, coord_1) {
+                let num1 = parse_num_p2(lines[coord_0.0], coord_0.1);
+                let num2 = parse_num_p2(lines[coord_1.0], coord_1.1);
+                sum_p2 += num1 * num2;
+            }
         }
     }
 
-    (sum_p1, sum_p2)
+    sum_p2
 }
 
-pub fn day03() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
+const TEST_INPUT: &str = indoc! {r#"
         467..114..
         ...*......
         ..35..633.
@@ -107,13 +124,41 @@ mod test {
         .664.598..
     "#};
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = Vec<&'a [u8]>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("4361"),
+        part2: Some("467835"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_schematic(input)
+    }
+
+    fn part1(lines: &Self::Parsed<'_>) -> String {
+        part_1(lines).to_string()
+    }
+
+    fn part2(lines: &Self::Parsed<'_>) -> String {
+        part_2(lines).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT).0, 4361);
+        assert_eq!(part_1(&parse_schematic(TEST_INPUT).unwrap()), 4361);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT).1, 467835);
+        assert_eq!(part_2(&parse_schematic(TEST_INPUT).unwrap()), 467835);
     }
 }
@@ -1,21 +1,32 @@
-const INPUT: &str = include_str!("../input/day04.txt");
-
 /// Returns the number of wins for each card.
-fn get_card_wins(input: &str) -> Vec<u32> {
+use aoc_common::{parse, Example, ParseError, Solution};
+use indoc::indoc;
+
+fn get_card_wins(input: &str) -> Result<Vec<u32>, ParseError> {
+    let numbers = |nums: &str| {
+        nums.split_ascii_whitespace()
+            .map(|n| parse::token::<u32>(input, n))
+            .collect::<Result<Vec<_>, _>>()
+    };
     input
         .lines()
         .map(|card| {
-            let (_card_id, values) = card.split_once(": ").unwrap();
-            let (win_nums, scratched_nums) = values.split_once(" | ").unwrap();
-            let mut win_nums = win_nums
-                .split_ascii_whitespace()
-                .map(|n| n.parse::<u32>().unwrap())
-                .collect::<Vec<_>>();
+            let (card_id, values) = card.split_once(": ").ok_or_else(|| {
+                ParseError::at(input, card, "expected a card, like Card 1: 41 48 | 83 86")
+            })?;
+            if !card_id.starts_with("Card ") {
+                return Err(ParseError::at(input, card_id, "expected Card <id>"));
+            }
+            let (win_nums, scratched_nums) = values.split_once(" | ").ok_or_else(|| {
+                let reason = "expected winning numbers and scratched numbers, separated by |";
+                ParseError::at(input, values, reason)
+            })?;
+            if win_nums.trim().is_empty() {
+                return Err(ParseError::at(input, win_nums, "expected winning numbers"));
+            }
+            let mut win_nums = numbers(win_nums)?;
             win_nums.sort_unstable();
-            let mut scratched_nums = scratched_nums
-                .split_ascii_whitespace()
-                .map(|n| n.parse::<u32>().unwrap())
-                .collect::<Vec<_>>();
+            let mut scratched_nums = numbers(scratched_nums)?;
             scratched_nums.sort_unstable();
 
             let mut num_wins = 0;
@@ -31,7 +42,7 @@ fn get_card_wins(input: &str) -> Vec<u32> {
                     num_wins += 1;
                 }
             }
-            num_wins
+            Ok(num_wins)
         })
         .collect()
 }
@@ -58,25 +69,7 @@ fn solve_p2(wins: &[u32]) -> u32 {
     cards_copies.into_iter().sum()
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let wins = get_card_wins(input);
-    let p1 = solve_p1(&wins);
-    let p2 = solve_p2(&wins);
-
-    (p1, p2)
-}
-
-pub fn day04() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
+const TEST_INPUT: &str = indoc! {r#"
         Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
         Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
         Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
@@ -85,20 +78,48 @@ mod test {
         Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
     "#};
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Vec<u32>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("13"),
+        part2: Some("30"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        get_card_wins(input)
+    }
+
+    fn part1(wins: &Self::Parsed<'_>) -> String {
+        solve_p1(wins).to_string()
+    }
+
+    fn part2(wins: &Self::Parsed<'_>) -> String {
+        solve_p2(wins).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_get_card_wins() {
-        assert_eq!(get_card_wins(TEST_INPUT), vec![4, 2, 2, 1, 0, 0]);
+        assert_eq!(get_card_wins(TEST_INPUT).unwrap(), vec![4, 2, 2, 1, 0, 0]);
     }
 
     #[test]
     fn test_part_1() {
-        let wins = get_card_wins(TEST_INPUT);
+        let wins = get_card_wins(TEST_INPUT).unwrap();
         assert_eq!(solve_p1(&wins), 13);
     }
 
     #[test]
     fn test_part_2() {
-        let wins = get_card_wins(TEST_INPUT);
+        let wins = get_card_wins(TEST_INPUT).unwrap();
         assert_eq!(solve_p2(&wins), 30);
     }
 }
@@ -1,7 +1,7 @@
+use aoc_common::{parse, Example, ParseError, Rng, Solution};
+use indoc::indoc;
 use std::ops::Add;
 
-const INPUT: &str = include_str!("../input/day05.txt");
-
 fn move_range<T: Ord + Copy + Add<Output = T>>(
     r: &std::ops::Range<T>,
     offset: T,
@@ -9,32 +9,59 @@ fn move_range<T: Ord + Copy + Add<Output = T>>(
     r.start + offset..r.end + offset
 }
 
-fn solve(input: &str) -> (i64, i64) {
+/// The seeds, and the maps from a category to the next: a destination, a source and a length
+/// per line
+pub struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<Vec<[i64; 3]>>,
+}
+
+/// The seeds, in pairs for part 2, and the maps of 3 numbers per line. The numbers are small
+/// enough for their sums not to overflow.
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let numbers = |line: &str| {
+        line.split(' ')
+            .map(|num| parse::token::<u32>(input, num).map(i64::from))
+            .collect::<Result<Vec<_>, _>>()
+    };
     let mut categories = input.split("\n\n");
-    let mut seeds_p1: Vec<i64> = categories
-        .next()
-        .unwrap()
-        .split_once(": ")
-        .unwrap()
-        .1
-        .split(' ')
-        .map(|num| num.parse().unwrap())
-        .collect();
-    let mut seeds_p2: Vec<_> = seeds_p1.chunks(2).map(|c| c[0]..c[0] + c[1]).collect();
-
-    let maps = categories.map(|cat| {
-        let mut lines = cat.lines();
-        lines.next();
-        lines
-            .map(|line| {
-                line.split(' ')
-                    .map(|num| num.parse().unwrap())
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>()
-    });
-    for map in maps {
-        let mut mapped_seeds_p2 = Vec::with_capacity(seeds_p2.len() * 2);
+    let seeds = parse::next(input, &mut categories, "the seeds")?;
+    let seeds_str = seeds
+        .strip_prefix("seeds: ")
+        .ok_or_else(|| ParseError::at(input, seeds, "expected seeds: <numbers>"))?;
+    let seeds = numbers(seeds_str)?;
+    if seeds.len() % 2 != 0 {
+        return Err(ParseError::at(
+            input,
+            seeds_str,
+            "expected pairs of numbers",
+        ));
+    }
+    let mut maps = Vec::new();
+    for category in categories {
+        let mut lines = category.lines();
+        let header = parse::next(input, &mut lines, "a map")?;
+        if !header.ends_with(" map:") {
+            return Err(ParseError::at(input, header, "expected <name> map:"));
+        }
+        let mut map = Vec::new();
+        for line in lines {
+            match numbers(line)?[..] {
+                [destination, source, length] => map.push([destination, source, length]),
+                _ => {
+                    let reason = "expected a destination, a source and a length";
+                    return Err(ParseError::at(input, line, reason));
+                }
+            }
+        }
+        maps.push(map);
+    }
+    Ok(Almanac { seeds, maps })
+}
+
+fn part_1(almanac: &Almanac) -> i64 {
+    let mut seeds_p1 = almanac.seeds.clone();
+    for map in &almanac.maps {
         for line in map {
             let source_range = line[1]..line[1] + line[2];
             let diff = line[0] - line[1];
@@ -44,6 +71,22 @@ fn solve(input: &str) -> (i64, i64) {
                     *seed = -(*seed + diff);
                 }
             }
+        }
+        for s in &mut seeds_p1 {
+            *s = s.abs();
+        }
+    }
+
+    seeds_p1.into_iter().fold(i64::MAX, |acc, x| acc.min(x))
+}
+
+fn part_2(almanac: &Almanac) -> i64 {
+    let mut seeds_p2: Vec<_> = almanac.seeds.chunks(2).map(|c| c[0]..c[0] + c[1]).collect();
+    for map in &almanac.maps {
+        let mut mapped_seeds_p2 = Vec::with_capacity(seeds_p2.len() * 2);
+        for line in map {
+            let source_range = line[1]..line[1] + line[2];
+            let diff = line[0] - line[1];
             let mut tmp_seeds_p2 = Vec::with_capacity(seeds_p2.len() * 2);
             for seed_range in seeds_p2.into_iter() {
                 if source_range.start < seed_range.end && seed_range.start < source_range.end {
@@ -67,30 +110,14 @@ fn solve(input: &str) -> (i64, i64) {
             seeds_p2 = tmp_seeds_p2;
         }
         seeds_p2.append(&mut mapped_seeds_p2);
-        for s in &mut seeds_p1 {
-            *s = s.abs();
-        }
     }
 
-    let p1 = seeds_p1.into_iter().fold(i64::MAX, |acc, x| acc.min(x));
-    let p2 = seeds_p2
+    seeds_p2
         .into_iter()
-        .fold(i64::MAX, |acc, x| acc.min(x.start));
-
-    (p1, p2)
-}
-
-pub fn day05() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+        .fold(i64::MAX, |acc, x| acc.min(x.start))
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
+const TEST_INPUT: &str = indoc! {r#"
         seeds: 79 14 55 13
 
         seed-to-soil map:
@@ -126,13 +153,92 @@ mod test {
         56 93 4
     "#};
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed<'a> = Almanac;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("35"),
+        part2: Some("46"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_almanac(input)
+    }
+
+    fn part1(almanac: &Self::Parsed<'_>) -> String {
+        part_1(almanac).to_string()
+    }
+
+    fn part2(almanac: &Self::Parsed<'_>) -> String {
+        part_2(almanac).to_string()
+    }
+
+    /// `size` ranges of seeds, and `size` ranges in each map. The ranges of a map do not overlap,
+    /// the numbers fit in a `u32`.
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+        const LIMIT: i64 = 4_000_000_000;
+
+        let seeds: Vec<_> = (0..size)
+            .map(|_| {
+                let start = rng.range(0..=LIMIT - 1);
+                let len = rng.range(1..=(LIMIT - start).min(100_000_000));
+                format!("{start} {len}")
+            })
+            .collect();
+        let mut input = format!("seeds: {}\n", seeds.join(" "));
+        for names in CATEGORIES.windows(2) {
+            input += &format!("\n{}-to-{} map:\n", names[0], names[1]);
+            let mut bounds: Vec<_> = (0..2 * size).map(|_| rng.range(0..=LIMIT)).collect();
+            bounds.sort_unstable();
+            let mut lines: Vec<_> = bounds
+                .chunks(2)
+                .filter(|range| range[0] < range[1])
+                .map(|range| {
+                    let len = range[1] - range[0];
+                    format!("{} {} {len}\n", rng.range(0..=LIMIT - len), range[0])
+                })
+                .collect();
+            rng.shuffle(&mut lines);
+            input.extend(lines);
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT).0, 35);
+        assert_eq!(part_1(&parse_almanac(TEST_INPUT).unwrap()), 35);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = Day05::generate(10, &mut Rng::new(5)).unwrap();
+        assert_eq!(Some(&input), Day05::generate(10, &mut Rng::new(5)).as_ref());
+        let almanac = parse_almanac(&input).unwrap();
+        // the maps keep the numbers below their limit
+        let (p1, p2) = (part_1(&almanac), part_2(&almanac));
+        assert!((0..4_000_000_000).contains(&p1) && (0..4_000_000_000).contains(&p2));
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT).1, 46);
+        assert_eq!(part_2(&parse_almanac(TEST_INPUT).unwrap()), 46);
     }
 }
@@ -1,6 +1,7 @@
-const INPUT: &str = include_str!("../input/day06.txt");
-
 /// In part one, time is small but there are many races.
+use aoc_common::{parse, Example, ParseError, Solution};
+use indoc::indoc;
+
 fn ways_to_win_race_p1(time: i64, distance: i64) -> i64 {
     let mut ways_to_win = 0;
     for button_time in 0..time {
@@ -32,6 +33,32 @@ fn ways_to_win_race_p2(time: i64, distance: i64) -> i64 {
     bounds.0 - bounds.1 + 1
 }
 
+/// Checks a line like `Time:      7  15   30`, returns it and its number of races
+fn check_line<'a>(
+    input: &'a str,
+    line: Option<&'a str>,
+    name: &str,
+) -> Result<(&'a str, usize), ParseError> {
+    let line = line.ok_or_else(|| ParseError::at(input, "", format!("missing the {name} line")))?;
+    let values = line
+        .strip_prefix(name)
+        .and_then(|l| l.strip_prefix(':'))
+        .ok_or_else(|| ParseError::at(input, line, format!("expected {name}: <numbers>")))?;
+    for num in values.split_whitespace() {
+        parse::token::<u32>(input, num)?;
+    }
+    // part 2 reads a single number, ignoring the spaces
+    let joined = values.split_whitespace().collect::<String>();
+    if joined.parse::<i64>().is_err() {
+        return Err(ParseError::at(
+            input,
+            values,
+            "expected a single race in part 2",
+        ));
+    }
+    Ok((line, values.split_whitespace().count()))
+}
+
 fn solve_p1(input: &str) -> i64 {
     let mut lines = input.lines();
     let times: Vec<_> = lines
@@ -50,7 +77,7 @@ fn solve_p1(input: &str) -> i64 {
         .collect();
     let mut p1 = 1;
 
-    for (time, distance) in times.into_iter().zip(distances.into_iter()) {
+    for (time, distance) in times.into_iter().zip(distances) {
         p1 *= ways_to_win_race_p1(time, distance);
     }
 
@@ -79,22 +106,45 @@ fn solve_p2(input: &str) -> i64 {
     ways_to_win_race_p2(time, distance)
 }
 
-pub fn day06() -> (String, String) {
-    let p1 = solve_p1(INPUT);
-    let p2 = solve_p2(INPUT);
+const TEST_INPUT: &str = indoc! {r#"
+        Time:      7  15   30
+        Distance:  9  40  200
+    "#};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = &'a str;
 
-    (p1.to_string(), p2.to_string())
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("288"),
+        part2: Some("71503"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        let mut lines = input.lines();
+        let (_, races) = check_line(input, lines.next(), "Time")?;
+        let (distances, records) = check_line(input, lines.next(), "Distance")?;
+        if races != records {
+            let reason = "expected a distance for each time";
+            return Err(ParseError::at(input, distances, reason));
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> String {
+        solve_p1(input).to_string()
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> String {
+        solve_p2(input).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
-        Time:      7  15   30
-        Distance:  9  40  200
-    "#};
 
     #[test]
     fn test_part_1() {
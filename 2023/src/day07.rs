@@ -1,6 +1,7 @@
-const INPUT: &str = include_str!("../input/day07.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
+use indoc::indoc;
 
-#[derive(Eq, Debug, Clone, Ord)]
+#[derive(Eq, Debug, Clone)]
 struct CardHand {
     pub bid: i64,
     pub cards: u64,
@@ -13,16 +14,38 @@ impl std::cmp::PartialEq for CardHand {
     }
 }
 
-impl std::cmp::PartialOrd for CardHand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl std::cmp::Ord for CardHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         if self.kind != other.kind {
-            Some(self.kind.cmp(&other.kind))
+            self.kind.cmp(&other.kind)
         } else {
-            Some(self.cards.cmp(&other.cards))
+            self.cards.cmp(&other.cards)
         }
     }
 }
 
+impl std::cmp::PartialOrd for CardHand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Checks that `line` is a hand of 5 cards and a bid, like `32T3K 765`
+fn check_hand(input: &str, line: &str) -> Result<(), ParseError> {
+    let (cards, bid) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::at(input, line, "expected cards and a bid, like 32T3K 765"))?;
+    if let Some(i) = cards.find(|c| !"23456789TJQKA".contains(c)) {
+        let reason = "expected a card: 2-9, T, J, Q, K or A";
+        return Err(ParseError::at(input, &cards[i..], reason));
+    }
+    if cards.len() != 5 {
+        return Err(ParseError::at(input, cards, "expected 5 cards"));
+    }
+    parse::token::<u32>(input, bid)?;
+    Ok(())
+}
+
 fn solve_p1(input: &str) -> i64 {
     let mut hands: Vec<CardHand> = Vec::new();
 
@@ -60,7 +83,7 @@ fn solve_p1(input: &str) -> i64 {
         hands.push(CardHand {
             bid: bid_str.parse().unwrap(),
             kind: hand_kind,
-            cards: cards,
+            cards,
         });
     }
     hands.sort();
@@ -111,7 +134,7 @@ fn solve_p2(input: &str) -> i64 {
         hands.push(CardHand {
             bid: bid_str.parse().unwrap(),
             kind: hand_kind,
-            cards: cards,
+            cards,
         });
     }
     hands.sort();
@@ -123,27 +146,45 @@ fn solve_p2(input: &str) -> i64 {
     sum
 }
 
-pub fn day07() -> (String, String) {
-    let p1 = solve_p1(INPUT);
-    let p2 = solve_p2(INPUT);
+const TEST_INPUT: &str = indoc! {r#"
+        32T3K 765
+        T55J5 684
+        KK677 28
+        KTJJT 220
+        QQQJA 483
+    "#};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = &'a str;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("6440"),
+        part2: Some("5905"),
+    }];
 
-    // 247670564 too low
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // the hands are ranked differently for each part, they are only checked here
+        for line in input.lines() {
+            check_hand(input, line)?;
+        }
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> String {
+        solve_p1(input).to_string()
+    }
 
-    (p1.to_string(), p2.to_string())
+    fn part2(input: &Self::Parsed<'_>) -> String {
+        solve_p2(input).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
-        32T3K 765
-        T55J5 684
-        KK677 28
-        KTJJT 220
-        QQQJA 483
-    "#};
 
     #[test]
     fn test_part_1() {
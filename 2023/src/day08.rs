@@ -1,4 +1,6 @@
-const INPUT: &str = include_str!("../input/day08.txt");
+use aoc_common::{Example, ParseError, Rng, Solution};
+use indoc::indoc;
+use std::collections::{HashMap, HashSet};
 
 const fn name_to_idx(name: &str) -> u32 {
     let letters = name.as_bytes();
@@ -11,25 +13,19 @@ const fn name_to_idx(name: &str) -> u32 {
 }
 
 #[allow(dead_code)]
-fn idx_to_name(idx: u32) -> &'static str {
-    static mut NAME: [u8; 3] = [0; 3];
-    unsafe {
-        NAME[0] = (idx & 0b11111) as u8 + b'A';
-        NAME[1] = (idx >> 5 & 0b11111) as u8 + b'A';
-        NAME[2] = (idx >> 10 & 0b11111) as u8 + b'A';
-        std::str::from_utf8_unchecked(&NAME)
-    }
+fn idx_to_name(idx: u32) -> String {
+    [idx & 0b11111, idx >> 5 & 0b11111, idx >> 10 & 0b11111]
+        .iter()
+        .map(|&l| (l as u8 + b'A') as char)
+        .collect()
 }
 
 /// least common multiple
 fn lcm(values: &[u64]) -> u64 {
     // a * b / gcd(a, b)
-
-    let mut gcd_ = values[0];
-    for value in values.iter().skip(1) {
-        gcd_ = gcd(gcd_, *value);
-    }
-    gcd_ * values.iter().map(|value| value / gcd_).product::<u64>()
+    values
+        .iter()
+        .fold(1, |lcm, &value| lcm / gcd(lcm, value) * value)
 }
 
 /// greatest common denominator/divisor/factor
@@ -48,24 +44,66 @@ fn gcd(a: u64, b: u64) -> u64 {
     }
 }
 
-fn parse(input: &str) -> (&[u8], Vec<(u32, u32)>) {
+/// The instructions, and the left and right nodes of each node indexed by `name_to_idx`
+type Network<'a> = (&'a [u8], Vec<(u32, u32)>);
+
+fn parse(input: &str) -> Result<Network<'_>, ParseError> {
+    let invalid = |token, reason| ParseError::at(input, token, reason);
+    let node_name =
+        |name: &str| match name.len() == 3 && name.bytes().all(|c| c.is_ascii_uppercase()) {
+            true => Ok(name_to_idx(name)),
+            false => Err(ParseError::at(
+                input,
+                name,
+                "expected a node of 3 uppercase letters",
+            )),
+        };
     let mut nodes_vec = vec![(u32::MAX, u32::MAX); name_to_idx("ZZZ") as usize + 1];
 
-    let (instructions, nodes) = input.split_once("\n\n").unwrap();
+    let (instructions, nodes) = input.split_once("\n\n").ok_or_else(|| {
+        invalid(
+            input,
+            "expected instructions and nodes, separated by an empty line",
+        )
+    })?;
+    if let Some(i) = instructions.find(|c| c != 'L' && c != 'R') {
+        return Err(invalid(&instructions[i..], "expected L or R"));
+    }
+    if instructions.is_empty() {
+        return Err(invalid(instructions, "expected instructions"));
+    }
+    let mut links = Vec::new();
     for node in nodes.lines() {
-        let (name, directions) = node.split_once(" = ").unwrap();
+        let (name, directions) = node
+            .split_once(" = ")
+            .ok_or_else(|| invalid(node, "expected a node, like AAA = (BBB, CCC)"))?;
         let (left, right) = directions
-            .trim_start_matches('(')
-            .trim_end_matches(')')
-            .split_once(", ")
-            .unwrap();
-        let name_idx = name_to_idx(name);
-        let left_idx = name_to_idx(left);
-        let right_idx = name_to_idx(right);
+            .strip_prefix('(')
+            .and_then(|d| d.strip_suffix(')'))
+            .and_then(|d| d.split_once(", "))
+            .ok_or_else(|| invalid(directions, "expected (<left>, <right>)"))?;
+        let name_idx = node_name(name)?;
+        let left_idx = node_name(left)?;
+        let right_idx = node_name(right)?;
         nodes_vec[name_idx as usize] = (left_idx, right_idx);
+        links.extend([left, right]);
+    }
+    // the paths only go through nodes with directions
+    if let Some(link) = links
+        .into_iter()
+        .find(|&link| nodes_vec[name_to_idx(link) as usize].0 == u32::MAX)
+    {
+        return Err(invalid(link, "undefined node"));
+    }
+    // part 1 goes from AAA to ZZZ
+    for name in ["AAA", "ZZZ"] {
+        if nodes_vec[name_to_idx(name) as usize].0 == u32::MAX {
+            let reason = format!("expected a node {name}");
+            return Err(ParseError::at(input, &input[input.len()..], reason));
+        }
     }
 
-    (instructions.as_bytes(), nodes_vec)
+    Ok((instructions.as_bytes(), nodes_vec))
 }
 
 fn solve_p1(instructions: &[u8], nodes: &[(u32, u32)]) -> i64 {
@@ -89,58 +127,119 @@ fn solve_p1(instructions: &[u8], nodes: &[(u32, u32)]) -> i64 {
     num_steps
 }
 
+/// The answer of part 2 when the paths are never all on a **Z node at once
+const NEVER: &str = "the paths are never all on a **Z node at once";
+
+/// Whether `node` is a **Z node
+fn is_arrived(node: u32) -> bool {
+    node >= name_to_idx("AAZ")
+}
+
+/// The node after `node`, at the `step`th step
+fn next_node(instructions: &[u8], nodes: &[(u32, u32)], node: u32, step: usize) -> u32 {
+    let (left, right) = nodes[node as usize];
+    match instructions[step % instructions.len()] {
+        b'L' => left,
+        _ => right,
+    }
+}
+
 /// For part 2 the solution is HUGE, impossible to compute using loops.
 /// Some input analysis is required to find a pattern.
 /// Basically each path neatly loops around (N steps to a **Z node, then N steps until it comes back to a **Z).
 /// So the solution is the least common multiple of the number of steps for each path !
-fn solve_p2(instructions: &[u8], nodes: &[(u32, u32)]) -> u64 {
-    let current_nodes = (name_to_idx("AAA")..=name_to_idx("ZZA"))
-        .map(|id| &nodes[id as usize])
-        .filter(|directions| directions.0 != u32::MAX)
-        .collect::<Vec<_>>();
-    let is_arrived = |node: &u32| *node >= name_to_idx("AAZ");
-
-    let steps_for_each: Vec<_> = current_nodes
-        .iter()
-        .map(|node| {
-            let mut num_steps = 0;
-            let mut cur_node: &(_, _) = *node;
-            let mut instructions_it = instructions.iter().cycle();
-            loop {
-                num_steps += 1;
-                let instruction = instructions_it.next().unwrap();
-                let direction = match instruction {
-                    b'L' => cur_node.0,
-                    b'R' => cur_node.1,
-                    _ => unreachable!(),
-                };
-                cur_node = &nodes[direction as usize];
-                if is_arrived(&direction) {
-                    break;
-                }
-            }
-            num_steps
-        })
+/// Each path is checked to loop that way, otherwise the paths are walked at once.
+/// `None` if they are never all on a **Z node at once.
+fn solve_p2(instructions: &[u8], nodes: &[(u32, u32)]) -> Option<u64> {
+    let starts: Vec<u32> = (name_to_idx("AAA")..=name_to_idx("ZZA"))
+        .filter(|&id| nodes[id as usize].0 != u32::MAX)
         .collect();
+    // a path that takes more steps than there are (node, instruction) pairs goes in circles
+    let states = nodes.iter().filter(|d| d.0 != u32::MAX).count() * instructions.len();
 
-    lcm(&steps_for_each)
-}
-
-pub fn day08() -> (String, String) {
-    let (instructions, nodes) = parse(INPUT);
+    let mut steps_for_each = Vec::new();
+    for &start in &starts {
+        let mut node = start;
+        let mut num_steps = 0;
+        while num_steps == 0 || !is_arrived(node) {
+            if num_steps == states {
+                return None;
+            }
+            node = next_node(instructions, nodes, node, num_steps);
+            num_steps += 1;
+        }
+        // N steps later, the path must be back on the same **Z node at the same instruction,
+        // without any **Z node in between
+        let end = node;
+        let mut loops = num_steps % instructions.len() == 0;
+        for step in num_steps..2 * num_steps {
+            if !loops {
+                break;
+            }
+            node = next_node(instructions, nodes, node, step);
+            loops = is_arrived(node) == (step + 1 == 2 * num_steps);
+        }
+        if !loops || node != end {
+            return walk_together(instructions, nodes, &starts);
+        }
+        steps_for_each.push(num_steps as u64);
+    }
 
-    let p1 = solve_p1(instructions, &nodes);
-    let p2 = solve_p2(instructions, &nodes);
+    Some(lcm(&steps_for_each))
+}
 
-    (p1.to_string(), p2.to_string())
+/// Walks the paths from `starts` at once until they all are on a **Z node, `None` if they come
+/// back where they were at the same instruction before that
+fn walk_together(instructions: &[u8], nodes: &[(u32, u32)], starts: &[u32]) -> Option<u64> {
+    let mut current = starts.to_vec();
+    let mut seen = HashSet::new();
+    let mut num_steps = 0;
+    loop {
+        if current.iter().all(|&node| is_arrived(node)) {
+            return Some(num_steps as u64);
+        }
+        if !seen.insert((num_steps % instructions.len(), current.clone())) {
+            return None;
+        }
+        for node in current.iter_mut() {
+            *node = next_node(instructions, nodes, *node, num_steps);
+        }
+        num_steps += 1;
+    }
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
+/// Walks every path at once, one step at a time, until they all are on a **Z node,
+/// `None` if they go through the same nodes at the same instruction twice before that
+fn reference(input: &str, part: u8) -> Option<u64> {
+    let (instructions, nodes) = input.split_once("\n\n").unwrap();
+    let nodes: HashMap<_, _> = nodes
+        .lines()
+        .map(|line| (&line[0..3], (&line[7..10], &line[12..15])))
+        .collect();
+    let (mut current, end): (Vec<_>, fn(&str) -> bool) = match part {
+        1 => (vec!["AAA"], |node| node == "ZZZ"),
+        _ => (
+            nodes.keys().copied().filter(|n| n.ends_with('A')).collect(),
+            |node| node.ends_with('Z'),
+        ),
+    };
+    let mut seen = HashSet::new();
+    for (steps, (i, instruction)) in (0..).zip(instructions.bytes().enumerate().cycle()) {
+        if current.iter().all(|node| end(node)) {
+            return Some(steps);
+        }
+        if !seen.insert((i, current.clone())) {
+            return None;
+        }
+        for node in current.iter_mut() {
+            let (left, right) = nodes[node];
+            *node = if instruction == b'L' { left } else { right };
+        }
+    }
+    unreachable!()
+}
 
-    const TEST_INPUT_1: &str = indoc! {r#"
+const TEST_INPUT_1: &str = indoc! {r#"
         RL
 
         AAA = (BBB, CCC)
@@ -152,7 +251,7 @@ mod test {
         ZZZ = (ZZZ, ZZZ)
     "#};
 
-    const TEST_INPUT_2: &str = indoc! {r#"
+const TEST_INPUT_2: &str = indoc! {r#"
         LLR
 
         AAA = (BBB, BBB)
@@ -160,7 +259,7 @@ mod test {
         ZZZ = (ZZZ, ZZZ)
     "#};
 
-    const TEST_INPUT_3: &str = indoc! {r#"
+const TEST_INPUT_3: &str = indoc! {r#"
         LR
 
         AAA = (AAB, XXX)
@@ -171,8 +270,105 @@ mod test {
         BBC = (BBZ, BBZ)
         BBZ = (BBB, BBB)
         XXX = (XXX, XXX)
+        ZZZ = (ZZZ, ZZZ)
     "#};
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = Network<'a>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: TEST_INPUT_1,
+            part1: Some("2"),
+            part2: None,
+        },
+        Example {
+            input: TEST_INPUT_2,
+            part1: Some("6"),
+            part2: None,
+        },
+        Example {
+            input: TEST_INPUT_3,
+            part1: None,
+            part2: Some("6"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1((instructions, nodes): &Self::Parsed<'_>) -> String {
+        solve_p1(instructions, nodes).to_string()
+    }
+
+    fn part2((instructions, nodes): &Self::Parsed<'_>) -> String {
+        solve_p2(instructions, nodes).map_or(NEVER.to_string(), |steps| steps.to_string())
+    }
+
+    /// Up to 4 paths from a **A node (the first from AAA) to a **Z node, of a multiple of the
+    /// instructions up to `size` steps. From their **Z, half of them go on as from their **A,
+    /// the loops part 2 relies on, the others go back to any node of the path.
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let size = size.max(1) as i64;
+        let instructions: String = (0..rng.range(1..=size))
+            .map(|_| *rng.choose(&['L', 'R']))
+            .collect();
+        let letter = |rng: &mut Rng, from: u8, to: u8| rng.range(from as i64..=to as i64) as u8;
+        let prefix = |rng: &mut Rng| {
+            String::from_utf8(vec![letter(rng, b'A', b'Z'), letter(rng, b'A', b'Z')]).unwrap()
+        };
+        let mut names = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+        let mut nodes = Vec::new();
+        for path in 0..rng.range(1..=4) {
+            let (start, end) = match path {
+                0 => ("AAA".to_string(), "ZZZ".to_string()),
+                _ => loop {
+                    let prefix = prefix(rng);
+                    let (start, end) = (prefix.clone() + "A", prefix + "Z");
+                    if !names.contains(&start) && !names.contains(&end) {
+                        names.extend([start.clone(), end.clone()]);
+                        break (start, end);
+                    }
+                },
+            };
+            let length = instructions.len() as i64;
+            let steps = length * rng.range(1..=(size / length).max(1));
+            let mut path = vec![start];
+            for _ in 1..steps {
+                let node = loop {
+                    let mut node = prefix(rng);
+                    node.push(letter(rng, b'B', b'Y') as char);
+                    if names.insert(node.clone()) {
+                        break node;
+                    }
+                };
+                path.push(node);
+            }
+            path.push(end);
+            let after_end = match rng.range(0..=1) {
+                0 => path[1].clone(),
+                _ => path[rng.range(0..=steps) as usize].clone(),
+            };
+            for (from, to) in path.iter().zip(path.iter().skip(1).chain([&after_end])) {
+                nodes.push(format!("{from} = ({to}, {to})"));
+            }
+        }
+        rng.shuffle(&mut nodes);
+        Some(format!("{instructions}\n\n{}\n", nodes.join("\n")))
+    }
+
+    fn reference(input: &str, part: u8) -> Option<String> {
+        Some(reference(input, part).map_or(NEVER.to_string(), |steps| steps.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_name_to_idx() {
         assert_eq!(name_to_idx("AAA"), 0);
@@ -182,22 +378,65 @@ mod test {
         assert_eq!("ZZZ", idx_to_name(name_to_idx("ZZZ")));
     }
 
+    #[test]
+    fn test_parse() {
+        assert!(parse(TEST_INPUT_1).is_ok());
+        assert!(parse(&TEST_INPUT_1.replace("AAA = (BBB, CCC)\n", "")).is_err());
+        assert!(parse(&TEST_INPUT_2.replace("ZZZ = (ZZZ, ZZZ)\n", "")).is_err());
+    }
+
     #[test]
     fn test_part_1_input_1() {
-        let (instructions, nodes) = parse(TEST_INPUT_1);
+        let (instructions, nodes) = parse(TEST_INPUT_1).unwrap();
         assert_eq!(solve_p1(instructions, &nodes), 2);
     }
 
     #[test]
     fn test_part_1_input_2() {
-        let (instructions, nodes) = parse(TEST_INPUT_2);
+        let (instructions, nodes) = parse(TEST_INPUT_2).unwrap();
         assert_eq!(solve_p1(instructions, &nodes), 6);
     }
 
     #[test]
     fn test_part_2() {
-        let (instructions, nodes) = parse(TEST_INPUT_3);
-        assert_eq!(solve_p2(instructions, &nodes), 6);
+        let (instructions, nodes) = parse(TEST_INPUT_3).unwrap();
+        assert_eq!(solve_p2(instructions, &nodes), Some(6));
+    }
+
+    #[test]
+    fn test_part_2_offset_loops() {
+        // AAA is on AAZ every 2 steps, BBA on BBZ after 1 step then every 3 steps
+        let input = indoc! {"
+            L
+
+            AAA = (AAB, AAB)
+            AAB = (AAZ, AAZ)
+            AAZ = (AAB, AAB)
+            BBA = (BBZ, BBZ)
+            BBZ = (BBB, BBB)
+            BBB = (BBC, BBC)
+            BBC = (BBZ, BBZ)
+            ZZZ = (ZZZ, ZZZ)
+        "};
+        let (instructions, nodes) = parse(input).unwrap();
+        assert_eq!(solve_p2(instructions, &nodes), Some(4));
+        assert_eq!(reference(input, 2), Some(4));
+        // BBA on BBZ every odd step
+        let input = input.replace("BBB = (BBC, BBC)\nBBC = (BBZ, BBZ)", "BBB = (BBZ, BBZ)");
+        let (instructions, nodes) = parse(&input).unwrap();
+        assert_eq!(solve_p2(instructions, &nodes), None);
+        assert_eq!(reference(&input, 2), None);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(&[6, 10, 15]), 30);
+        assert_eq!(lcm(&[4, 8, 3]), 24);
+    }
+
+    #[test]
+    fn test_reference() {
+        aoc_common::differential::check::<Day08>(10, 0..100).unwrap();
     }
 
     // #[test]
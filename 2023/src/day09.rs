@@ -1,80 +1,112 @@
-const INPUT: &str = include_str!("../input/day09.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
+use indoc::indoc;
 
-fn extrapolate_history(numbers: &[i64]) -> (i64, i64) {
+/// The value after the last of `numbers`
+fn extrapolate_history(numbers: &[i64]) -> i64 {
     let differences = numbers
         .windows(2)
         .map(|chunk| chunk[1] - chunk[0])
         .collect::<Vec<_>>();
     let last_num = *numbers.last().unwrap();
-    let first_num = *numbers.first().unwrap();
     if differences.iter().all(|diff| *diff == 0) {
-        (first_num, last_num)
+        last_num
     } else {
-        let (before_diff, after_diff) = extrapolate_history(&differences);
-        (first_num - before_diff, last_num + after_diff)
+        last_num + extrapolate_history(&differences)
     }
 }
 
-fn solve(input: &str) -> (i64, i64) {
-    let report = input
+/// The values of each history. They fit in an `i32`, so that their differences cannot overflow.
+fn parse_report(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
         .lines()
         .map(|line| {
             line.split(' ')
-                .map(|val| val.parse().unwrap())
-                .collect::<Vec<_>>()
+                .map(|val| parse::token::<i32>(input, val).map(i64::from))
+                .collect()
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
+fn part_1(report: &[Vec<i64>]) -> i64 {
     report
         .iter()
-        .map(|history| extrapolate_history(&history))
-        .fold((0, 0), |acc, x| (acc.0 + x.0, acc.1 + x.1))
+        .map(|history| extrapolate_history(history))
+        .sum()
 }
 
-pub fn day09() -> (String, String) {
-    let (p2, p1) = solve(INPUT);
-
-    (p1.to_string(), p2.to_string())
+/// Extrapolating backwards is extrapolating the reversed history
+fn part_2(report: &[Vec<i64>]) -> i64 {
+    report
+        .iter()
+        .map(|history| {
+            let reversed: Vec<_> = history.iter().rev().copied().collect();
+            extrapolate_history(&reversed)
+        })
+        .sum()
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
+const TEST_INPUT: &str = indoc! {r#"
         0 3 6 9 12 15
         1 3 6 10 15 21
         10 13 16 21 30 45
     "#};
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Parsed<'a> = Vec<Vec<i64>>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("114"),
+        part2: Some("2"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_report(input)
+    }
+
+    fn part1(report: &Self::Parsed<'_>) -> String {
+        part_1(report).to_string()
+    }
+
+    fn part2(report: &Self::Parsed<'_>) -> String {
+        part_2(report).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_extrapolate_history_after() {
-        assert_eq!(extrapolate_history(&[0, 0, 0]).1, 0);
-        assert_eq!(extrapolate_history(&[2, 2]).1, 2);
-        assert_eq!(extrapolate_history(&[0, 2, 4, 6]).1, 8);
-        assert_eq!(extrapolate_history(&[3, 3, 5, 9, 15]).1, 23);
+        assert_eq!(extrapolate_history(&[0, 0, 0]), 0);
+        assert_eq!(extrapolate_history(&[2, 2]), 2);
+        assert_eq!(extrapolate_history(&[0, 2, 4, 6]), 8);
+        assert_eq!(extrapolate_history(&[3, 3, 5, 9, 15]), 23);
     }
 
     #[test]
     fn test_extrapolate_history_before() {
-        assert_eq!(extrapolate_history(&[0, 0, 0]).0, 0);
-        assert_eq!(extrapolate_history(&[2, 2]).0, 2);
-        assert_eq!(extrapolate_history(&[0, 2, 4, 6]).0, -2);
-        assert_eq!(extrapolate_history(&[3, 3, 5, 9, 15]).0, 5);
-
-        assert_eq!(extrapolate_history(&[10, 13, 16, 21, 30, 45]).0, 5);
-        assert_eq!(extrapolate_history(&[0, 3, 6, 9, 12, 15]).0, -3);
-        assert_eq!(extrapolate_history(&[1, 3, 6, 10, 15, 21]).0, 0);
+        // as part 2 does, on the reversed history
+        assert_eq!(extrapolate_history(&[0, 0, 0]), 0);
+        assert_eq!(extrapolate_history(&[2, 2]), 2);
+        assert_eq!(extrapolate_history(&[6, 4, 2, 0]), -2);
+        assert_eq!(extrapolate_history(&[15, 9, 5, 3, 3]), 5);
+
+        assert_eq!(extrapolate_history(&[45, 30, 21, 16, 13, 10]), 5);
+        assert_eq!(extrapolate_history(&[15, 12, 9, 6, 3, 0]), -3);
+        assert_eq!(extrapolate_history(&[21, 15, 10, 6, 3, 1]), 0);
     }
 
     #[test]
     fn test_solve_p1() {
-        assert_eq!(solve(TEST_INPUT).1, 114);
+        assert_eq!(part_1(&parse_report(TEST_INPUT).unwrap()), 114);
     }
 
     #[test]
     fn test_solve_p2() {
-        assert_eq!(solve(TEST_INPUT).0, 2);
+        assert_eq!(part_2(&parse_report(TEST_INPUT).unwrap()), 2);
     }
 }
@@ -1,9 +1,9 @@
+use aoc_common::{parse, Example, ParseError, Solution};
+use indoc::indoc;
 use std::collections::HashMap;
 
-const INPUT: &str = include_str!("../input/day14.txt");
-
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
-struct Platform {
+pub struct Platform {
     width: usize,
     height: usize,
     rocks: Vec<u8>,
@@ -88,25 +88,25 @@ impl Platform {
     }
 }
 
-fn solve(input: &str) -> (u64, u64) {
-    let platform = input
-        .as_bytes()
-        .iter()
-        .filter(|&&c| c != b'\n')
-        .copied()
-        .collect::<Vec<_>>();
-    let width = input.find('\n').unwrap();
-    let height = input.len() / (width + 1);
-    let mut platform = Platform {
-        width,
-        height,
-        rocks: platform,
-    };
-    let mut cache: HashMap<Platform, usize> = HashMap::new();
-    cache.insert(platform.clone(), 0);
+fn parse_platform(input: &str) -> Result<Platform, ParseError> {
+    let rows = parse::grid(input, ".#O")?;
+    Ok(Platform {
+        width: rows[0].len(),
+        height: rows.len(),
+        rocks: rows.concat().into_bytes(),
+    })
+}
 
+fn part_1(platform: &Platform) -> u64 {
+    let mut platform = platform.clone();
     platform.tilt_north();
-    let p1 = platform.calculate_load();
+    platform.calculate_load()
+}
+
+fn part_2(platform: &Platform) -> u64 {
+    let mut platform = platform.clone();
+    let mut cache: HashMap<Platform, usize> = HashMap::new();
+    cache.insert(platform.clone(), 0);
 
     for i in 0..1000000000 {
         platform.tilt_north();
@@ -128,23 +128,10 @@ fn solve(input: &str) -> (u64, u64) {
         }
         cache.insert(platform.clone(), i + 1);
     }
-    let p2 = platform.calculate_load();
-
-    (p1, p2)
+    platform.calculate_load()
 }
 
-pub fn day14() -> (String, String) {
-    let (p1, p2) = solve(INPUT);
-
-    (p1.to_string(), p2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {r#"
+const TEST_INPUT: &str = indoc! {r#"
         O....#....
         O.OO#....#
         .....##...
@@ -157,16 +144,41 @@ mod test {
         #OO..#....
     "#};
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed<'a> = Platform;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("136"),
+        part2: Some("64"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_platform(input)
+    }
+
+    fn part1(platform: &Self::Parsed<'_>) -> String {
+        part_1(platform).to_string()
+    }
+
+    fn part2(platform: &Self::Parsed<'_>) -> String {
+        part_2(platform).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     #[test]
     fn test_p1() {
-        assert_eq!(solve(TEST_INPUT).0, 136);
+        assert_eq!(part_1(&parse_platform(TEST_INPUT).unwrap()), 136);
     }
 
-
     #[test]
     fn test_p2() {
-        assert_eq!(solve(TEST_INPUT).1, 64);
+        assert_eq!(part_2(&parse_platform(TEST_INPUT).unwrap()), 64);
     }
-
-
 }
@@ -1,6 +1,6 @@
-const INPUT: &str = include_str!("../input/day01.txt");
+use aoc_common::{Example, ParseError, Solution};
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
+fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
     let line_parser = nom::sequence::terminated(
         nom::sequence::separated_pair(
             nom::character::complete::u32::<_, nom::error::Error<_>>,
@@ -18,66 +18,78 @@ fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
             (vec_a, vec_b)
         },
     );
-    let (remaining, parsed) = parser(input).expect("Could not parse input");
-    assert!(remaining.is_empty());
-    parsed
-}
-
-fn solve(input: &str) -> (u32, u32) {
-    let (mut a, mut b) = parse_input(input);
+    let (mut a, mut b) = crate::finish(input, parser(input), "two numbers per line")?;
+    // both parts pair the numbers in order
     a.sort();
     b.sort();
+    Ok((a, b))
+}
 
-    let part1 = a
-        .iter()
+fn part_1((a, b): &(Vec<u32>, Vec<u32>)) -> u32 {
+    a.iter()
         .zip(b.iter())
-        .fold(0, |acc, (a, b)| acc + a.abs_diff(*b));
+        .fold(0, |acc, (a, b)| acc + a.abs_diff(*b))
+}
 
+fn part_2((a, b): &(Vec<u32>, Vec<u32>)) -> u32 {
     let mut part2 = 0;
     let mut i = 0;
     let mut j = 0;
-    let b_len = b.len();
-    let a_len = a.len();
-    // padd the arrays with 1 extra value to avoid having to check `j < b.len()` everywhere
-    a.push(u32::MAX);
-    b.push(u32::MAX);
-    while i < a_len && j < b_len {
+    while i < a.len() && j < b.len() {
         let mut num_matches = 0;
-        while b[j] < a[i] {
+        let cur_a_val = a[i];
+        while j < b.len() && b[j] < cur_a_val {
             j += 1;
         }
-        while b[j] == a[i] {
+        while j < b.len() && b[j] == cur_a_val {
             num_matches += 1;
             j += 1;
         }
-        let cur_a_val = a[i];
-        while a[i] == cur_a_val {
+        while i < a.len() && a[i] == cur_a_val {
             part2 += cur_a_val * num_matches;
             i += 1
         }
     }
-
-    (part1, part2)
+    part2
 }
 
-pub fn day01() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+const TEST_INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Parsed<'a> = (Vec<u32>, Vec<u32>);
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("11"),
+        part2: Some("31"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(lists: &Self::Parsed<'_>) -> String {
+        part_1(lists).to_string()
+    }
+
+    fn part2(lists: &Self::Parsed<'_>) -> String {
+        part_2(lists).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
-
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT).0, 11);
+        assert_eq!(part_1(&parse_input(TEST_INPUT).unwrap()), 11);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT).1, 31);
+        assert_eq!(part_2(&parse_input(TEST_INPUT).unwrap()), 31);
     }
 }
@@ -1,6 +1,6 @@
-const INPUT: &str = include_str!("../input/day02.txt");
+use aoc_common::{Example, ParseError, Solution};
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
     let mut parser = nom::multi::separated_list1(
         nom::character::complete::newline,
         nom::multi::separated_list1(
@@ -8,10 +8,7 @@ fn parse_input(input: &str) -> Vec<Vec<i32>> {
             nom::character::complete::i32::<_, nom::error::Error<_>>,
         ),
     );
-
-    let (remaining, parsed) = parser(input).expect("Could not parse input");
-    assert_eq!(remaining.trim(), "");
-    parsed
+    crate::finish(input, parser(input), "levels separated by spaces")
 }
 
 fn is_safe_level_interval(sign: &mut i32, diff: i32) -> bool {
@@ -30,66 +27,93 @@ fn is_safe_level_interval(sign: &mut i32, diff: i32) -> bool {
     }
 }
 
-fn is_report_safe(report: &mut Vec<i32>, did_rm_level: bool) -> (bool, bool) {
+/// Whether the levels all increase or all decrease, by 1 to 3
+fn is_report_safe_p1(report: &[i32]) -> bool {
+    let mut sign = 0;
+    report
+        .windows(2)
+        .all(|pair| is_safe_level_interval(&mut sign, pair[0] - pair[1]))
+}
+
+/// Whether the report is safe once at most one level is removed
+fn is_report_safe(report: &mut Vec<i32>, did_rm_level: bool) -> bool {
     let mut sign = 0;
     for i in 0..report.len() - 1 {
         let diff = report[i] - report[i + 1];
         if !is_safe_level_interval(&mut sign, diff) {
             if did_rm_level {
-                return (false, false);
+                return false;
             }
             let report_i = report.remove(i);
-            if is_report_safe(report, true).1 {
-                return (false, true);
+            if is_report_safe(report, true) {
+                return true;
             }
             let report_i_plus_one = report[i];
             report[i] = report_i; // add report[i] back and remove report[i+1]
-            if is_report_safe(report, true).1 {
-                return (false, true);
+            if is_report_safe(report, true) {
+                return true;
             }
             if i > 0 {
                 report[i - 1] = report[i];
                 report[i] = report_i_plus_one;
-                if is_report_safe(report, true).1 {
-                    return (false, true);
+                if is_report_safe(report, true) {
+                    return true;
                 }
             }
-            return (false, false);
+            return false;
         }
     }
-    (!did_rm_level, true)
+    true
+}
+
+fn part_1(table: &[Vec<i32>]) -> usize {
+    table
+        .iter()
+        .filter(|report| is_report_safe_p1(report))
+        .count()
+}
+
+fn part_2(table: &[Vec<i32>]) -> usize {
+    table
+        .iter()
+        .filter(|report| is_report_safe(&mut (*report).clone(), false))
+        .count()
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let table = parse_input(input);
+const TEST_INPUT: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+pub struct Day02;
 
-    let mut num_safe_reports_p1 = 0;
-    let mut num_safe_reports_p2 = 0;
+impl Solution for Day02 {
+    type Parsed<'a> = Vec<Vec<i32>>;
 
-    for mut report in table.into_iter() {
-        let (safe_p1, safe_p2) = is_report_safe(&mut report, false);
-        num_safe_reports_p1 += safe_p1 as u32;
-        num_safe_reports_p2 += safe_p2 as u32;
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("2"),
+        part2: Some("4"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input(input)
     }
 
-    (num_safe_reports_p1, num_safe_reports_p2)
-}
+    fn part1(table: &Self::Parsed<'_>) -> String {
+        part_1(table).to_string()
+    }
 
-pub fn day02() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+    fn part2(table: &Self::Parsed<'_>) -> String {
+        part_2(table).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
-
     #[test]
     fn test_parser() {
         assert_eq!(
-            parse_input(TEST_INPUT),
+            parse_input(TEST_INPUT).unwrap(),
             vec![
                 vec![7, 6, 4, 2, 1],
                 vec![1, 2, 7, 8, 9],
@@ -117,7 +141,7 @@ mod test {
         ];
         for report in reports {
             assert!(
-                is_report_safe(&mut (report.clone()), false).1,
+                is_report_safe(&mut (report.clone()), false),
                 "report {report:?} failed"
             );
         }
@@ -125,12 +149,12 @@ mod test {
 
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT).0, 2);
+        assert_eq!(part_1(&parse_input(TEST_INPUT).unwrap()), 2);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT).1, 4);
-        assert_eq!(solve("1 1 2\n2 6 1").1, 2);
+        assert_eq!(part_2(&parse_input(TEST_INPUT).unwrap()), 4);
+        assert_eq!(part_2(&parse_input("1 1 2\n2 6 1").unwrap()), 2);
     }
 }
@@ -1,15 +1,14 @@
+use aoc_common::{Example, ParseError, Solution};
 use nom::Parser;
 
-const INPUT: &str = include_str!("../input/day03.txt");
-
 #[derive(Debug)]
-enum Instruction {
+pub enum Instruction {
     Do,
     Dont,
     Mul((u32, u32)),
 }
 
-fn parse_input(input: &str) -> Vec<Instruction> {
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
     let parse_instr_mul = nom::combinator::map(
         nom::sequence::delimited(
             nom::bytes::complete::tag("mul("),
@@ -41,15 +40,21 @@ fn parse_input(input: &str) -> Vec<Instruction> {
         nom::bytes::complete::take(1usize).map(|_| None),
     )));
 
-    let (remaining, parsed) = parser.parse(input).expect("Could not parse input");
-    assert_eq!(remaining.trim(), "");
-    parsed.into_iter().flatten().collect()
+    let parsed = crate::finish(input, parser.parse(input), "a corrupted memory")?;
+    Ok(parsed.into_iter().flatten().collect())
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let table = parse_input(input);
+fn part_1(table: &[Instruction]) -> u32 {
+    table
+        .iter()
+        .map(|instr| match instr {
+            Instruction::Mul((x, y)) => x * y,
+            _ => 0,
+        })
+        .sum()
+}
 
-    let mut part1 = 0;
+fn part_2(table: &[Instruction]) -> u32 {
     let mut part2 = 0;
     let mut dont = false;
     for instr in table {
@@ -59,38 +64,64 @@ fn solve(input: &str) -> (u32, u32) {
             }
             Instruction::Dont => dont = true,
             Instruction::Mul((x, y)) => {
-                part1 += x * y;
                 if !dont {
                     part2 += x * y;
                 }
             }
         }
     }
-
-    (part1, part2)
+    part2
 }
 
-pub fn day03() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+const TEST_INPUT_P1: &str =
+    "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+
+const TEST_INPUT_P2: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Parsed<'a> = Vec<Instruction>;
+
+    const EXAMPLES: &'static [Example] = &[
+        Example {
+            input: TEST_INPUT_P1,
+            part1: Some("161"),
+            part2: None,
+        },
+        Example {
+            input: TEST_INPUT_P2,
+            part1: None,
+            part2: Some("48"),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        // any character that is not part of an instruction is skipped
+        parse_input(input)
+    }
+
+    fn part1(instructions: &Self::Parsed<'_>) -> String {
+        part_1(instructions).to_string()
+    }
+
+    fn part2(instructions: &Self::Parsed<'_>) -> String {
+        part_2(instructions).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT_P1: &str =
-        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    const TEST_INPUT_P2: &str =
-        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT_P1).0, 161);
+        assert_eq!(part_1(&parse_input(TEST_INPUT_P1).unwrap()), 161);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT_P2).1, 48);
+        assert_eq!(part_2(&parse_input(TEST_INPUT_P2).unwrap()), 48);
     }
 }
@@ -1,11 +1,22 @@
-const INPUT: &str = include_str!("../input/day04.txt");
+use aoc_common::{parse, Example, ParseError, Solution};
 
-fn solve(input: &str) -> (u32, u32) {
-    let table = input.as_bytes();
-    let width = table.iter().position(|c| *c == b'\n').unwrap() + 1;
-    let w = width as isize;
+/// The letters, with the line ends, and the width of a line (with its `\n`)
+pub struct Grid<'a> {
+    table: &'a [u8],
+    width: usize,
+}
+
+fn parse_input(input: &str) -> Result<Grid<'_>, ParseError> {
+    let rows = parse::grid(input, "XMAS")?;
+    Ok(Grid {
+        table: input.as_bytes(),
+        width: rows[0].len() + 1,
+    })
+}
 
-    let p1_directions = [
+fn part_1(&Grid { table, width }: &Grid) -> u32 {
+    let w = width as isize;
+    let directions = [
         [0, 1, 2, 3],
         [0, -1, -2, -3],
         [0, w, 2 * w, 3 * w],
@@ -16,57 +27,80 @@ fn solve(input: &str) -> (u32, u32) {
         [0, -w + 1, -2 * w + 2, -3 * w + 3],
     ];
     let mut sum_p1 = 0;
-    let mut sum_p2 = 0;
     for i in 0..table.len() {
         let is = i as isize;
-        if table[is as usize] == b'\n' {
+        if table[i] != b'X' {
             continue;
-        };
-        if table[is as usize] == b'X' {
-            for dir in p1_directions.iter() {
-                if is + dir[3] < 0 || is + dir[3] >= table.len() as isize {
-                    continue;
-                }
-                let to_match = dir.map(|d| unsafe { *table.get_unchecked((is + d) as usize) });
-                if to_match == "XMAS".as_bytes() {
-                    sum_p1 += 1;
-                }
-            }
         }
-        if is < (table.len() as isize) - 2 - 2 * w {
-            let a = [table[i], table[i + 1 + width], table[i + 2 + 2 * width]];
-            let b = [table[i + 2], table[i + 1 + width], table[i + 2 * width]];
-
-            if (a == "MAS".as_bytes() || a == "SAM".as_bytes())
-                && (b == "MAS".as_bytes() || b == "SAM".as_bytes())
-            {
-                sum_p2 += 1;
+        for dir in directions.iter() {
+            if is + dir[3] < 0 || is + dir[3] >= table.len() as isize {
+                continue;
+            }
+            let to_match = dir.map(|d| unsafe { *table.get_unchecked((is + d) as usize) });
+            if to_match == "XMAS".as_bytes() {
+                sum_p1 += 1;
             }
         }
     }
+    sum_p1
+}
+
+fn part_2(&Grid { table, width }: &Grid) -> u32 {
+    let mut sum_p2 = 0;
+    for i in 0..(table.len() as isize - 2 - 2 * width as isize).max(0) as usize {
+        if table[i] == b'\n' {
+            continue;
+        };
+        let a = [table[i], table[i + 1 + width], table[i + 2 + 2 * width]];
+        let b = [table[i + 2], table[i + 1 + width], table[i + 2 * width]];
 
-    (sum_p1, sum_p2)
+        if (a == "MAS".as_bytes() || a == "SAM".as_bytes())
+            && (b == "MAS".as_bytes() || b == "SAM".as_bytes())
+        {
+            sum_p2 += 1;
+        }
+    }
+    sum_p2
 }
 
-pub fn day04() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+const TEST_INPUT: &str =
+        "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Parsed<'a> = Grid<'a>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("18"),
+        part2: Some("9"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(grid: &Self::Parsed<'_>) -> String {
+        part_1(grid).to_string()
+    }
+
+    fn part2(grid: &Self::Parsed<'_>) -> String {
+        part_2(grid).to_string()
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    const TEST_INPUT: &str =
-        "MMMSXXMASM\nMSAMXMSMSA\nAMXSXMAAMM\nMSAMASMSMX\nXMASAMXAMM\nXXAMMXXAMA\nSMSMSASXSS\nSAXAMASAAA\nMAMMMXMMMM\nMXMXAXMASX";
-
     #[test]
     fn test_part_1() {
-        assert_eq!(solve(TEST_INPUT).0, 18);
+        assert_eq!(part_1(&parse_input(TEST_INPUT).unwrap()), 18);
     }
 
     #[test]
     fn test_part_2() {
-        assert_eq!(solve(TEST_INPUT).1, 9);
+        assert_eq!(part_2(&parse_input(TEST_INPUT).unwrap()), 9);
     }
 }
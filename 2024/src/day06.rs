@@ -1,9 +1,7 @@
-use std::hint::unreachable_unchecked;
+use aoc_common::{parse, Example, ParseError, Solution};
 
 use log::{debug, trace};
 
-const INPUT: &str = include_str!("../input/day06.txt");
-
 #[derive(Debug, Copy, Clone)]
 /// 4 bits: cell type
 /// 3 bits: visited direction p1
@@ -119,12 +117,15 @@ struct Table {
 }
 
 impl Table {
-    pub fn new(input: &str) -> Self {
-        let width = input.as_bytes().iter().position(|c| *c == b'\n').unwrap();
-        let mut table = Vec::with_capacity(width * 2 + input.len());
-        table.extend(std::iter::repeat(Cell::new(b'\n')).take(width));
-        table.extend(input.as_bytes().iter().map(|b| Cell::new(*b)));
-        table.extend(std::iter::repeat(Cell::new(b'\n')).take(width));
+    /// The table of `rows`, each ended by a newline, between rows of newlines
+    pub fn new(rows: &[&str]) -> Self {
+        let width = rows[0].len();
+        let mut table = Vec::with_capacity((width + 1) * (rows.len() + 2));
+        table.extend(std::iter::repeat_n(Cell::new(b'\n'), width));
+        for row in rows {
+            table.extend(row.bytes().chain(*b"\n").map(Cell::new));
+        }
+        table.extend(std::iter::repeat_n(Cell::new(b'\n'), width));
 
         Self {
             buf: table,
@@ -176,7 +177,7 @@ fn rotate(table: &Table, guard_dir: isize) -> isize {
     } else if guard_dir == (table.width) {
         -1
     } else {
-        unsafe { unreachable_unchecked() };
+        unreachable!()
     }
 }
 
@@ -206,22 +207,72 @@ fn solve_is_a_loop(
     }
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let mut table = Table::new(input);
-    let mut guard_dir = -(table.width);
-    let mut guard_pos = table.buf.iter().position(Cell::is_start).unwrap();
+/// The lab and where the guard starts, facing up
+#[derive(Debug, Clone)]
+pub struct Lab {
+    table: Table,
+    start: usize,
+}
 
-    let mut p2_visitor_id = 2;
+fn parse_input(input: &str) -> Result<Lab, ParseError> {
+    let rows = parse::grid(input, ".#^")?;
+    if input.matches('^').count() != 1 {
+        let guard = rows
+            .iter()
+            .rev()
+            .find_map(|row| row.find('^').map(|i| &row[i..]));
+        return Err(ParseError::at(
+            input,
+            guard.unwrap_or(""),
+            "expected a single guard",
+        ));
+    }
+    let table = Table::new(&rows);
+    let start = table.buf.iter().position(Cell::is_start).unwrap();
+    Ok(Lab { table, start })
+}
+
+fn part_1(lab: &Lab) -> u32 {
+    let mut table = lab.table.clone();
+    let mut guard_dir = -(table.width);
+    let mut guard_pos = lab.start;
     let mut p1 = 0;
-    let mut p2 = 0;
 
     loop {
         trace!("p1\n{}", table.as_str(1, guard_pos));
 
+        if table.buf[guard_pos].did_visit(1) == Direction::None {
+            table.buf[guard_pos].set_visited_p1(Direction::from_gdir(guard_dir));
+            p1 += 1;
+        }
+
+        let mut next_pos = (guard_pos as isize + guard_dir) as usize;
+        if table.buf[next_pos].is_blocked() {
+            guard_dir = rotate(&table, guard_dir);
+            next_pos = (guard_pos as isize + guard_dir) as usize;
+        }
+        if table.buf[next_pos].is_off_limit() {
+            break;
+        }
+        guard_pos = next_pos;
+    }
+    debug!("Final State:\n{}", table.as_str(1, guard_pos));
+    p1
+}
+
+/// Walks the path of part 1, trying an obstacle on each cell before the guard first steps on it
+fn part_2(lab: &Lab) -> u32 {
+    let mut table = lab.table.clone();
+    let mut guard_dir = -(table.width);
+    let mut guard_pos = lab.start;
+
+    let mut p2_visitor_id = 2;
+    let mut p2 = 0;
+
+    loop {
         let gdir = Direction::from_gdir(guard_dir);
         if table.buf[guard_pos].did_visit(1) == Direction::None {
             table.buf[guard_pos].set_visited_p1(gdir);
-            p1 += 1;
         }
 
         let mut next_pos = (guard_pos as isize + guard_dir) as usize;
@@ -250,20 +301,10 @@ fn solve(input: &str) -> (u32, u32) {
         }
         guard_pos = next_pos;
     }
-    debug!("Final State:\n{}", table.as_str(1, guard_pos));
-    (p1, p2)
-}
-
-pub fn day06() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+    p2
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = r#"....#.....
+const TEST_INPUT: &str = r#"....#.....
 .........#
 ..........
 ..#.......
@@ -275,6 +316,34 @@ mod test {
 ......#...
 "#;
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Parsed<'a> = Lab;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("41"),
+        part2: Some("6"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(lab: &Self::Parsed<'_>) -> String {
+        part_1(lab).to_string()
+    }
+
+    fn part2(lab: &Self::Parsed<'_>) -> String {
+        part_2(lab).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     fn init() {
         let _ = env_logger::builder().is_test(true).try_init();
     }
@@ -282,21 +351,24 @@ mod test {
     #[test]
     fn test_part_1() {
         init();
-        assert_eq!(solve(TEST_INPUT).0, 41);
+        assert_eq!(part_1(&parse_input(TEST_INPUT).unwrap()), 41);
+        let crlf = TEST_INPUT.replace('\n', "\r\n");
+        assert_eq!(part_1(&parse_input(&crlf).unwrap()), 41);
     }
 
     #[test]
     fn test_part_2() {
         init();
-        assert_eq!(solve(TEST_INPUT).1, 6);
+        assert_eq!(part_2(&parse_input(TEST_INPUT).unwrap()), 6);
     }
 
     #[test]
     fn test_part_2_2() {
         init();
         assert_eq!(
-            solve(
-                "...........#.....#......
+            part_2(
+                &parse_input(
+                    "...........#.....#......
 ...................#....
 ...#.....##.............
 ......................#.
@@ -308,8 +380,9 @@ mod test {
 ..........#..........#..
 ..#.....#..........#....
 ........#.....#..#......"
-            )
-            .1,
+                )
+                .unwrap()
+            ),
             19
         );
     }
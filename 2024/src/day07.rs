@@ -1,8 +1,7 @@
+use aoc_common::{Example, ParseError, Rng, Solution};
 use log::debug;
 
-const INPUT: &str = include_str!("../input/day07.txt");
-
-fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
+fn parse(input: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
     let mut parser = nom::multi::separated_list1(
         nom::character::complete::newline::<_, nom::error::Error<_>>,
         nom::sequence::separated_pair(
@@ -15,10 +14,8 @@ fn parse(input: &str) -> Vec<(u64, Vec<u64>)> {
         ),
     );
 
-    let (remaining, parsed) = parser(input).expect("Could not parse input");
-    assert_eq!(remaining.trim(), "");
-
-    parsed
+    let reason = "equations like 190: 10 19";
+    crate::finish(input, parser(input), reason)
 }
 
 fn concat(a: u64, b: u64) -> u64 {
@@ -62,39 +59,36 @@ fn try_compute_p1(nums: &[u64]) -> Vec<u64> {
     }
 }
 
-fn solve(input: &str) -> (u64, u64) {
-    let parsed = parse(input);
-
+fn part_1(equations: &[(u64, Vec<u64>)]) -> u64 {
     let mut p1 = 0;
-    let mut p2 = 0;
-    for (res, nums) in parsed {
-        let results_1 = try_compute_p1(&nums);
+    for &(res, ref nums) in equations {
+        let results_1 = try_compute_p1(nums);
         debug!("1: {} in {:?} ?", res, results_1);
         if results_1.contains(&res) {
             p1 += res;
+        }
+    }
+    p1
+}
+
+fn part_2(equations: &[(u64, Vec<u64>)]) -> u64 {
+    let mut p2 = 0;
+    for &(res, ref nums) in equations {
+        // most equations are true without concatenation, faster to check
+        if try_compute_p1(nums).contains(&res) {
             p2 += res;
         } else {
-            let results_2 = try_compute_p2(&nums);
+            let results_2 = try_compute_p2(nums);
             debug!("2: {} in {:?} ?", res, results_2);
             if results_2.contains(&res) {
                 p2 += res;
             }
         }
     }
-
-    (p1, p2)
-}
-
-pub fn day07() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+    p2
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = r#"190: 10 19
+const TEST_INPUT: &str = r#"190: 10 19
 3267: 81 40 27
 83: 17 5
 156: 15 6
@@ -105,6 +99,57 @@ mod test {
 292: 11 6 16 20
 "#;
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Parsed<'a> = Vec<(u64, Vec<u64>)>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("3749"),
+        part2: Some("11387"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(equations: &Self::Parsed<'_>) -> String {
+        part_1(equations).to_string()
+    }
+
+    fn part2(equations: &Self::Parsed<'_>) -> String {
+        part_2(equations).to_string()
+    }
+
+    /// `size` equations of 2 to 8 numbers below 100, so that no combination overflows, half of
+    /// them made true by random operators
+    fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+        let mut input = String::new();
+        for _ in 0..size {
+            let len = rng.range(2..=8);
+            let nums: Vec<u64> = (0..len).map(|_| rng.range(1..=99) as u64).collect();
+            let res = match rng.one_in(2) {
+                true => nums[1..]
+                    .iter()
+                    .fold(nums[0], |acc, &n| match rng.below(3) {
+                        0 => acc + n,
+                        1 => acc * n,
+                        _ => concat(acc, n),
+                    }),
+                false => rng.range(1..=10_000_000_000) as u64,
+            };
+            let nums: Vec<_> = nums.iter().map(u64::to_string).collect();
+            input += &format!("{res}: {}\n", nums.join(" "));
+        }
+        Some(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     fn init() {
         let _ = env_logger::builder().is_test(true).try_init();
     }
@@ -112,12 +157,22 @@ mod test {
     #[test]
     fn test_part_1() {
         init();
-        assert_eq!(solve(TEST_INPUT).0, 3749);
+        assert_eq!(part_1(&parse(TEST_INPUT).unwrap()), 3749);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = Day07::generate(50, &mut Rng::new(7)).unwrap();
+        assert_eq!(Some(&input), Day07::generate(50, &mut Rng::new(7)).as_ref());
+        let equations = parse(&input).unwrap();
+        assert_eq!(equations.len(), 50);
+        let (p1, p2) = (part_1(&equations), part_2(&equations));
+        assert!(0 < p1 && p1 <= p2);
     }
 
     #[test]
     fn test_part_2() {
         init();
-        assert_eq!(solve(TEST_INPUT).1, 11387);
+        assert_eq!(part_2(&parse(TEST_INPUT).unwrap()), 11387);
     }
 }
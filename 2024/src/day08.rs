@@ -1,9 +1,11 @@
+use aoc_common::{parse, Example, ParseError, Solution};
 use core::str;
 use std::ops::{Add, Sub};
 
 use log::debug;
 
-const INPUT: &str = include_str!("../input/day08.txt");
+/// Empty locations and the frequencies of the antennas
+const LOCATIONS: &str = ".0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct Point<T>(T, T);
@@ -32,19 +34,24 @@ fn map_as_str(map: &[Vec<u8>]) -> String {
         .join("\n")
 }
 
-fn solve(input: &str) -> (u32, u32) {
-    let width = input.as_bytes().iter().position(|&c| c == b'\n').unwrap() as i32;
-    let height = (input.len() + 1) as i32 / (width + 1);
+/// The size of the map, and the antennas grouped by frequency
+#[derive(Debug)]
+pub struct Antennas {
+    width: i32,
+    height: i32,
+    groups: Vec<Vec<Point<i32>>>,
+}
 
-    let mut map_2d_p1 = input
-        .lines()
-        .map(|s| s.as_bytes().to_owned())
-        .collect::<Vec<_>>();
+impl Antennas {
+    fn contains(&self, a: Point<i32>) -> bool {
+        (0..self.width).contains(&a.0) && (0..self.height).contains(&a.1)
+    }
+}
 
-    let mut map_2d_p2 = input
-        .lines()
-        .map(|s| s.as_bytes().to_owned())
-        .collect::<Vec<_>>();
+fn parse_input(input: &str) -> Result<Antennas, ParseError> {
+    let rows = parse::grid(input, LOCATIONS)?;
+    let width = rows[0].len() as i32;
+    let height = rows.len() as i32;
 
     let mut flat_nodes = input
         .as_bytes()
@@ -58,24 +65,31 @@ fn solve(input: &str) -> (u32, u32) {
             }
         })
         .collect::<Vec<_>>();
-    flat_nodes.sort_by(|a, b| a.0.cmp(&b.0));
+    flat_nodes.sort_by_key(|a| a.0);
 
     let mut last_node = 0;
-    let mut grouped_nodes: Vec<Vec<Point<i32>>> = Vec::with_capacity(30);
+    let mut groups: Vec<Vec<Point<i32>>> = Vec::with_capacity(30);
     for n in flat_nodes {
         let pt = Point(n.1 % (width + 1), n.1 / (width + 1));
         if n.0 == last_node {
-            grouped_nodes.last_mut().unwrap().push(pt);
+            groups.last_mut().unwrap().push(pt);
         } else {
-            grouped_nodes.push(vec![pt]);
+            groups.push(vec![pt]);
         }
         last_node = n.0;
     }
-    debug!("Grouped nodes: {:?}", grouped_nodes);
+    debug!("Grouped nodes: {:?}", groups);
+    Ok(Antennas {
+        width,
+        height,
+        groups,
+    })
+}
 
-    let mut p1 = 0;
-    let mut p2 = 0;
-    for node_group in grouped_nodes {
+/// Calls `f` with every pair of antennas of the same frequency, and the vector from the second
+/// to the first
+fn for_each_pair(antennas: &Antennas, mut f: impl FnMut(Point<i32>, Point<i32>, Point<i32>)) {
+    for node_group in &antennas.groups {
         let mut it = node_group.iter();
 
         while let Some(&n0) = it.next() {
@@ -83,52 +97,55 @@ fn solve(input: &str) -> (u32, u32) {
                 let delta = n0 - n1;
                 debug_assert_eq!(n0 - delta, n1);
                 debug_assert_eq!(n1 + delta, n0);
+                f(n0, n1, delta);
+            }
+        }
+    }
+}
 
-                let antinodes_p1 = [n0 + delta, n1 - delta];
-                for &a in antinodes_p1.iter() {
-                    if (0..width).contains(&a.0) && (0..height).contains(&a.1) {
-                        let loc = &mut map_2d_p1[a.1 as usize][a.0 as usize];
-                        if *loc != b'#' {
-                            p1 += 1;
-                            *loc = b'#';
-                        }
-                    }
+fn part_1(antennas: &Antennas) -> u32 {
+    let mut antinodes = vec![vec![false; antennas.width as usize]; antennas.height as usize];
+    let mut p1 = 0;
+    for_each_pair(antennas, |n0, n1, delta| {
+        for a in [n0 + delta, n1 - delta] {
+            if antennas.contains(a) {
+                let loc = &mut antinodes[a.1 as usize][a.0 as usize];
+                if !*loc {
+                    p1 += 1;
+                    *loc = true;
                 }
+            }
+        }
+    });
+    p1
+}
 
-                let mut antinodes_p2 = [n0, n1];
-                let mut out_of_bounds = false;
-                while !out_of_bounds {
-                    out_of_bounds = true;
-                    for a in antinodes_p2 {
-                        if (0..width).contains(&a.0) && (0..height).contains(&a.1) {
-                            out_of_bounds = false;
-                            let loc = &mut map_2d_p2[a.1 as usize][a.0 as usize];
-                            if *loc != b'#' {
-                                p2 += 1;
-                                *loc = b'#';
-                            }
-                        }
+fn part_2(antennas: &Antennas) -> u32 {
+    let mut antinodes = vec![vec![false; antennas.width as usize]; antennas.height as usize];
+    let mut p2 = 0;
+    for_each_pair(antennas, |n0, n1, delta| {
+        let mut antinodes_p2 = [n0, n1];
+        let mut out_of_bounds = false;
+        while !out_of_bounds {
+            out_of_bounds = true;
+            for a in antinodes_p2 {
+                if antennas.contains(a) {
+                    out_of_bounds = false;
+                    let loc = &mut antinodes[a.1 as usize][a.0 as usize];
+                    if !*loc {
+                        p2 += 1;
+                        *loc = true;
                     }
-                    antinodes_p2[0] = antinodes_p2[0] + delta;
-                    antinodes_p2[1] = antinodes_p2[1] - delta;
                 }
             }
+            antinodes_p2[0] = antinodes_p2[0] + delta;
+            antinodes_p2[1] = antinodes_p2[1] - delta;
         }
-    }
-
-    (p1, p2)
+    });
+    p2
 }
 
-pub fn day08() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const TEST_INPUT: &str = r#"............
+const TEST_INPUT: &str = r#"............
 ........0...
 .....0......
 .......0....
@@ -141,6 +158,34 @@ mod test {
 ............
 ............"#;
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Parsed<'a> = Antennas;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some("14"),
+        part2: Some("34"),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(antennas: &Self::Parsed<'_>) -> String {
+        part_1(antennas).to_string()
+    }
+
+    fn part2(antennas: &Self::Parsed<'_>) -> String {
+        part_2(antennas).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
     fn init() {
         let _ = env_logger::builder().is_test(true).try_init();
     }
@@ -148,12 +193,12 @@ mod test {
     #[test]
     fn test_part_1() {
         init();
-        assert_eq!(solve(TEST_INPUT).0, 14);
+        assert_eq!(part_1(&parse_input(TEST_INPUT).unwrap()), 14);
     }
 
     #[test]
     fn test_part_2() {
         init();
-        assert_eq!(solve(TEST_INPUT).1, 34);
+        assert_eq!(part_2(&parse_input(TEST_INPUT).unwrap()), 34);
     }
 }
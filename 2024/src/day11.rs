@@ -1,9 +1,8 @@
+use aoc_common::{parse, Example, ParseError, Solution};
 use core::str;
 use std::collections::HashMap;
 
 
-const INPUT: &str = include_str!("../input/day11.txt");
-
 fn split_even_digits(num: u64) -> Option<(u64, u64)> {
     let mut digit_count = 0;
     let mut n = num;
@@ -51,22 +50,51 @@ fn stone(mut val: u64, iter: usize, shortcut: &mut HashMap<(u64, usize), u64>) -
     res
 }
 
-fn solve(input: &str) -> (u64, u64) {
-    let parsed: Vec<u64> = input
+/// The engraved numbers, small enough for the stones to split before they overflow
+fn parse_stones(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
         .trim()
         .split(' ')
-        .map(|s| s.parse().unwrap())
-        .collect();
+        .map(|s| parse::token::<u32>(input, s).map(u64::from))
+        .collect()
+}
+
+/// How many stones the engraved ones turn into after `blinks`
+fn blink(stones: &[u64], blinks: usize) -> u64 {
     let mut cache = HashMap::new();
+    stones.iter().map(|s| stone(*s, blinks, &mut cache)).sum()
+}
 
-    let p1 = parsed.iter().map(|s| stone(*s, 25, &mut cache)).sum();
-    let p2 = parsed.iter().map(|s| stone(*s, 75, &mut cache)).sum();
-    (p1, p2)
+fn part_1(stones: &[u64]) -> u64 {
+    blink(stones, 25)
 }
 
-pub fn day11() -> (String, String) {
-    let (sum_p1, sum_p2) = solve(INPUT);
-    (sum_p1.to_string(), sum_p2.to_string())
+fn part_2(stones: &[u64]) -> u64 {
+    blink(stones, 75)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Vec<u64>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: "125 17",
+        part1: Some("55312"),
+        part2: None,
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse_stones(input)
+    }
+
+    fn part1(stones: &Self::Parsed<'_>) -> String {
+        part_1(stones).to_string()
+    }
+
+    fn part2(stones: &Self::Parsed<'_>) -> String {
+        part_2(stones).to_string()
+    }
 }
 
 #[cfg(test)]
@@ -99,6 +127,6 @@ mod test {
     #[test]
     fn test_part_1() {
         init();
-        assert_eq!(solve("125 17").0, 55312);
+        assert_eq!(part_1(&parse_stones("125 17").unwrap()), 55312);
     }
 }
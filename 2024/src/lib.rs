@@ -0,0 +1,20 @@
+// `pub mod dayNN;` for every `src/dayNN.rs`, `SOLUTIONS` and what the runner uses of a year,
+// see `aoc_common::build`
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// The result of a `nom` parser of the whole `input`, or where it stopped: on the line it could
+/// not parse, or on what is left after what it parsed
+fn finish<'a, T>(
+    input: &'a str,
+    result: nom::IResult<&'a str, T>,
+    what: &str,
+) -> Result<T, aoc_common::ParseError> {
+    let rest = match result {
+        Ok((rest, parsed)) if rest.trim().is_empty() => return Ok(parsed),
+        Ok((rest, _)) => rest,
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => e.input,
+        Err(nom::Err::Incomplete(_)) => "",
+    };
+    let reason = format!("expected {what}");
+    Err(aoc_common::ParseError::at(input, rest.trim_start(), reason))
+}
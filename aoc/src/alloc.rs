@@ -0,0 +1,121 @@
+//! Heap allocations counting, the counting allocator is only installed by the `count_allocs`
+//! feature so that the other builds are not slowed down
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Whether the runner counts allocations
+pub const ENABLED: bool = cfg!(feature = "count_allocs");
+
+/// Allocations made by the current thread since the last [`reset`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included
+    pub count: u64,
+    /// Bytes allocated, a reallocation counting its new size
+    pub bytes: u64,
+    /// Peak bytes allocated and not freed yet
+    pub peak: u64,
+}
+
+thread_local! {
+    // counted per thread as each day runs on its own one, even with `--jobs`
+    static COUNT: Cell<u64> = const { Cell::new(0) };
+    static BYTES: Cell<u64> = const { Cell::new(0) };
+    // signed as a thread may free what another allocated
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+pub fn reset() {
+    COUNT.set(0);
+    BYTES.set(0);
+    LIVE.set(0);
+    PEAK.set(0);
+}
+
+pub fn stats() -> AllocStats {
+    AllocStats {
+        count: COUNT.get(),
+        bytes: BYTES.get(),
+        peak: PEAK.get().max(0) as u64,
+    }
+}
+
+#[cfg_attr(not(feature = "count_allocs"), allow(dead_code))]
+fn record(new_allocs: u64, allocated: usize, freed: usize) {
+    // the thread locals may be gone while the thread exits
+    let _ = COUNT.try_with(|c| c.set(c.get() + new_allocs));
+    let _ = BYTES.try_with(|b| b.set(b.get() + allocated as u64));
+    let _ = LIVE.try_with(|live| {
+        live.set(live.get() + allocated as i64 - freed as i64);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+    });
+}
+
+/// The system allocator, counting what each thread allocates
+#[cfg_attr(not(feature = "count_allocs"), allow(dead_code))]
+pub struct Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(1, layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(1, layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, 0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record(1, new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting() {
+        // on a new thread, where the installed allocator (if any) counts nothing else
+        std::thread::spawn(|| {
+            reset();
+            let small = Layout::from_size_align(16, 8).unwrap();
+            let big = Layout::from_size_align(1024, 8).unwrap();
+            unsafe {
+                let a = Counting.alloc(small);
+                let b = Counting.alloc_zeroed(big);
+                Counting.dealloc(b, big);
+                let a = Counting.realloc(a, small, 64);
+                Counting.dealloc(a, Layout::from_size_align(64, 8).unwrap());
+            }
+            assert_eq!(
+                stats(),
+                AllocStats {
+                    count: 3,
+                    bytes: 16 + 1024 + 64,
+                    peak: 1024 + 16,
+                }
+            );
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A day's known good answers, as recorded in a year's `answers.toml`
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Loads an `answers.toml` file made of `[dayNN]` tables, a missing file means no known answers
+pub fn load(path: &str) -> Result<HashMap<usize, Answers>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(format!("cannot read {path}: {e}")),
+    };
+    parse(&content).map_err(|e| format!("invalid answers file {path}: {e}"))
+}
+
+fn parse(content: &str) -> Result<HashMap<usize, Answers>, String> {
+    let tables: HashMap<String, Answers> = toml::from_str(content).map_err(|e| e.to_string())?;
+    tables
+        .into_iter()
+        .map(|(key, answers)| {
+            key.strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .map(|day| (day, answers))
+                .ok_or_else(|| format!("`{key}` is not a `dayNN` table"))
+        })
+        .collect()
+}
+
+/// Outcome of comparing an answer against the recorded one
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Check {
+    Unknown,
+    Match,
+    Mismatch,
+}
+
+impl Check {
+    pub fn new(expected: Option<&str>, answer: &str) -> Self {
+        match expected {
+            None => Check::Unknown,
+            Some(e) if e == answer => Check::Match,
+            Some(_) => Check::Mismatch,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            parse("[day01]\npart1 = \"42\"\npart2 = \"abc\"\n\n[day12]\npart1 = \"7\"\n").unwrap();
+        assert_eq!(answers[&1].part1.as_deref(), Some("42"));
+        assert_eq!(answers[&1].part2.as_deref(), Some("abc"));
+        assert_eq!(answers[&12].part1.as_deref(), Some("7"));
+        assert_eq!(answers[&12].part2, None);
+        assert!(parse("[first]\npart1 = \"1\"\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Check::new(None, "1"), Check::Unknown);
+        assert_eq!(Check::new(Some("1"), "1"), Check::Match);
+        assert_eq!(Check::new(Some("1"), "2"), Check::Mismatch);
+    }
+}
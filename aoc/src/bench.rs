@@ -0,0 +1,129 @@
+use std::time::{Duration, Instant};
+
+/// Summary of the durations (in seconds) of a benchmark's runs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        assert!(!samples.is_empty(), "no samples to compute statistics on");
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let n = sorted.len();
+        let median = match n % 2 {
+            0 => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.,
+            _ => sorted[n / 2],
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        // sample standard deviation, 0 when there is a single run
+        let stddev = match n {
+            1 => 0.,
+            _ => (sorted.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt(),
+        };
+        Self {
+            runs: n,
+            min: sorted[0],
+            median,
+            mean,
+            stddev,
+        }
+    }
+}
+
+/// How many times to run a benchmark
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Untimed runs before the measurements, to warm up caches and the allocator
+    pub warmup: usize,
+    /// The measured runs stop once they took this long, unless `min_runs` is not reached
+    pub budget: Duration,
+    pub min_runs: usize,
+    pub max_runs: usize,
+}
+
+/// Number of runs fitting in the budget, from the duration of a single run
+fn runs_for_budget(config: &Config, estimate: Duration) -> usize {
+    let runs = match estimate.as_secs_f64() {
+        e if e > 0. => (config.budget.as_secs_f64() / e) as usize,
+        _ => config.max_runs,
+    };
+    runs.clamp(config.min_runs, config.max_runs)
+}
+
+/// Runs `f` `config.warmup` times, then measures it with a monotonic clock as many times as
+/// fits in the time budget
+pub fn measure<F: FnMut()>(config: &Config, mut f: F) -> Stats {
+    let t0 = Instant::now();
+    (0..config.warmup).for_each(|_| f());
+    let estimate = match config.warmup {
+        0 => {
+            let t0 = Instant::now();
+            f();
+            t0.elapsed()
+        }
+        n => t0.elapsed() / n as u32,
+    };
+
+    let samples: Vec<f64> = (0..runs_for_budget(config, estimate))
+        .map(|_| {
+            let t0 = Instant::now();
+            f();
+            t0.elapsed().as_secs_f64()
+        })
+        .collect();
+    Stats::from_samples(&samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[4., 1., 3., 2.]);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, 1.);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 2.5);
+        assert!((stats.stddev - 1.2909944).abs() < 1e-6);
+
+        let stats = Stats::from_samples(&[5., 1., 3.]);
+        assert_eq!(stats.median, 3.);
+        assert_eq!(Stats::from_samples(&[1.]).stddev, 0.);
+    }
+
+    #[test]
+    fn test_runs_for_budget() {
+        let config = Config {
+            warmup: 0,
+            budget: Duration::from_secs(1),
+            min_runs: 5,
+            max_runs: 1000,
+        };
+        assert_eq!(runs_for_budget(&config, Duration::from_millis(10)), 100);
+        assert_eq!(runs_for_budget(&config, Duration::from_secs(2)), 5);
+        assert_eq!(runs_for_budget(&config, Duration::from_nanos(1)), 1000);
+        assert_eq!(runs_for_budget(&config, Duration::ZERO), 1000);
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let config = Config {
+            warmup: 2,
+            budget: Duration::ZERO,
+            min_runs: 3,
+            max_runs: 10,
+        };
+        let stats = measure(&config, || calls += 1);
+        assert_eq!(stats.runs, 3);
+        assert_eq!(calls, 5);
+    }
+}
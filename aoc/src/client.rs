@@ -0,0 +1,226 @@
+//! Talks to the Advent of Code website, with the session cookie of a logged in browser
+
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable holding the value of the `session` cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding [`DEFAULT_BASE_URL`]
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Environment variable holding a contact (like an email) for the User-Agent
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+/// The User-Agent of the requests. The site's maintainer asks automated tools to name a contact,
+/// so that their traffic can be traced back.
+fn user_agent(contact: Option<&str>) -> String {
+    let agent = concat!("aoc/", env!("CARGO_PKG_VERSION"));
+    match contact.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(contact) => format!("{agent} by {contact}"),
+        None => agent.to_string(),
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, contact: Option<&str>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent(contact))
+                .build(),
+        }
+    }
+
+    /// A client for `base_url`, or the URL in [`BASE_URL_VAR`], with the session in
+    /// [`SESSION_VAR`] and the contact in [`CONTACT_VAR`]
+    pub fn from_env(base_url: Option<&str>) -> Result<Self, String> {
+        let session = std::env::var(SESSION_VAR).map_err(|_| {
+            format!("{SESSION_VAR} must hold the `session` cookie of adventofcode.com")
+        })?;
+        let base_url = match base_url {
+            Some(url) => url.to_string(),
+            None => std::env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string()),
+        };
+        let contact = std::env::var(CONTACT_VAR).ok();
+        Ok(Self::new(&base_url, &session, contact.as_deref()))
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, String> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(404, _) => format!("{url} not found, is the puzzle unlocked?"),
+                ureq::Error::Status(400, _) => format!("{url} refused the session, is it expired?"),
+                e => format!("GET {url}: {e}"),
+            })?;
+        response
+            .into_string()
+            .map_err(|e| format!("GET {url}: {e}"))
+    }
+
+    /// Posts an url encoded form
+    pub fn post(&self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        let url = format!("{}{path}", self.base_url);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form)
+            .map_err(|e| format!("POST {url}: {e}"))?;
+        response
+            .into_string()
+            .map_err(|e| format!("POST {url}: {e}"))
+    }
+
+    pub fn input(&self, year: u16, day: usize) -> Result<String, String> {
+        self.get(&format!("/{year}/day/{day}/input"))
+    }
+}
+
+/// Whether [`fetch`] had to download the input
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input of a day is in `input_dir`, downloading it only if it is not there yet
+/// (or is the empty placeholder of `aoc new`). The client is only created when needed, so that
+/// cached inputs do not need a session.
+pub fn fetch<F>(input_dir: &str, year: u16, day: usize, client: F) -> Result<Fetched, String>
+where
+    F: FnOnce() -> Result<Client, String>,
+{
+    let path = Path::new(input_dir).join(format!("day{day:02}.txt"));
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client()?.input(year, day)?;
+    std::fs::create_dir_all(input_dir).map_err(|e| format!("cannot create {input_dir}: {e}"))?;
+    // written aside then renamed, so that an interrupted write never leaves a partial input that
+    // would be taken for a cached one
+    let partial = path.with_extension("txt.part");
+    std::fs::write(&partial, input)
+        .and_then(|()| std::fs::rename(&partial, &path))
+        .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// A stand-in for the website on a random local port, answering requests with `respond`
+    pub struct StubServer {
+        pub url: String,
+        pub requests: Arc<AtomicUsize>,
+    }
+
+    impl StubServer {
+        pub fn start<F>(respond: F) -> Self
+        where
+            F: Fn(&mut tiny_http::Request) -> (u16, String) + Send + 'static,
+        {
+            let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+            let requests = Arc::new(AtomicUsize::new(0));
+            let counter = requests.clone();
+            std::thread::spawn(move || {
+                for mut request in server.incoming_requests() {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    let (status, body) = respond(&mut request);
+                    let response = tiny_http::Response::from_string(body).with_status_code(status);
+                    let _ = request.respond(response);
+                }
+            });
+            Self { url, requests }
+        }
+    }
+
+    /// A new empty directory
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn cookie(request: &tiny_http::Request) -> Option<String> {
+        request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Cookie"))
+            .map(|h| h.value.to_string())
+    }
+
+    #[test]
+    fn test_fetch() {
+        let server = StubServer::start(|request| match (request.url(), cookie(request)) {
+            (_, Some(c)) if c != "session=abc" => (400, "bad session".into()),
+            ("/2023/day/10/input", _) => (200, "7-F7-\n.FJ|7\n".into()),
+            _ => (404, "not found".into()),
+        });
+        let dir = temp_dir("fetch");
+        let input_dir = dir.join("input");
+        let input_dir = input_dir.to_str().unwrap();
+        let client = || Ok(Client::new(&server.url, "abc\n", None));
+
+        let path = input_dir.to_string() + "/day10.txt";
+        assert_eq!(
+            fetch(input_dir, 2023, 10, client),
+            Ok(Fetched::Downloaded(path.clone().into()))
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "7-F7-\n.FJ|7\n");
+        assert!(!Path::new(input_dir).join("day10.txt.part").exists());
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+
+        // never downloaded twice, even without a session
+        let no_client = || Err("no session".to_string());
+        assert_eq!(
+            fetch(input_dir, 2023, 10, no_client),
+            Ok(Fetched::Cached(path.into()))
+        );
+        assert_eq!(server.requests.load(Ordering::SeqCst), 1);
+
+        // placeholders are replaced
+        std::fs::write(input_dir.to_string() + "/day10.txt", "").unwrap();
+        assert!(matches!(
+            fetch(input_dir, 2023, 10, client),
+            Ok(Fetched::Downloaded(_))
+        ));
+        assert_eq!(server.requests.load(Ordering::SeqCst), 2);
+
+        // nothing is written on errors
+        assert!(fetch(input_dir, 2023, 11, client).is_err());
+        assert!(!Path::new(input_dir).join("day11.txt").exists());
+        let bad_session = || Ok(Client::new(&server.url, "xyz", None));
+        assert!(fetch(input_dir, 2023, 10, bad_session).is_ok());
+        assert!(fetch(input_dir, 2023, 12, bad_session)
+            .unwrap_err()
+            .contains("session"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(user_agent(None), format!("aoc/{version}"));
+        assert_eq!(user_agent(Some(" ")), format!("aoc/{version}"));
+        assert_eq!(
+            user_agent(Some("me@example.com\n")),
+            format!("aoc/{version} by me@example.com")
+        );
+    }
+}
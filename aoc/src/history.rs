@@ -0,0 +1,243 @@
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Where `aoc run` records its timings, kept out of git
+pub const HISTORY_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/history.toml");
+
+/// Average durations (in seconds) of a day's phases
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DayTimings {
+    pub year: u16,
+    pub day: usize,
+    pub parse: f64,
+    pub part1: f64,
+    pub part2: f64,
+}
+
+impl DayTimings {
+    pub fn total(&self) -> f64 {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+/// The timings of one `aoc run`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Run {
+    pub commit: String,
+    pub date: String,
+    /// Name given with `--name`, to compare against this run later on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub iterations: usize,
+    pub days: Vec<DayTimings>,
+}
+
+impl Run {
+    /// A new run of the current commit, made now
+    pub fn new(name: Option<String>, iterations: usize) -> Self {
+        Self {
+            commit: git_commit(),
+            date: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            name,
+            iterations,
+            days: Vec::new(),
+        }
+    }
+
+    /// How to refer to this run in a comparison
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("`{name}` ({} {})", self.commit, self.date),
+            None => format!("{} {}", self.commit, self.date),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct History {
+    #[serde(default)]
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Loads the history, a missing file being an empty history
+    pub fn load(path: &str) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                toml::from_str(&content).map_err(|e| format!("invalid history file {path}: {e}"))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("cannot read {path}: {e}")),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content).map_err(|e| format!("cannot write {path}: {e}"))
+    }
+
+    /// The last run and the one to compare it to: the last run named `baseline`, or the
+    /// previous run
+    pub fn latest_and_baseline(&self, baseline: Option<&str>) -> Result<(&Run, &Run), String> {
+        let (latest, previous) = self
+            .runs
+            .split_last()
+            .ok_or("no recorded run, see `aoc run`")?;
+        let baseline = match baseline {
+            Some(name) => previous
+                .iter()
+                .rfind(|r| r.name.as_deref() == Some(name))
+                .ok_or_else(|| format!("no run named `{name}` before the last one"))?,
+            None => previous
+                .last()
+                .ok_or("a single recorded run, nothing to compare it to")?,
+        };
+        Ok((latest, baseline))
+    }
+}
+
+/// Short hash of the checked out commit, suffixed by `-dirty` when there are uncommitted changes
+fn git_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(commit) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(status) if !status.is_empty() => format!("{commit}-dirty"),
+            _ => commit,
+        },
+        None => "unknown".to_string(),
+    }
+}
+
+/// Timing of a day in the baseline and the latest run
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub year: u16,
+    pub day: usize,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    /// Relative change of the duration, in percents (negative is a speed-up). `None` if the
+    /// baseline took no measurable time, the change has no relative size then.
+    pub fn percent(&self) -> Option<f64> {
+        if self.before == self.after {
+            Some(0.)
+        } else if self.before == 0. {
+            None
+        } else {
+            Some((self.after - self.before) / self.before * 100.)
+        }
+    }
+}
+
+/// Changes of the days present in both runs, in the order of the latest run
+pub fn compare(baseline: &Run, latest: &Run) -> Vec<Change> {
+    latest
+        .days
+        .iter()
+        .filter_map(|after| {
+            let before = baseline
+                .days
+                .iter()
+                .find(|b| b.year == after.year && b.day == after.day)?;
+            Some(Change {
+                year: after.year,
+                day: after.day,
+                before: before.total(),
+                after: after.total(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(name: Option<&str>, days: &[(u16, usize, f64)]) -> Run {
+        Run {
+            commit: "abc1234".to_string(),
+            date: "2024-12-01 06:00:00".to_string(),
+            name: name.map(str::to_string),
+            iterations: 1,
+            days: days
+                .iter()
+                .map(|&(year, day, t)| DayTimings {
+                    year,
+                    day,
+                    parse: 0.,
+                    part1: t,
+                    part2: t,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let history = History {
+            runs: vec![
+                run(Some("base"), &[(2020, 15, 0.5)]),
+                run(None, &[(2020, 15, 0.25), (2020, 23, 0.125)]),
+            ],
+        };
+        let content = toml::to_string(&history).unwrap();
+        assert_eq!(toml::from_str::<History>(&content).unwrap(), history);
+    }
+
+    #[test]
+    fn test_latest_and_baseline() {
+        let mut history = History::default();
+        assert!(history.latest_and_baseline(None).is_err());
+        history.runs.push(run(Some("base"), &[(2020, 15, 0.5)]));
+        assert!(history.latest_and_baseline(None).is_err());
+        assert!(history.latest_and_baseline(Some("base")).is_err());
+        history.runs.push(run(None, &[(2020, 15, 0.4)]));
+        history.runs.push(run(None, &[(2020, 15, 0.3)]));
+
+        let (latest, baseline) = history.latest_and_baseline(None).unwrap();
+        assert_eq!((latest, baseline), (&history.runs[2], &history.runs[1]));
+        let (_, baseline) = history.latest_and_baseline(Some("base")).unwrap();
+        assert_eq!(baseline, &history.runs[0]);
+        assert!(history.latest_and_baseline(Some("other")).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = run(None, &[(2020, 15, 0.5), (2020, 22, 0.1)]);
+        let latest = run(None, &[(2020, 15, 0.25), (2020, 23, 0.125)]);
+        let changes = compare(&baseline, &latest);
+        assert_eq!(
+            changes,
+            [Change {
+                year: 2020,
+                day: 15,
+                before: 1.,
+                after: 0.5
+            }]
+        );
+        assert_eq!(changes[0].percent(), Some(-50.));
+    }
+
+    #[test]
+    fn test_percent_from_zero() {
+        let change = |before, after| Change {
+            year: 2020,
+            day: 15,
+            before,
+            after,
+        };
+        assert_eq!(change(0., 0.).percent(), Some(0.));
+        assert_eq!(change(0., 0.5).percent(), None);
+        assert_eq!(change(0.5, 0.).percent(), Some(-100.));
+    }
+}
@@ -0,0 +1,967 @@
+mod alloc;
+mod answers;
+mod bench;
+mod client;
+mod history;
+mod output;
+mod pool;
+mod scaffold;
+mod solve;
+mod submit;
+mod watch;
+
+use answers::Check;
+use clap::{Parser, Subcommand};
+use colored::*;
+use output::{Format, Record, Status};
+use solve::Failure;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+type Day = &'static dyn aoc_common::Day;
+
+#[cfg(feature = "count_allocs")]
+#[global_allocator]
+static GLOBAL: alloc::Counting = alloc::Counting;
+
+#[derive(Clone, Copy)]
+struct Year {
+    year: u16,
+    input_dir: &'static str,
+    answers_file: &'static str,
+    /// `solutions[day - 1]` solves `day`, if it is implemented
+    solutions: &'static [Option<Day>],
+}
+
+impl Year {
+    fn solution(&self, day: usize) -> Result<Day, String> {
+        self.solutions
+            .get(day - 1)
+            .copied()
+            .flatten()
+            .ok_or_else(|| format!("Day {day} of {} is not implemented", self.year))
+    }
+}
+
+macro_rules! year {
+    ($year:literal, $krate:ident) => {
+        Year {
+            year: $year,
+            input_dir: $krate::INPUT_DIR,
+            answers_file: $krate::ANSWERS_FILE,
+            solutions: &$krate::SOLUTIONS,
+        }
+    };
+}
+
+const YEARS: [Year; 5] = [
+    year!(2019, aoc2019),
+    year!(2020, aoc2020),
+    year!(2021, aoc2021),
+    year!(2023, aoc2023),
+    year!(2024, aoc2024),
+];
+
+fn fmt_time(t: f64) -> String {
+    match t {
+        t if t < 1e-3 => format!("{:.2}µs", t / 1e-6),
+        t if t < 1. => format!("{:.2}ms", t / 1e-3),
+        t => format!("{:.2}s", t),
+    }
+}
+
+fn fmt_bytes(b: u64) -> String {
+    match b {
+        b if b < 1 << 10 => format!("{b}B"),
+        b if b < 1 << 20 => format!("{:.2}KiB", b as f64 / (1u64 << 10) as f64),
+        b if b < 1 << 30 => format!("{:.2}MiB", b as f64 / (1u64 << 20) as f64),
+        b => format!("{:.2}GiB", b as f64 / (1u64 << 30) as f64),
+    }
+}
+
+fn clap_parse_num_range<T>(s: &str, min: T, max: T) -> Result<T, String>
+where
+    T: std::str::FromStr + std::cmp::PartialOrd + std::fmt::Display,
+    <T as std::str::FromStr>::Err: std::fmt::Display,
+{
+    let val = s.parse::<T>().map_err(|e| format!("{e}"))?;
+    if val < min || val > max {
+        Err(format!("{val} is not in range [{min}, {max}]"))
+    } else {
+        Ok(val)
+    }
+}
+
+fn clap_parse_iterations(s: &str) -> Result<usize, String> {
+    clap_parse_num_range(s, 1, 1000)
+}
+
+fn clap_parse_runs(s: &str) -> Result<usize, String> {
+    clap_parse_num_range(s, 1, 100_000)
+}
+
+fn clap_parse_jobs(s: &str) -> Result<usize, String> {
+    clap_parse_num_range(s, 1, 256)
+}
+
+fn clap_parse_timeout(s: &str) -> Result<Duration, String> {
+    let secs = s.parse::<f64>().map_err(|e| format!("{e}"))?;
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|t| !t.is_zero())
+        .ok_or_else(|| format!("{secs} is not a positive number of seconds"))
+}
+
+fn clap_parse_days(s: &str) -> Result<usize, String> {
+    clap_parse_num_range(s, 1, 25)
+}
+
+fn clap_parse_size(s: &str) -> Result<usize, String> {
+    clap_parse_num_range(s, 1, 1_000_000)
+}
+
+fn clap_parse_part(s: &str) -> Result<u8, String> {
+    clap_parse_num_range(s, 1, 2)
+}
+
+fn clap_parse_year(s: &str) -> Result<u16, String> {
+    let year = s.parse::<u16>().map_err(|e| format!("{e}"))?;
+    match YEARS.iter().any(|y| y.year == year) {
+        true => Ok(year),
+        false => Err(format!("no solutions for {year}")),
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Advent of Code solutions runner", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run solutions and print a markdown table of the results
+    Run(RunArgs),
+    /// Benchmark solutions and print a markdown table of their timing statistics
+    Bench(BenchArgs),
+    /// Compare the timings of the last recorded run against a previous one
+    Compare(CompareArgs),
+    /// Download puzzle inputs into the year's `input/` directory, unless they are already there
+    Fetch(FetchArgs),
+    /// Solve a day on its input and submit the answer of one part to the website
+    Submit(SubmitArgs),
+    /// Generate the module of a new day, with an empty input (the day is registered by the build
+    /// script of its year)
+    New(NewArgs),
+    /// Re-run a day, on its examples then its input, whenever its source or input changes
+    Watch(WatchArgs),
+    /// Print a random but valid input of a day, to stress test its solution
+    Gen(GenArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct Selection {
+    /// Which years to run (defaults to the most recent one)
+    #[arg(short, long, num_args = 1.., value_parser = clap_parse_year)]
+    year: Vec<u16>,
+
+    /// Which solutions to run (defaults to every solution of the year)
+    #[arg(short, long, num_args = 1.., value_parser = clap_parse_days)]
+    days: Vec<usize>,
+}
+
+impl Selection {
+    fn years(&self) -> Vec<Year> {
+        match self.year.is_empty() {
+            true => vec![*YEARS.last().unwrap()],
+            false => YEARS
+                .iter()
+                .filter(|y| self.year.contains(&y.year))
+                .copied()
+                .collect(),
+        }
+    }
+
+    /// The selected days of the selected years, with their solution if they are implemented.
+    /// Fails only if none of them is.
+    fn days(&self) -> Result<Vec<(Year, usize, Option<Day>)>, String> {
+        let mut days = Vec::new();
+        for year in self.years() {
+            match self.days.is_empty() {
+                true => days.extend(
+                    (1..)
+                        .zip(year.solutions)
+                        .filter_map(|(day, f)| Some((year, day, Some((*f)?)))),
+                ),
+                false => days.extend(
+                    self.days
+                        .iter()
+                        .map(|&day| (year, day, year.solution(day).ok())),
+                ),
+            }
+        }
+        if days.iter().all(|(_, _, f)| f.is_none()) {
+            return Err("None of the selected days is implemented".into());
+        }
+        Ok(days)
+    }
+}
+
+#[derive(clap::Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Number of times to run each solution (for more accurate timing)
+    #[arg(short, long, default_value_t = 1, value_parser = clap_parse_iterations)]
+    iterations: usize,
+
+    /// Input file to use instead of `input/dayNN.txt`, `-` reads stdin (needs a single year and day)
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Compare the answers against the year's `answers.toml`, fail if any is wrong
+    #[arg(long)]
+    check: bool,
+
+    /// Give up on a day after this many seconds (a day that times out keeps running in the
+    /// background until the runner exits)
+    #[arg(long, value_parser = clap_parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Number of days to run at once, timings are then not recorded in the history as the days
+    /// compete for the CPU (use `bench` for accurate timings)
+    #[arg(short, long, default_value_t = 1, value_parser = clap_parse_jobs)]
+    jobs: usize,
+
+    /// How to print the results
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Run the days on the examples of their puzzle instead, and check their answers
+    #[arg(long, conflicts_with_all = ["input", "check", "format", "jobs", "name"])]
+    examples: bool,
+
+    /// Name of this run in the timings history, to compare against it later on
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Do not record the timings of this run in the history
+    #[arg(long, conflicts_with = "name")]
+    no_record: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Number of untimed runs of each solution before measuring it
+    #[arg(short, long, default_value_t = 3)]
+    warmup: usize,
+
+    /// Time to spend measuring each solution, in seconds
+    #[arg(short, long, default_value_t = 1.)]
+    budget: f64,
+
+    /// Minimum number of measured runs, even if it exceeds the budget
+    #[arg(long, default_value_t = 5, value_parser = clap_parse_runs)]
+    min_runs: usize,
+
+    /// Maximum number of measured runs
+    #[arg(long, default_value_t = 1000, value_parser = clap_parse_runs)]
+    max_runs: usize,
+}
+
+#[derive(clap::Args, Debug)]
+struct CompareArgs {
+    /// Name of the run to compare against (defaults to the run before the last one)
+    #[arg(short, long)]
+    baseline: Option<String>,
+
+    /// Changes of the duration beyond this percentage are flagged
+    #[arg(short, long, default_value_t = 10.)]
+    threshold: f64,
+}
+
+#[derive(clap::Args, Debug)]
+struct FetchArgs {
+    #[arg(short, long, value_parser = clap_parse_year)]
+    year: u16,
+
+    #[arg(short, long, required = true, num_args = 1.., value_parser = clap_parse_days)]
+    days: Vec<usize>,
+
+    /// Website to download from (defaults to $AOC_BASE_URL, or adventofcode.com)
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct SubmitArgs {
+    #[arg(short, long, value_parser = clap_parse_year)]
+    year: u16,
+
+    #[arg(short, long, value_parser = clap_parse_days)]
+    day: usize,
+
+    #[arg(short, long, value_parser = clap_parse_part)]
+    part: u8,
+
+    /// Website to submit to (defaults to $AOC_BASE_URL, or adventofcode.com)
+    #[arg(long)]
+    base_url: Option<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct NewArgs {
+    #[arg(short, long, value_parser = clap_parse_year)]
+    year: u16,
+
+    #[arg(short, long, value_parser = clap_parse_days)]
+    day: usize,
+}
+
+#[derive(clap::Args, Debug)]
+struct WatchArgs {
+    /// Defaults to the most recent year
+    #[arg(short, long, value_parser = clap_parse_year)]
+    year: Option<u16>,
+
+    #[arg(short, long, value_parser = clap_parse_days)]
+    day: usize,
+}
+
+#[derive(clap::Args, Debug)]
+struct GenArgs {
+    #[arg(short, long, value_parser = clap_parse_year)]
+    year: u16,
+
+    #[arg(short, long, value_parser = clap_parse_days)]
+    day: usize,
+
+    /// How large the input is, in what fits the day (lines, side of a grid, cards...)
+    #[arg(short, long, default_value_t = 100, value_parser = clap_parse_size)]
+    size: usize,
+
+    /// The same seed always gives the same input (defaults to a random seed, printed on stderr)
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+/// Reads the puzzle input, either from `path` (`-` being stdin) or from the year's input directory
+fn read_input(input_dir: &str, day: usize, path: Option<&Path>) -> Result<String, String> {
+    let path = match path {
+        Some(p) if p == Path::new("-") => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("cannot read input from stdin: {e}"))?;
+            return Ok(input);
+        }
+        Some(p) => p.to_owned(),
+        None => Path::new(input_dir).join(format!("day{day:02}.txt")),
+    };
+    std::fs::read_to_string(&path).map_err(|e| format!("cannot read input {}: {e}", path.display()))
+}
+
+/// A day to run, with what it needs
+struct Task {
+    year: u16,
+    day: usize,
+    /// `None` if the day is not implemented
+    f: Option<Day>,
+    /// `None` if it is missing
+    input: Option<String>,
+    expected: answers::Answers,
+}
+
+struct Outcome {
+    record: Record,
+    mismatches: Vec<String>,
+    failure: Option<String>,
+}
+
+fn run_day(task: Task, iterations: usize, timeout: Option<Duration>) -> Outcome {
+    let Task {
+        year,
+        day,
+        f,
+        input,
+        expected,
+    } = task;
+    let (f, input) = match (f, input) {
+        (Some(f), Some(input)) => (f, input),
+        (f, _) => {
+            let status = match f {
+                None => Status::NotImplemented,
+                Some(_) => Status::NoInput,
+            };
+            return Outcome {
+                record: Record::failed(year, day, status, iterations),
+                mismatches: Vec::new(),
+                failure: None,
+            };
+        }
+    };
+    let solve::Solved {
+        t_parse,
+        t_p1,
+        t_p2,
+        p1,
+        p2,
+        allocs,
+    } = match solve::solve(format!("{year} day {day}"), f, input, iterations, timeout) {
+        Ok(solved) => solved,
+        Err(failure) => {
+            let status = match failure {
+                Failure::Invalid(_) => Status::Invalid,
+                Failure::Panicked(_) => Status::Failed,
+                Failure::Timeout(_) => Status::Timeout,
+            };
+            return Outcome {
+                record: Record::failed(year, day, status, iterations),
+                mismatches: Vec::new(),
+                failure: Some(format!("{year} day {day}: {failure}")),
+            };
+        }
+    };
+
+    let check_p1 = Check::new(expected.part1.as_deref(), &p1);
+    let check_p2 = Check::new(expected.part2.as_deref(), &p2);
+    let mut mismatches = Vec::new();
+    for (part, check, answer, expected) in [
+        (1, check_p1, &p1, expected.part1),
+        (2, check_p2, &p2, expected.part2),
+    ] {
+        if check == Check::Mismatch {
+            mismatches.push(format!(
+                "{year} day {day} part {part}: got {answer}, expected {}",
+                expected.unwrap_or_default()
+            ));
+        }
+    }
+    Outcome {
+        record: Record::solved(
+            year,
+            day,
+            iterations,
+            (t_parse, t_p1, t_p2),
+            (p1, check_p1),
+            (p2, check_p2),
+            allocs,
+        ),
+        mismatches,
+        failure: None,
+    }
+}
+
+/// Runs the selected days on their examples, fails if any answer is wrong
+fn run_examples(args: RunArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let selected = args.selection.days()?;
+    println!("| YEAR | DAY | EXAMPLE |      PART 1     |      Part 2     |");
+    println!("| :--: | :-: | :-----: | :-------------: | :-------------: |");
+    let mut failures = Vec::new();
+    for (y, day, f) in selected {
+        let year = y.year;
+        let Some(f) = f else {
+            println!(
+                "| {:4} | {:3} | {:7} | {:15} | {:15} |",
+                year.to_string().cyan(),
+                day.to_string().yellow(),
+                "-",
+                "not implemented".red(),
+                ""
+            );
+            continue;
+        };
+        if f.examples().is_empty() {
+            println!(
+                "| {:4} | {:3} | {:7} | {:15} | {:15} |",
+                year.to_string().cyan(),
+                day.to_string().yellow(),
+                "-",
+                "no examples".yellow(),
+                ""
+            );
+        }
+        for (i, example) in f.examples().iter().enumerate() {
+            let name = format!("{year} day {day} example {}", i + 1);
+            let answers =
+                match solve::solve_example(name.clone(), f, *example, args.timeout) {
+                    Ok((p1, p2)) => [(example.part1, p1), (example.part2, p2)].map(
+                        |(expected, answer)| match (expected, answer) {
+                            (Some(expected), Some(answer)) => {
+                                let correct = answer == expected;
+                                if !correct {
+                                    failures
+                                        .push(format!("{name}: got {answer}, expected {expected}"));
+                                }
+                                output::fmt_answer(&answer, Some(correct))
+                            }
+                            _ => "-".normal(),
+                        },
+                    ),
+                    Err(failure) => {
+                        let status = match failure {
+                            Failure::Invalid(_) => "INVALID INPUT",
+                            Failure::Panicked(_) => "FAILED",
+                            Failure::Timeout(_) => "TIMEOUT",
+                        };
+                        failures.push(format!("{name}: {failure}"));
+                        [status.red(), "".normal()]
+                    }
+                };
+            let [p1, p2] = answers;
+            println!(
+                "| {:4} | {:3} | {:7} | {:15} | {:15} |",
+                year.to_string().cyan(),
+                day.to_string().yellow(),
+                (i + 1).to_string(),
+                p1,
+                p2
+            );
+        }
+    }
+
+    if !failures.is_empty() {
+        println!();
+        for f in failures.iter() {
+            println!("{}", f.red());
+        }
+        return Err(format!("{} failed example(s)", failures.len()).into());
+    }
+    Ok(())
+}
+
+fn run(args: RunArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.examples {
+        return run_examples(args);
+    }
+    let years = args.selection.years();
+    if args.input.is_some() && (years.len() != 1 || args.selection.days.len() != 1) {
+        return Err("--input needs exactly one year and one day".into());
+    }
+
+    // timings on another input, or with days competing for the CPU, are not comparable
+    let record = !args.no_record && args.input.is_none() && args.jobs == 1;
+    if args.name.is_some() && !record {
+        return Err("--name needs a recorded run, without --input or --jobs".into());
+    }
+    let mut known_answers = HashMap::new();
+    if args.check {
+        for y in &years {
+            known_answers.insert(y.year, answers::load(y.answers_file)?);
+        }
+    }
+    let mut tasks = Vec::new();
+    for (y, day, f) in args.selection.days()? {
+        let input = match f {
+            None => None,
+            Some(_) => match read_input(y.input_dir, day, args.input.as_deref()) {
+                Ok(input) => Some(input),
+                Err(e) if args.input.is_some() => return Err(e.into()),
+                Err(e) => {
+                    log::warn!("{e}");
+                    None
+                }
+            },
+        };
+        let expected = known_answers.get(&y.year).and_then(|a| a.get(&day));
+        tasks.push(Task {
+            year: y.year,
+            day,
+            f,
+            input,
+            expected: expected.cloned().unwrap_or_default(),
+        });
+    }
+
+    let markdown = args.format == Format::Markdown;
+    if markdown {
+        println!("Running the solutions {} times.", args.iterations);
+        println!("{}", output::markdown_header());
+    }
+    let mut timings = history::Run::new(args.name.clone(), args.iterations);
+    let mut total_time = 0.;
+    let mut mismatches = Vec::new();
+    let mut failures = Vec::new();
+    let mut records = Vec::new();
+    pool::map_in_order(
+        args.jobs,
+        tasks,
+        |task| run_day(task, args.iterations, args.timeout),
+        |outcome| {
+            let record = outcome.record;
+            mismatches.extend(outcome.mismatches);
+            failures.extend(outcome.failure);
+            if let (Some(parse), Some(part1), Some(part2)) =
+                (record.parse_time, record.part1_time, record.part2_time)
+            {
+                total_time += parse + part1 + part2;
+                timings.days.push(history::DayTimings {
+                    year: record.year,
+                    day: record.day,
+                    parse,
+                    part1,
+                    part2,
+                });
+            }
+            match args.format {
+                Format::Markdown => println!("{}", output::markdown_row(&record)),
+                _ => records.push(record),
+            }
+        },
+    );
+    match args.format {
+        Format::Markdown => println!("\nTOTAL TIME: {}", fmt_time(total_time)),
+        Format::Json => println!("{}", output::json(&records)),
+        Format::Csv => print!("{}", output::csv(&records)?),
+    }
+
+    if record && !timings.days.is_empty() {
+        let mut history = history::History::load(history::HISTORY_FILE)?;
+        history.runs.push(timings);
+        history.save(history::HISTORY_FILE)?;
+    }
+
+    if !mismatches.is_empty() || !failures.is_empty() {
+        if markdown {
+            println!();
+        }
+        for m in mismatches.iter().chain(failures.iter()) {
+            // keep the machine readable output parsable
+            match markdown {
+                true => println!("{}", m.red()),
+                false => eprintln!("{}", m.red()),
+            }
+        }
+        return Err(format!(
+            "{} wrong answer(s), {} failed day(s)",
+            mismatches.len(),
+            failures.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn bench(args: BenchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if !args.budget.is_finite() || args.budget < 0. {
+        return Err(format!("invalid time budget {}", args.budget).into());
+    }
+    if args.min_runs > args.max_runs {
+        return Err("--min-runs cannot be greater than --max-runs".into());
+    }
+    let config = bench::Config {
+        warmup: args.warmup,
+        budget: Duration::from_secs_f64(args.budget),
+        min_runs: args.min_runs,
+        max_runs: args.max_runs,
+    };
+
+    println!(
+        "Benchmarking each solution for {}s after {} warm-up runs.",
+        args.budget, args.warmup
+    );
+    println!("| YEAR | DAY |  Runs  |   Min    |  Median  |   Mean   |  Stddev  |");
+    println!("| :--: | :-: | -----: | :------: | :------: | :------: | :------: |");
+    for (y, i, f) in args.selection.days()? {
+        let Some(f) = f else {
+            log::warn!("Day {i} of {} is not implemented", y.year);
+            continue;
+        };
+        let input = match read_input(y.input_dir, i, None) {
+            Ok(input) => input,
+            Err(e) => {
+                log::warn!("{e}");
+                continue;
+            }
+        };
+        if let Err(e) = f.parse(&input) {
+            log::warn!("{} day {i}: {}", y.year, Failure::Invalid(e));
+            continue;
+        }
+        // a full solve, parsing included
+        let stats = bench::measure(&config, || {
+            let _ = std::hint::black_box(f.answers(&input));
+        });
+        println!(
+            "| {:4} | {:3} | {:6} | {:8} | {:8} | {:8} | {:8} |",
+            y.year.to_string().cyan(),
+            i.to_string().yellow(),
+            stats.runs,
+            fmt_time(stats.min).green(),
+            fmt_time(stats.median).green(),
+            fmt_time(stats.mean).green(),
+            fmt_time(stats.stddev),
+        );
+    }
+    Ok(())
+}
+
+fn compare(args: CompareArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let history = history::History::load(history::HISTORY_FILE)?;
+    let (latest, baseline) = history.latest_and_baseline(args.baseline.as_deref())?;
+
+    println!("Comparing {} against {}.", latest.label(), baseline.label());
+    println!("| YEAR | DAY |  Before  |  After   |  Change  |");
+    println!("| :--: | :-: | :------: | :------: | -------: |");
+    let (mut faster, mut slower) = (0, 0);
+    for change in history::compare(baseline, latest) {
+        let fmt_percent = match change.percent() {
+            Some(p) if p > args.threshold => {
+                slower += 1;
+                format!("{p:+.1}%").red()
+            }
+            Some(p) if p < -args.threshold => {
+                faster += 1;
+                format!("{p:+.1}%").green()
+            }
+            Some(p) => format!("{p:+.1}%").normal(),
+            // the baseline was too fast to be measured
+            None => "n/a".normal(),
+        };
+        println!(
+            "| {:4} | {:3} | {:8} | {:8} | {:>8} |",
+            change.year.to_string().cyan(),
+            change.day.to_string().yellow(),
+            fmt_time(change.before),
+            fmt_time(change.after),
+            fmt_percent
+        );
+    }
+    println!(
+        "\n{} faster, {} slower beyond ±{}%",
+        faster.to_string().green(),
+        slower.to_string().red(),
+        args.threshold
+    );
+    Ok(())
+}
+
+fn fetch(args: FetchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let year = YEARS.iter().find(|y| y.year == args.year).unwrap();
+    for day in args.days {
+        let fetched = client::fetch(year.input_dir, year.year, day, || {
+            client::Client::from_env(args.base_url.as_deref())
+        });
+        match fetched? {
+            client::Fetched::Cached(path) => {
+                println!("{} day {day}: already in {}", year.year, path.display())
+            }
+            client::Fetched::Downloaded(path) => {
+                println!("{} day {day}: downloaded to {}", year.year, path.display())
+            }
+        }
+    }
+    Ok(())
+}
+
+fn submit(args: SubmitArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let SubmitArgs {
+        year,
+        day,
+        part,
+        base_url,
+    } = args;
+    let y = YEARS.iter().find(|y| y.year == year).unwrap();
+    let f = y.solution(day)?;
+    let input = read_input(y.input_dir, day, None)?;
+    let name = format!("{year} day {day}");
+    let solved = solve::solve(name.clone(), f, input, 1, None)
+        .map_err(|failure| format!("{name}: {failure}"))?;
+    let answer = match part {
+        1 => solved.p1,
+        _ => solved.p2,
+    };
+    println!("{year} day {day} part {part}: submitting {}", answer.bold());
+
+    let mut log = submit::GuessLog::load(submit::GUESSES_FILE)?;
+    let submitted = submit::submit(
+        || client::Client::from_env(base_url.as_deref()),
+        &mut log,
+        year,
+        day,
+        part,
+        &answer,
+        chrono::Local::now().naive_local(),
+    );
+    // saved first, so that no guess goes unrecorded
+    log.save(submit::GUESSES_FILE)?;
+    let response = submitted?;
+    match response.verdict {
+        submit::Verdict::Correct => println!("{}", response.verdict.to_string().green()),
+        verdict => {
+            let wait = match response.wait {
+                Some(wait) => format!(", retry in {}s", wait.as_secs()),
+                None => String::new(),
+            };
+            return Err(format!("{verdict}{wait}").into());
+        }
+    }
+    Ok(())
+}
+
+fn new(args: NewArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let year = YEARS.iter().find(|y| y.year == args.year).unwrap();
+    let crate_dir = Path::new(year.input_dir).parent().unwrap();
+    for path in scaffold::scaffold(crate_dir, args.day)? {
+        println!("created {}", path.display());
+    }
+    Ok(())
+}
+
+/// Rebuilds the runner, then runs a day on its examples and on its input with the new build
+fn rerun(exe: &Path, year: u16, day: usize) -> Result<answers::Answers, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut build = std::process::Command::new(cargo);
+    build
+        .args(["build", "-p", "aoc"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"));
+    if !cfg!(debug_assertions) {
+        build.arg("--release");
+    }
+    if alloc::ENABLED {
+        build.args(["--features", "count_allocs"]);
+    }
+    let built = build
+        .status()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    if !built.success() {
+        return Err("the build failed".to_string());
+    }
+
+    let selection = [
+        "--year".into(),
+        year.to_string(),
+        "--days".into(),
+        day.to_string(),
+    ];
+    // wrong examples are reported by the run itself, the input is run anyway
+    std::process::Command::new(exe)
+        .arg("run")
+        .args(&selection)
+        .arg("--examples")
+        .status()
+        .map_err(|e| format!("cannot run {}: {e}", exe.display()))?;
+    let output = std::process::Command::new(exe)
+        .arg("run")
+        .args(&selection)
+        .args(["--format", "json", "--no-record"])
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|e| format!("cannot run {}: {e}", exe.display()))?;
+    watch::parse_answers(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn watch(args: WatchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let year = match args.year {
+        Some(year) => *YEARS.iter().find(|y| y.year == year).unwrap(),
+        None => *YEARS.last().unwrap(),
+    };
+    let day = args.day;
+    let crate_dir = Path::new(year.input_dir).parent().unwrap();
+    let mut watched = watch::Watched::new(vec![
+        crate_dir.join(format!("src/day{day:02}.rs")),
+        Path::new(year.input_dir).join(format!("day{day:02}.txt")),
+    ]);
+    // resolved before the first rebuild replaces it
+    let exe = std::env::current_exe()?;
+    for path in watched.paths() {
+        println!("watching {}", path.display());
+    }
+
+    let mut previous = None;
+    loop {
+        match rerun(&exe, year.year, day) {
+            Ok(answers) => {
+                println!();
+                for line in watch::diff(previous.as_ref(), &answers) {
+                    println!("{}", line.bold());
+                }
+                previous = Some(answers);
+            }
+            Err(e) => println!("{}", e.red()),
+        }
+        while !watched.changed() {
+            std::thread::sleep(Duration::from_millis(500));
+        }
+        println!("\n{}", "changed, re-running".cyan());
+    }
+}
+
+fn gen(args: GenArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let year = YEARS.iter().find(|y| y.year == args.year).unwrap();
+    let f = year.solution(args.day)?;
+    let seed = args.seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        let seed = now.map_or(0, |d| d.as_nanos() as u64);
+        eprintln!("generating with --seed {seed}");
+        seed
+    });
+    let input = f
+        .generate(args.size, seed)
+        .ok_or_else(|| format!("Day {} of {} has no input generator", args.day, args.year))?;
+    print!("{input}");
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+        Command::Compare(args) => compare(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Submit(args) => submit(args),
+        Command::New(args) => new(args),
+        Command::Watch(args) => watch(args),
+        Command::Gen(args) => gen(args),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solve::Failure;
+
+    #[test]
+    fn test_malformed_inputs() {
+        // the parts may assume a parsed input is well formed, so `parse` has to reject the
+        // inputs the parts cannot solve
+        let mut failed = Vec::new();
+        for year in &YEARS {
+            for (day, f) in (1..)
+                .zip(year.solutions)
+                .filter_map(|(d, f)| Some((d, (*f)?)))
+            {
+                let mut inputs = vec![String::new(), "\n".to_string()];
+                if let Some(example) = f.examples().first().map(|e| e.input) {
+                    let mut half = example.len() / 2;
+                    while !example.is_char_boundary(half) {
+                        half -= 1;
+                    }
+                    inputs.extend([
+                        example[..half].to_string(),
+                        example.replace('\n', "\r\n"),
+                        format!("{}\n\n", example.trim_end()),
+                    ]);
+                }
+                for input in inputs {
+                    let name = format!("{} day {day} {input:?}", year.year);
+                    let timeout = Some(Duration::from_secs(10));
+                    match solve::solve(name.clone(), f, input, 1, timeout) {
+                        Err(Failure::Panicked(_) | Failure::Timeout(_)) => failed.push(name),
+                        Ok(_) | Err(Failure::Invalid(_)) => (),
+                    }
+                }
+            }
+        }
+        assert!(failed.is_empty(), "failed on:\n{}", failed.join("\n"));
+    }
+}
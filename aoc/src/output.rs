@@ -0,0 +1,245 @@
+use crate::alloc::{self, AllocStats};
+use crate::answers::Check;
+use crate::{fmt_bytes, fmt_time};
+use colored::*;
+use serde::Serialize;
+
+/// How `aoc run` prints its results
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// The README's table, coloured on a terminal
+    #[default]
+    Markdown,
+    /// A single array of records
+    Json,
+    /// One record per line, after a header
+    Csv,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    NotImplemented,
+    NoInput,
+    /// The input could not be parsed
+    Invalid,
+    Failed,
+    Timeout,
+}
+
+fn correct(check: Check) -> Option<bool> {
+    match check {
+        Check::Unknown => None,
+        Check::Match => Some(true),
+        Check::Mismatch => Some(false),
+    }
+}
+
+/// The outcome of a day, durations are averages over `iterations` runs in seconds. Only days
+/// with the `Ok` status have answers and durations.
+#[derive(Serialize, Debug, Clone)]
+pub struct Record {
+    pub year: u16,
+    pub day: usize,
+    pub status: Status,
+    pub iterations: usize,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// Whether the answers match the known ones, when checked and known
+    pub part1_correct: Option<bool>,
+    pub part2_correct: Option<bool>,
+    pub parse_time: Option<f64>,
+    pub part1_time: Option<f64>,
+    pub part2_time: Option<f64>,
+    /// Allocations of a single run, with the `count_allocs` feature
+    pub allocs: Option<u64>,
+    pub alloc_bytes: Option<u64>,
+    pub peak_bytes: Option<u64>,
+}
+
+impl Record {
+    /// A day without answers
+    pub fn failed(year: u16, day: usize, status: Status, iterations: usize) -> Self {
+        Self {
+            year,
+            day,
+            status,
+            iterations,
+            part1: None,
+            part2: None,
+            part1_correct: None,
+            part2_correct: None,
+            parse_time: None,
+            part1_time: None,
+            part2_time: None,
+            allocs: None,
+            alloc_bytes: None,
+            peak_bytes: None,
+        }
+    }
+
+    pub fn solved(
+        year: u16,
+        day: usize,
+        iterations: usize,
+        (t_parse, t_p1, t_p2): (f64, f64, f64),
+        (p1, check_p1): (String, Check),
+        (p2, check_p2): (String, Check),
+        allocs: Option<AllocStats>,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            status: Status::Ok,
+            iterations,
+            part1: Some(p1),
+            part2: Some(p2),
+            part1_correct: correct(check_p1),
+            part2_correct: correct(check_p2),
+            parse_time: Some(t_parse),
+            part1_time: Some(t_p1),
+            part2_time: Some(t_p2),
+            allocs: allocs.map(|a| a.count),
+            alloc_bytes: allocs.map(|a| a.bytes),
+            peak_bytes: allocs.map(|a| a.peak),
+        }
+    }
+}
+
+pub fn markdown_header() -> &'static str {
+    match alloc::ENABLED {
+        false => "\
+| YEAR | DAY |  Parse   |  Time 1  |  Time 2  |      PART 1     |      Part 2     |
+| :--: | :-: | :------: | :------: | :------: | :-------------: | :-------------: |",
+        true => "\
+| YEAR | DAY |  Parse   |  Time 1  |  Time 2  |  Allocs  |   Bytes   |   Peak    |      PART 1     |      Part 2     |
+| :--: | :-: | :------: | :------: | :------: | -------: | --------: | --------: | :-------------: | :-------------: |",
+    }
+}
+
+pub fn fmt_answer(answer: &str, correct: Option<bool>) -> ColoredString {
+    match correct {
+        None => answer.normal(),
+        Some(true) => answer.green(),
+        Some(false) => answer.red(),
+    }
+}
+
+pub fn markdown_row(record: &Record) -> String {
+    let status = match record.status {
+        Status::Ok => None,
+        Status::NotImplemented => Some("not implemented"),
+        Status::NoInput => Some("no input"),
+        Status::Invalid => Some("INVALID INPUT"),
+        Status::Failed => Some("FAILED"),
+        Status::Timeout => Some("TIMEOUT"),
+    };
+    let time = |t: Option<f64>| match t {
+        Some(t) => fmt_time(t).green(),
+        None => "-".normal(),
+    };
+    let (p1, p2) = match status {
+        Some(status) => (status.red(), "".normal()),
+        None => (
+            fmt_answer(
+                record.part1.as_deref().unwrap_or_default(),
+                record.part1_correct,
+            ),
+            fmt_answer(
+                record.part2.as_deref().unwrap_or_default(),
+                record.part2_correct,
+            ),
+        ),
+    };
+    let allocs = match alloc::ENABLED {
+        false => String::new(),
+        true => {
+            let fmt = |n: Option<u64>, f: fn(u64) -> String| n.map_or("-".to_string(), f);
+            format!(
+                " {:>8} | {:>9} | {:>9} |",
+                fmt(record.allocs, |n| n.to_string()),
+                fmt(record.alloc_bytes, fmt_bytes),
+                fmt(record.peak_bytes, fmt_bytes)
+            )
+        }
+    };
+    format!(
+        "| {:4} | {:3} | {:8} | {:8} | {:8} |{allocs} {:15} | {:15} |",
+        record.year.to_string().cyan(),
+        record.day.to_string().yellow(),
+        time(record.parse_time),
+        time(record.part1_time),
+        time(record.part2_time),
+        p1,
+        p2
+    )
+}
+
+pub fn json(records: &[Record]) -> String {
+    serde_json::to_string_pretty(records).expect("records are serializable")
+}
+
+pub fn csv(records: &[Record]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for record in records {
+        writer.serialize(record).map_err(|e| e.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record::solved(
+                2020,
+                15,
+                2,
+                (0.5, 0.25, 0.125),
+                ("436".into(), Check::Match),
+                ("1,2\n3".into(), Check::Unknown),
+                None,
+            ),
+            Record::failed(2020, 16, Status::Timeout, 2),
+        ]
+    }
+
+    #[test]
+    #[cfg_attr(feature = "count_allocs", ignore = "the table has allocation columns")]
+    fn test_markdown() {
+        colored::control::set_override(false);
+        let rows: Vec<_> = records().iter().map(markdown_row).collect();
+        assert_eq!(
+            rows,
+            [
+                "| 2020 | 15  | 500.00ms | 250.00ms | 125.00ms | 436             | 1,2\n3           |",
+                "| 2020 | 16  | -        | -        | -        | TIMEOUT         |                 |",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value = serde_json::from_str(&json(&records())).unwrap();
+        assert_eq!(json[0]["part1"], "436");
+        assert_eq!(json[0]["part1_correct"], true);
+        assert_eq!(json[0]["part2_correct"], serde_json::Value::Null);
+        assert_eq!(json[0]["parse_time"], 0.5);
+        assert_eq!(json[1]["status"], "timeout");
+        assert_eq!(json[1]["iterations"], 2);
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            csv(&records()).unwrap(),
+            "year,day,status,iterations,part1,part2,part1_correct,part2_correct,parse_time,part1_time,part2_time,allocs,alloc_bytes,peak_bytes\n\
+             2020,15,ok,2,436,\"1,2\n3\",true,,0.5,0.25,0.125,,,\n\
+             2020,16,timeout,2,,,,,,,,,,\n"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// Maps `items` with `f` on `jobs` threads, `consume` gets the results in the order of `items`
+/// as soon as all the previous ones are done
+pub fn map_in_order<T, R, F, C>(jobs: usize, items: Vec<T>, f: F, mut consume: C)
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+    C: FnMut(R),
+{
+    if jobs <= 1 {
+        items.into_iter().map(f).for_each(consume);
+        return;
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs {
+            let (tx, queue, f) = (tx.clone(), &queue, &f);
+            s.spawn(move || loop {
+                let next = queue.lock().unwrap().next();
+                match next {
+                    Some((i, item)) => tx.send((i, f(item))).unwrap(),
+                    None => break,
+                }
+            });
+        }
+        drop(tx);
+
+        let mut done = BTreeMap::new();
+        let mut next = 0;
+        for (i, result) in rx {
+            done.insert(i, result);
+            while let Some(result) = done.remove(&next) {
+                consume(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_in_order() {
+        for jobs in [1, 3, 16] {
+            let mut results = Vec::new();
+            // the first items are the slowest, so they finish last
+            map_in_order(
+                jobs,
+                (0..10u64).collect(),
+                |i| {
+                    thread::sleep(Duration::from_millis(10 - i));
+                    i * 2
+                },
+                |r| results.push(r),
+            );
+            assert_eq!(results, (0..10).map(|i| i * 2).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Generates the module of a new day, the build script of its year registers it
+
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// The module of a new day, from [`TEMPLATE`]
+pub fn module(day: usize) -> String {
+    TEMPLATE.replace("{{NN}}", &format!("{day:02}"))
+}
+
+/// Creates `src/dayNN.rs` and an empty `input/dayNN.txt` in the crate of a year. Returns the
+/// files created.
+pub fn scaffold(crate_dir: &Path, day: usize) -> Result<Vec<PathBuf>, String> {
+    let src = crate_dir.join(format!("src/day{day:02}.rs"));
+    if src.exists() {
+        return Err(format!("{} already exists", src.display()));
+    }
+    let write = |path: &Path, content: &str| {
+        std::fs::write(path, content).map_err(|e| format!("cannot write {}: {e}", path.display()))
+    };
+    write(&src, &module(day))?;
+    let mut created = vec![src];
+    let input = crate_dir.join(format!("input/day{day:02}.txt"));
+    if !input.exists() {
+        std::fs::create_dir_all(crate_dir.join("input"))
+            .map_err(|e| format!("cannot create the input directory: {e}"))?;
+        write(&input, "")?;
+        created.push(input);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::temp_dir;
+
+    #[test]
+    fn test_scaffold() {
+        let dir = temp_dir("scaffold");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+
+        let created = scaffold(&dir, 5).unwrap();
+        assert_eq!(
+            created,
+            [dir.join("src/day05.rs"), dir.join("input/day05.txt")]
+        );
+        let module = std::fs::read_to_string(dir.join("src/day05.rs")).unwrap();
+        assert!(module.contains("pub struct Day05;"));
+        assert!(!module.contains("{{"));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("input/day05.txt")).unwrap(),
+            ""
+        );
+
+        // an existing day is left untouched, an existing input is kept
+        std::fs::write(dir.join("src/day05.rs"), "solved").unwrap();
+        assert!(scaffold(&dir, 5).is_err());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/day05.rs")).unwrap(),
+            "solved"
+        );
+        std::fs::write(dir.join("input/day06.txt"), "input").unwrap();
+        assert_eq!(scaffold(&dir, 6).unwrap(), [dir.join("src/day06.rs")]);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("input/day06.txt")).unwrap(),
+            "input"
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
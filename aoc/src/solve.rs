@@ -0,0 +1,261 @@
+use crate::alloc::{self, AllocStats};
+use aoc_common::{Example, ParseError};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Day = &'static dyn aoc_common::Day;
+
+/// Runs `f` `iterations` times, returns the average duration and the first result
+pub fn timeit<F, E>(iterations: usize, f: F) -> (f64, E)
+where
+    F: Fn() -> E,
+    E: Sized,
+{
+    let t0 = Instant::now();
+    let res = f();
+    (1..iterations).for_each(|_| drop(f()));
+    (t0.elapsed().as_secs_f64() / iterations as f64, res)
+}
+
+/// Answers of a day and the average durations of its phases
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub t_parse: f64,
+    pub t_p1: f64,
+    pub t_p2: f64,
+    pub p1: String,
+    pub p2: String,
+    /// Allocations of a single run of the three phases, when counted
+    pub allocs: Option<AllocStats>,
+}
+
+/// Why a day has no answers
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    Invalid(ParseError),
+    Panicked(String),
+    Timeout(Duration),
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Invalid(e) => write!(f, "invalid input: {e}"),
+            Failure::Panicked(msg) => write!(f, "panicked: {msg}"),
+            Failure::Timeout(t) => write!(f, "timed out after {}s", t.as_secs_f64()),
+        }
+    }
+}
+
+/// Runs `job` on its own thread, so that a panic or an endless loop does not take the whole
+/// run down. A job that times out keeps running in the background until the runner exits.
+fn isolate<R, F>(name: String, timeout: Option<Duration>, job: F) -> Result<R, Failure>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .name(name)
+        .spawn(move || {
+            // the receiver is gone if the job timed out
+            let _ = tx.send(job());
+        })
+        .expect("failed to spawn a thread");
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => Failure::Timeout(timeout),
+            // the sender was dropped by a panic, reported by `join` below
+            mpsc::RecvTimeoutError::Disconnected => Failure::Panicked(String::new()),
+        }),
+        None => rx.recv().map_err(|_| Failure::Panicked(String::new())),
+    };
+    match received {
+        Err(Failure::Timeout(t)) => Err(Failure::Timeout(t)),
+        received => match handle.join() {
+            Ok(()) => received,
+            Err(payload) => Err(Failure::Panicked(panic_message(payload.as_ref()))),
+        },
+    }
+}
+
+/// Solves a day on its own thread, see [`isolate`]
+pub fn solve(
+    name: String,
+    f: Day,
+    input: String,
+    iterations: usize,
+    timeout: Option<Duration>,
+) -> Result<Solved, Failure> {
+    isolate(name, timeout, move || {
+        alloc::reset();
+        let (t_parse, parsed) = timeit(iterations, || f.parse(&input));
+        let parsed = parsed.map_err(Failure::Invalid)?;
+        let (t_p1, p1) = timeit(iterations, || parsed.part1());
+        let (t_p2, p2) = timeit(iterations, || parsed.part2());
+        let allocs = alloc::ENABLED.then(|| {
+            let AllocStats { count, bytes, peak } = alloc::stats();
+            AllocStats {
+                count: count / iterations as u64,
+                bytes: bytes / iterations as u64,
+                peak,
+            }
+        });
+        Ok(Solved {
+            t_parse,
+            t_p1,
+            t_p2,
+            p1,
+            p2,
+            allocs,
+        })
+    })?
+}
+
+/// Solves the parts of an example that have an expected answer, on its own thread
+pub fn solve_example(
+    name: String,
+    f: Day,
+    example: Example,
+    timeout: Option<Duration>,
+) -> Result<(Option<String>, Option<String>), Failure> {
+    isolate(name, timeout, move || {
+        let parsed = f.parse(example.input).map_err(Failure::Invalid)?;
+        Ok((
+            example.part1.map(|_| parsed.part1()),
+            example.part2.map(|_| parsed.part2()),
+        ))
+    })?
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(msg) => msg.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(msg) => msg.clone(),
+            None => "unknown panic payload".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Solution;
+
+    struct Fine;
+
+    impl Solution for Fine {
+        type Parsed<'a> = usize;
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+            match input.find('!') {
+                Some(i) => Err(ParseError::at_offset(input, i, "unexpected '!'")),
+                None => Ok(input.len()),
+            }
+        }
+
+        fn part1(len: &Self::Parsed<'_>) -> String {
+            len.to_string()
+        }
+
+        fn part2(len: &Self::Parsed<'_>) -> String {
+            (len * 2).to_string()
+        }
+    }
+
+    struct Panics;
+
+    impl Solution for Panics {
+        type Parsed<'a> = ();
+
+        fn parse(_: &str) -> Result<Self::Parsed<'_>, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Parsed<'_>) -> String {
+            "1".to_string()
+        }
+
+        fn part2(_: &Self::Parsed<'_>) -> String {
+            unreachable!("no part 2")
+        }
+    }
+
+    struct Loops;
+
+    impl Solution for Loops {
+        type Parsed<'a> = ();
+
+        fn parse(_: &str) -> Result<Self::Parsed<'_>, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Parsed<'_>) -> String {
+            thread::sleep(Duration::from_secs(5));
+            "1".to_string()
+        }
+
+        fn part2(_: &Self::Parsed<'_>) -> String {
+            "2".to_string()
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let solved = solve("fine".into(), &Fine, "abc".into(), 2, None).unwrap();
+        assert_eq!((solved.p1.as_str(), solved.p2.as_str()), ("3", "6"));
+        let solved = solve(
+            "fine".into(),
+            &Fine,
+            "ab".into(),
+            1,
+            Some(Duration::from_secs(5)),
+        );
+        assert_eq!(solved.unwrap().p2, "4");
+    }
+
+    #[test]
+    fn test_solve_failures() {
+        let failure = solve("panics".into(), &Panics, String::new(), 1, None).unwrap_err();
+        assert_eq!(
+            failure,
+            Failure::Panicked("internal error: entered unreachable code: no part 2".into())
+        );
+        let timeout = Some(Duration::from_millis(50));
+        let failure = solve("panics".into(), &Panics, String::new(), 1, timeout).unwrap_err();
+        assert!(matches!(failure, Failure::Panicked(_)));
+        let failure = solve("loops".into(), &Loops, String::new(), 1, timeout).unwrap_err();
+        assert_eq!(failure, Failure::Timeout(Duration::from_millis(50)));
+        let failure = solve("fine".into(), &Fine, "ab\nc!".into(), 1, None).unwrap_err();
+        assert_eq!(
+            failure,
+            Failure::Invalid(ParseError::new(2, 2, "unexpected '!'"))
+        );
+        assert_eq!(
+            failure.to_string(),
+            "invalid input: line 2, column 2: unexpected '!'"
+        );
+    }
+
+    #[test]
+    fn test_solve_example() {
+        let example = Example {
+            input: "abc",
+            part1: None,
+            part2: Some("6"),
+        };
+        let solved = solve_example("fine".into(), &Fine, example, None);
+        assert_eq!(solved, Ok((None, Some("6".to_string()))));
+        // only the parts with an expected answer are run
+        let example = Example {
+            input: "",
+            part1: Some("1"),
+            part2: None,
+        };
+        let solved = solve_example("panics".into(), &Panics, example, None);
+        assert_eq!(solved, Ok((Some("1".to_string()), None)));
+    }
+}
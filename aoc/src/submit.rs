@@ -0,0 +1,425 @@
+use crate::client::Client;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Where `aoc submit` records every guess, kept out of git
+pub const GUESSES_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/guesses.toml");
+/// Format of the dates in the log
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// What the website made of an answer
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answered too soon after the previous guess, nothing was checked
+    RateLimited,
+    /// The part is locked or already solved
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "wrong, too high",
+            Verdict::TooLow => "wrong, too low",
+            Verdict::RateLimited => "rate limited",
+            Verdict::WrongLevel => "not the right level, is the part already solved?",
+            Verdict::Unknown => "unknown response",
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long to wait before the next guess, when rate limited
+    pub wait: Option<Duration>,
+}
+
+/// Parses the `<article>` of the page answering a guess
+pub fn parse_response(html: &str) -> Response {
+    let verdict = if html.contains("That's the right answer") {
+        Verdict::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if html.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if html.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+    Response {
+        verdict,
+        wait: parse_wait(html),
+    }
+}
+
+/// Parses "You have 1m 5s left to wait"
+fn parse_wait(html: &str) -> Option<Duration> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+    html[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let n: u64 = n.parse().ok()?;
+            match unit {
+                "h" => Some(n * 3600),
+                "m" => Some(n * 60),
+                "s" => Some(n),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Guess {
+    pub year: u16,
+    pub day: usize,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub date: String,
+    /// When the website takes guesses again, when it asked to wait
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct GuessLog {
+    #[serde(default)]
+    pub guesses: Vec<Guess>,
+}
+
+impl GuessLog {
+    /// Loads the log, a missing file being an empty log
+    pub fn load(path: &str) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                toml::from_str(&content).map_err(|e| format!("invalid guess log {path}: {e}"))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("cannot read {path}: {e}")),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let content = toml::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, content).map_err(|e| format!("cannot write {path}: {e}"))
+    }
+
+    /// Fails if the previous guesses tell that submitting `answer` at `now` is pointless: it was
+    /// found wrong, is out of the too high / too low bounds, the part is already solved, or the
+    /// website asked to wait past `now`
+    pub fn check(
+        &self,
+        year: u16,
+        day: usize,
+        part: u8,
+        answer: &str,
+        now: NaiveDateTime,
+    ) -> Result<(), String> {
+        let value = answer.parse::<i128>().ok();
+        for guess in self
+            .guesses
+            .iter()
+            .filter(|g| (g.year, g.day, g.part) == (year, day, part))
+        {
+            let bound = value.zip(guess.answer.parse::<i128>().ok());
+            let retry_after = guess
+                .retry_after
+                .as_deref()
+                .and_then(|date| NaiveDateTime::parse_from_str(date, DATE_FORMAT).ok());
+            if let Some(retry_after) = retry_after.filter(|&t| now < t) {
+                return Err(format!(
+                    "rate limited, retry in {}s",
+                    (retry_after - now).num_seconds().max(1)
+                ));
+            }
+            match guess.verdict {
+                Verdict::Correct => {
+                    return Err(format!(
+                        "already solved on {} with {}",
+                        guess.date, guess.answer
+                    ))
+                }
+                v if v.is_wrong() && guess.answer == answer => {
+                    return Err(format!("{answer} was already found {v} on {}", guess.date))
+                }
+                Verdict::TooHigh if bound.is_some_and(|(a, g)| a >= g) => {
+                    return Err(format!(
+                        "{answer} is not lower than {}, too high",
+                        guess.answer
+                    ))
+                }
+                Verdict::TooLow if bound.is_some_and(|(a, g)| a <= g) => {
+                    return Err(format!(
+                        "{answer} is not higher than {}, too low",
+                        guess.answer
+                    ))
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Submits `answer` at `now` unless the log tells it is pointless, and records the guess in the
+/// log. The client is only created when needed.
+pub fn submit<F>(
+    client: F,
+    log: &mut GuessLog,
+    year: u16,
+    day: usize,
+    part: u8,
+    answer: &str,
+    now: NaiveDateTime,
+) -> Result<Response, String>
+where
+    F: FnOnce() -> Result<Client, String>,
+{
+    log.check(year, day, part, answer, now)?;
+    let html = client()?.post(
+        &format!("/{year}/day/{day}/answer"),
+        &[("level", &part.to_string()), ("answer", answer)],
+    )?;
+    let response = parse_response(&html);
+    log.guesses.push(Guess {
+        year,
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict: response.verdict,
+        date: now.format(DATE_FORMAT).to_string(),
+        retry_after: response.wait.map(|wait| {
+            let wait = chrono::Duration::from_std(wait).unwrap_or(chrono::Duration::MAX);
+            now.checked_add_signed(wait)
+                .unwrap_or(NaiveDateTime::MAX)
+                .format(DATE_FORMAT)
+                .to_string()
+        }),
+    });
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::StubServer;
+    use std::sync::atomic::Ordering;
+
+    fn article(text: &str) -> String {
+        format!("<html><body><main><article><p>{text}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Verdict::Correct,
+                None,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, ...",
+                Verdict::Wrong,
+                None,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+                None,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+                None,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait.",
+                Verdict::RateLimited,
+                Some(65),
+            ),
+            (
+                "You gave an answer too recently.  You have 42s left to wait.",
+                Verdict::RateLimited,
+                Some(42),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::WrongLevel,
+                None,
+            ),
+            (
+                "Please don't repeatedly request this endpoint",
+                Verdict::Unknown,
+                None,
+            ),
+        ];
+        for (text, verdict, wait) in cases {
+            assert_eq!(
+                parse_response(&article(text)),
+                Response {
+                    verdict,
+                    wait: wait.map(Duration::from_secs)
+                },
+                "{text}"
+            );
+        }
+    }
+
+    fn guess(part: u8, answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            year: 2023,
+            day: 10,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            date: "2023-12-10 06:00:00".to_string(),
+            retry_after: None,
+        }
+    }
+
+    fn date(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(date, DATE_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn test_check() {
+        let log = GuessLog {
+            guesses: vec![
+                guess(1, "100", Verdict::TooHigh),
+                guess(1, "10", Verdict::TooLow),
+                guess(1, "50", Verdict::Wrong),
+                guess(1, "60", Verdict::RateLimited),
+                guess(2, "abc", Verdict::Wrong),
+            ],
+        };
+        let check = |part, answer| log.check(2023, 10, part, answer, date("2023-12-10 07:00:00"));
+        assert!(check(1, "100").is_err());
+        assert!(check(1, "150").is_err());
+        assert!(check(1, "5").is_err());
+        assert!(check(1, "50").is_err());
+        assert!(check(1, "60").is_ok());
+        assert!(check(1, "99").is_ok());
+        assert!(check(2, "abc").is_err());
+        assert!(check(2, "100").is_ok());
+        assert!(log
+            .check(2023, 11, 1, "100", date("2023-12-10 07:00:00"))
+            .is_ok());
+
+        let log = GuessLog {
+            guesses: vec![guess(1, "42", Verdict::Correct)],
+        };
+        let check = |answer| log.check(2023, 10, 1, answer, date("2023-12-10 07:00:00"));
+        assert!(check("42").is_err());
+        assert!(check("43").is_err());
+
+        // nothing is sent until the wait asked by the website is over
+        let log = GuessLog {
+            guesses: vec![Guess {
+                retry_after: Some("2023-12-10 06:01:05".to_string()),
+                ..guess(1, "60", Verdict::RateLimited)
+            }],
+        };
+        let check = |part, now| log.check(2023, 10, part, "61", date(now));
+        assert!(check(1, "2023-12-10 06:00:30").is_err());
+        assert!(check(1, "2023-12-10 06:01:05").is_ok());
+        assert!(check(2, "2023-12-10 06:00:30").is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        // the stand-in answers according to the submitted answer
+        let server = StubServer::start(|request| {
+            let mut form = String::new();
+            request.as_reader().read_to_string(&mut form).unwrap();
+            let text = match form.as_str() {
+                "level=1&answer=42" => "That's the right answer!",
+                "level=1&answer=100" => "That's not the right answer; your answer is too high.",
+                "level=1&answer=1" => "That's not the right answer; your answer is too low.",
+                "level=1&answer=50" => "That's not the right answer.",
+                "level=1&answer=60" => {
+                    "You gave an answer too recently.  You have 30s left to wait."
+                }
+                "level=2&answer=7" => "You don't seem to be solving the right level.",
+                _ => "?",
+            };
+            (200, article(text))
+        });
+        let client = || Ok(Client::new(&server.url, "abc", None));
+        let mut log = GuessLog::default();
+        let mut submit =
+            |part, answer, now| submit(client, &mut log, 2023, 10, part, answer, date(now));
+
+        let verdict = |r: Result<Response, String>| r.unwrap().verdict;
+        assert_eq!(
+            verdict(submit(1, "100", "2023-12-10 06:00:00")),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            verdict(submit(1, "1", "2023-12-10 06:01:00")),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            verdict(submit(1, "50", "2023-12-10 06:02:00")),
+            Verdict::Wrong
+        );
+        let response = submit(1, "60", "2023-12-10 06:03:00").unwrap();
+        assert_eq!(response.wait, Some(Duration::from_secs(30)));
+        assert_eq!(
+            verdict(submit(2, "7", "2023-12-10 06:03:00")),
+            Verdict::WrongLevel
+        );
+        assert_eq!(server.requests.load(Ordering::SeqCst), 5);
+
+        // known wrong answers are never sent again, nor anything before the wait is over
+        for answer in ["100", "150", "1", "50"] {
+            assert!(submit(1, answer, "2023-12-10 06:04:00").is_err());
+        }
+        assert!(submit(1, "42", "2023-12-10 06:03:29").is_err());
+        assert_eq!(server.requests.load(Ordering::SeqCst), 5);
+
+        assert_eq!(
+            verdict(submit(1, "60", "2023-12-10 06:03:30")),
+            Verdict::RateLimited
+        );
+        assert_eq!(
+            verdict(submit(1, "42", "2023-12-10 06:04:00")),
+            Verdict::Correct
+        );
+        assert!(submit(1, "42", "2023-12-10 06:05:00").is_err());
+        assert_eq!(server.requests.load(Ordering::SeqCst), 7);
+        assert_eq!(log.guesses.len(), 7);
+        assert_eq!(
+            log.guesses[3].retry_after.as_deref(),
+            Some("2023-12-10 06:03:30")
+        );
+        assert_eq!(log.guesses[6].answer, "42");
+    }
+}
@@ -0,0 +1,133 @@
+//! `aoc watch`: re-runs a day whenever its source or input changes
+
+use crate::answers::Answers;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Files polled for changes, through their modification time
+pub struct Watched {
+    paths: Vec<PathBuf>,
+    stamps: Vec<Option<SystemTime>>,
+}
+
+impl Watched {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let stamps = paths.iter().map(modified).collect();
+        Self { paths, stamps }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    /// Whether a file changed, appeared or disappeared since the last call
+    pub fn changed(&mut self) -> bool {
+        let stamps: Vec<_> = self.paths.iter().map(modified).collect();
+        let changed = stamps != self.stamps;
+        self.stamps = stamps;
+        changed
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// The answers of the only day in the output of `aoc run --format json`
+pub fn parse_answers(json: &str) -> Result<Answers, String> {
+    let records: Vec<serde_json::Value> =
+        serde_json::from_str(json).map_err(|e| format!("invalid output of the runner: {e}"))?;
+    let [record] = records.as_slice() else {
+        return Err(format!("expected a single day, got {}", records.len()));
+    };
+    let answer = |part: &str| record[part].as_str().map(String::from);
+    Ok(Answers {
+        part1: answer("part1"),
+        part2: answer("part2"),
+    })
+}
+
+/// A line per part, telling how its answer changed since the previous run
+pub fn diff(previous: Option<&Answers>, current: &Answers) -> Vec<String> {
+    let show = |answer: Option<&String>| answer.map_or("none".to_string(), |a| a.clone());
+    [
+        (1, previous.map(|p| &p.part1), &current.part1),
+        (2, previous.map(|p| &p.part2), &current.part2),
+    ]
+    .into_iter()
+    .map(|(part, before, after)| match before {
+        Some(before) if before != after => format!(
+            "part {part}: {} -> {}",
+            show(before.as_ref()),
+            show(after.as_ref())
+        ),
+        Some(_) => format!("part {part}: {} (unchanged)", show(after.as_ref())),
+        None => format!("part {part}: {}", show(after.as_ref())),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::temp_dir;
+
+    fn answers(part1: Option<&str>, part2: Option<&str>) -> Answers {
+        Answers {
+            part1: part1.map(String::from),
+            part2: part2.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_watched() {
+        let dir = temp_dir("watch");
+        let (src, input) = (dir.join("day05.rs"), dir.join("day05.txt"));
+        std::fs::write(&src, "fn main() {}").unwrap();
+        let mut watched = Watched::new(vec![src.clone(), input.clone()]);
+        assert!(!watched.changed());
+
+        std::fs::write(&input, "1 2 3").unwrap();
+        assert!(watched.changed());
+        assert!(!watched.changed());
+        let later = SystemTime::now() + std::time::Duration::from_secs(10);
+        std::fs::File::options()
+            .write(true)
+            .open(&src)
+            .and_then(|f| f.set_modified(later))
+            .unwrap();
+        assert!(watched.changed());
+        std::fs::remove_file(&src).unwrap();
+        assert!(watched.changed());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let json = r#"[{"year": 2024, "day": 5, "status": "ok", "part1": "143", "part2": "123"}]"#;
+        let parsed = parse_answers(json).unwrap();
+        assert_eq!(parsed.part1.as_deref(), Some("143"));
+        assert_eq!(parsed.part2.as_deref(), Some("123"));
+        let failed = parse_answers(r#"[{"status": "failed", "part1": null}]"#).unwrap();
+        assert_eq!((failed.part1, failed.part2), (None, None));
+        assert!(parse_answers("[]").is_err());
+        assert!(parse_answers("Running the solutions").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = answers(Some("143"), None);
+        assert_eq!(
+            diff(None, &before),
+            ["part 1: 143", "part 2: none"].map(String::from)
+        );
+        assert_eq!(
+            diff(Some(&before), &answers(Some("143"), Some("123"))),
+            ["part 1: 143 (unchanged)", "part 2: none -> 123"].map(String::from)
+        );
+        assert_eq!(
+            diff(Some(&before), &answers(None, None)),
+            ["part 1: 143 -> none", "part 2: none (unchanged)"].map(String::from)
+        );
+    }
+}
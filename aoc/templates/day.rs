@@ -0,0 +1,54 @@
+use aoc_common::{parse, Example, ParseError, Solution};
+
+/// The answer of a part that is not solved yet
+const NOT_IMPLEMENTED: &str = "not implemented";
+
+// the first example of the puzzle, its answers go in `EXAMPLES`
+const TEST_INPUT: &str = "example\n";
+
+pub struct Day{{NN}};
+
+impl Solution for Day{{NN}} {
+    type Parsed<'a> = Vec<&'a str>;
+
+    const EXAMPLES: &'static [Example] = &[Example {
+        input: TEST_INPUT,
+        part1: Some(NOT_IMPLEMENTED),
+        part2: Some(NOT_IMPLEMENTED),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+        parse::at_least(input, input.lines().collect(), 1, "line")
+    }
+
+    fn part1(_lines: &Self::Parsed<'_>) -> String {
+        NOT_IMPLEMENTED.to_string()
+    }
+
+    fn part2(_lines: &Self::Parsed<'_>) -> String {
+        NOT_IMPLEMENTED.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        for example in Day{{NN}}::EXAMPLES {
+            let parsed = Day{{NN}}::parse(example.input).unwrap();
+            if let Some(expected) = example.part1 {
+                assert_eq!(Day{{NN}}::part1(&parsed), expected);
+            }
+            if let Some(expected) = example.part2 {
+                assert_eq!(Day{{NN}}::part2(&parsed), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(Day{{NN}}::parse("").is_err());
+    }
+}
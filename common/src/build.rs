@@ -0,0 +1,115 @@
+//! Registration of the days of a year, for its build script
+
+use std::fmt::Write;
+use std::path::Path;
+
+/// Days of a puzzle calendar
+pub const DAYS: usize = 25;
+
+/// What every year offers the runner, around its `SOLUTIONS`
+const YEAR_ITEMS: &str = r#"
+pub type Day = &'static dyn aoc_common::Day;
+
+/// The solution of `day`, `None` if it is not implemented
+pub fn solution(day: usize) -> Option<Day> {
+    *SOLUTIONS.get(day.checked_sub(1)?)?
+}
+
+/// Solves both parts of `day` on `input`, `None` if the day is not implemented
+pub fn solve(day: usize, input: &str) -> Option<Result<(String, String), aoc_common::ParseError>> {
+    solution(day).map(|f| f.answers(input))
+}
+
+/// Where the puzzle inputs are stored, as `dayNN.txt`
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+/// Known good answers, see the `aoc` runner's `--check`
+pub const ANSWERS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.toml");
+"#;
+
+/// The public `mod` of every day, the `SOLUTIONS` of the year, indexed by day: `SOLUTIONS[day - 1]`
+/// is `None` for the days without a `dayNN.rs`, and the `YEAR_ITEMS` built on them
+pub fn days_module(src_dir: &Path, days: &[usize]) -> String {
+    let mut module = String::new();
+    for day in days {
+        let path = src_dir.join(format!("day{day:02}.rs"));
+        // the generated file is not in `src`, `mod` would not find the day on its own
+        writeln!(
+            module,
+            "#[path = {:?}]\npub mod day{day:02};",
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+    writeln!(module, "\npub const SOLUTIONS: [Option<Day>; {DAYS}] = [").unwrap();
+    for day in 1..=DAYS {
+        match days.contains(&day) {
+            true => writeln!(module, "    Some(&day{day:02}::Day{day:02}),").unwrap(),
+            false => writeln!(module, "    None,").unwrap(),
+        }
+    }
+    module.push_str("];\n");
+    module.push_str(YEAR_ITEMS);
+    module
+}
+
+/// The days with a `dayNN.rs` in `src_dir`, in order
+pub fn find_days(src_dir: &Path) -> std::io::Result<Vec<usize>> {
+    let mut days = Vec::new();
+    for entry in std::fs::read_dir(src_dir)? {
+        let name = entry?.file_name();
+        let day = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("day")?.strip_suffix(".rs"))
+            .filter(|n| n.len() == 2)
+            .and_then(|n| n.parse().ok())
+            .filter(|day| (1..=DAYS).contains(day));
+        days.extend(day);
+    }
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// Writes the days module of the crate being built to `$OUT_DIR/days.rs`, for its `lib.rs` to
+/// `include!`. Meant to be the whole build script of a year.
+pub fn register_days() {
+    let src_dir = Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    // days added or removed change the directory
+    println!("cargo:rerun-if-changed={}", src_dir.display());
+    let days = find_days(&src_dir).expect("cannot list the days in src/");
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("days.rs");
+    std::fs::write(out, days_module(&src_dir, &days)).expect("cannot write days.rs");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_days() {
+        let dir = std::env::temp_dir().join(format!("aoc-common-days-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("bin")).unwrap();
+        for file in [
+            "day11.rs",
+            "day02.rs",
+            "lib.rs",
+            "day3.rs",
+            "day26.rs",
+            "day02.txt",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        assert_eq!(find_days(&dir).unwrap(), [2, 11]);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_days_module() {
+        let module = days_module(Path::new("/y/src"), &[2, 11]);
+        assert!(module.starts_with("#[path = \"/y/src/day02.rs\"]\npub mod day02;\n"));
+        assert!(module.contains("#[path = \"/y/src/day11.rs\"]\npub mod day11;\n"));
+        assert!(module.contains("= [\n    None,\n    Some(&day02::Day02),\n    None,\n"));
+        assert_eq!(module.matches("None,").count(), DAYS - 2);
+        assert!(module.contains("\npub fn solution(day: usize) -> Option<Day> {\n"));
+    }
+}
@@ -0,0 +1,145 @@
+//! Differential testing: checks the solution of a day against its slow but obvious
+//! [`Solution::reference`], on the examples and on generated inputs
+
+use crate::{Rng, Solution};
+use std::fmt;
+use std::ops::Range;
+
+/// The first input on which a solution and its reference disagree
+pub struct Disagreement {
+    /// `None` for an example
+    pub seed: Option<u64>,
+    pub input: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.seed {
+            Some(seed) => write!(f, "input of seed {seed}")?,
+            None => write!(f, "example")?,
+        }
+        write!(f, ": {}\n{}", self.reason, self.input)
+    }
+}
+
+// so that `unwrap` shows the input as it is
+impl fmt::Debug for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Compares the solution and its reference on the parts of `input` in `parts`
+pub fn compare<S: Solution>(input: &str, parts: [bool; 2]) -> Result<(), String> {
+    let parsed = S::parse(input).map_err(|e| format!("invalid input: {e}"))?;
+    for (part, checked) in (1..).zip(parts) {
+        if !checked {
+            continue;
+        }
+        let Some(expected) = S::reference(input, part) else {
+            continue;
+        };
+        let answer = match part {
+            1 => S::part1(&parsed),
+            _ => S::part2(&parsed),
+        };
+        if answer != expected {
+            return Err(format!("part {part} is {answer}, the reference {expected}"));
+        }
+    }
+    Ok(())
+}
+
+/// Compares the solution and its reference on the parts of the examples that have an answer,
+/// then on an input of `size` generated for each of `seeds`, if the day has a generator
+pub fn check<S: Solution>(size: usize, seeds: Range<u64>) -> Result<(), Disagreement> {
+    for example in S::EXAMPLES {
+        let parts = [example.part1.is_some(), example.part2.is_some()];
+        compare::<S>(example.input, parts).map_err(|reason| Disagreement {
+            seed: None,
+            input: example.input.to_string(),
+            reason,
+        })?;
+    }
+    for seed in seeds {
+        let Some(input) = S::generate(size, &mut Rng::new(seed)) else {
+            break;
+        };
+        compare::<S>(&input, [true, true]).map_err(|reason| Disagreement {
+            seed: Some(seed),
+            input,
+            reason,
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, Example, ParseError};
+
+    /// Sums numbers, wrongly once they no longer fit a byte
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed<'a> = Vec<u32>;
+
+        const EXAMPLES: &'static [Example] = &[Example {
+            input: "1 2 3",
+            part1: Some("6"),
+            part2: None,
+        }];
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+            input
+                .split_whitespace()
+                .map(|n| parse::token(input, n))
+                .collect()
+        }
+
+        fn part1(numbers: &Self::Parsed<'_>) -> String {
+            numbers.iter().fold(0u8, |s, &n| s.wrapping_add(n as u8)).to_string()
+        }
+
+        fn part2(_: &Self::Parsed<'_>) -> String {
+            unreachable!("no example has a part 2")
+        }
+
+        fn generate(size: usize, rng: &mut Rng) -> Option<String> {
+            let numbers: Vec<_> = (0..size).map(|_| rng.range(0..=99).to_string()).collect();
+            Some(numbers.join(" "))
+        }
+
+        fn reference(input: &str, part: u8) -> Option<String> {
+            let sum = input.split_whitespace().map(|n| n.parse::<u32>().unwrap());
+            (part == 1).then(|| sum.sum::<u32>().to_string())
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check::<Sum>(1, 0..0).is_ok());
+        assert_eq!(compare::<Sum>("200 100", [false, false]), Ok(()));
+        assert_eq!(
+            compare::<Sum>("200 100", [true, false]),
+            Err("part 1 is 44, the reference 300".to_string())
+        );
+        assert!(compare::<Sum>("1 x", [true, false])
+            .unwrap_err()
+            .starts_with("invalid input"));
+
+        let disagreement = check::<Sum>(10, 0..100).unwrap_err();
+        let seed = disagreement.seed.unwrap();
+        assert_eq!(
+            disagreement.input,
+            Sum::generate(10, &mut Rng::new(seed)).unwrap()
+        );
+        // the seeds before agree
+        assert!(check::<Sum>(10, 0..seed).is_ok());
+        assert!(disagreement
+            .to_string()
+            .starts_with(&format!("input of seed {seed}: part 1 is ")));
+    }
+}
@@ -0,0 +1,137 @@
+//! Code shared by every year: the [`Solution`] trait implemented by each day
+
+pub mod build;
+pub mod differential;
+pub mod ocr;
+pub mod parse;
+pub mod random;
+
+pub use parse::ParseError;
+pub use random::Rng;
+
+/// A day's puzzle, split in phases so the runner can time each one on its own
+pub trait Solution {
+    /// The puzzle input once parsed, it may borrow from the raw input
+    type Parsed<'a>;
+
+    /// Fails on a malformed input, the parts may then assume the parsed input is well formed
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError>;
+    fn part1(parsed: &Self::Parsed<'_>) -> String;
+    fn part2(parsed: &Self::Parsed<'_>) -> String;
+
+    /// The puzzle's examples, run by `aoc run --examples`
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// A random but valid input, `size` scaling it in the way that fits the day (lines, side of a
+    /// grid, cards...). `None` for the days without a generator, see `aoc gen`.
+    fn generate(_size: usize, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+
+    /// The answer of `part` (1 or 2) found the straightforward way, without the assumptions of
+    /// [`Solution::part1`] and [`Solution::part2`], on an input small enough and accepted by
+    /// [`Solution::parse`]. `None` for the days without a reference solver, see
+    /// [`differential`].
+    fn reference(_input: &str, _part: u8) -> Option<String> {
+        None
+    }
+
+    /// Parses the input and solves both parts
+    fn solve(input: &str) -> Result<(String, String), ParseError> {
+        let parsed = Self::parse(input)?;
+        Ok((Self::part1(&parsed), Self::part2(&parsed)))
+    }
+}
+
+/// An example input of a puzzle, with the answers its text gives (puzzles often have different
+/// examples for each part)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub input: &'static str,
+    pub part1: Option<&'static str>,
+    pub part2: Option<&'static str>,
+}
+
+/// Object safe [`Solution`], so that the days of a year fit in one array
+pub trait Day: Sync {
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+    fn examples(&self) -> &'static [Example];
+    /// A random input, the same for the same `seed`, like [`Solution::generate`]
+    fn generate(&self, size: usize, seed: u64) -> Option<String>;
+
+    /// Parses the input and solves both parts, like [`Solution::solve`]
+    fn answers(&self, input: &str) -> Result<(String, String), ParseError> {
+        let parsed = self.parse(input)?;
+        Ok((parsed.part1(), parsed.part2()))
+    }
+}
+
+/// A parsed input, ready to be solved
+pub trait Parsed {
+    fn part1(&self) -> String;
+    fn part2(&self) -> String;
+}
+
+struct ParsedInput<'a, S: Solution>(S::Parsed<'a>);
+
+impl<S: Solution> Parsed for ParsedInput<'_, S> {
+    fn part1(&self) -> String {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> String {
+        S::part2(&self.0)
+    }
+}
+
+impl<S: Solution + Sync + 'static> Day for S {
+    fn parse<'a>(&self, input: &'a str) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        let parsed = <S as Solution>::parse(input)?;
+        Ok(Box::new(ParsedInput::<S>(parsed)))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        S::EXAMPLES
+    }
+
+    fn generate(&self, size: usize, seed: u64) -> Option<String> {
+        S::generate(size, &mut Rng::new(seed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed<'a> = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Parsed<'_>, ParseError> {
+            input
+                .split_whitespace()
+                .map(|n| parse::token(input, n))
+                .collect()
+        }
+
+        fn part1(numbers: &Self::Parsed<'_>) -> String {
+            numbers.iter().sum::<u32>().to_string()
+        }
+
+        fn part2(numbers: &Self::Parsed<'_>) -> String {
+            numbers.iter().max().unwrap().to_string()
+        }
+    }
+
+    #[test]
+    fn test_day() {
+        let day: &dyn Day = &Sum;
+        assert_eq!(day.answers("1 5 2"), Ok(("8".to_string(), "5".to_string())));
+        assert_eq!(day.answers("1 5 2"), <Sum as Solution>::solve("1 5 2"));
+        let error = day.answers("1 5\n2 x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert!(day.examples().is_empty());
+        assert_eq!(day.generate(10, 0), None);
+    }
+}
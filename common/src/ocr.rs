@@ -0,0 +1,59 @@
+//! Reads the codes some puzzles draw, in capital letters 4 pixels wide and 6 high with a blank
+//! column between them
+
+/// The known letters, their rows one after the other
+const LETTERS: [(char, &str); 16] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// The letters of an image `width` pixels wide, `lit(x, y)` telling if a pixel is on. A letter
+/// that is not known is read as `?`.
+pub fn read(width: usize, lit: impl Fn(usize, usize) -> bool) -> String {
+    (0..width.div_ceil(5))
+        .map(|letter| {
+            let glyph: String = (0..6)
+                .flat_map(|y| (0..4).map(move |x| (letter * 5 + x, y)))
+                .map(|(x, y)| if lit(x, y) { '#' } else { '.' })
+                .collect();
+            LETTERS
+                .iter()
+                .find(|(_, pixels)| *pixels == glyph)
+                .map_or('?', |(c, _)| *c)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let image = [
+            "#..#.###..",
+            "#..#.#..#.",
+            "####.###..",
+            "#..#.#..#.",
+            "#..#.#..#.",
+            "#..#.###..",
+        ];
+        let lit = |x: usize, y: usize| image[y].as_bytes().get(x) == Some(&b'#');
+        assert_eq!(read(10, lit), "HB");
+        assert_eq!(read(14, |x, y| x >= 10 || lit(x, y)), "HB?");
+    }
+}
@@ -0,0 +1,187 @@
+//! Errors of the parse step, located in the input
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Why an input could not be parsed, and where
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// From 1
+    pub line: usize,
+    /// From 1, in characters
+    pub column: usize,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, reason: impl Display) -> Self {
+        Self {
+            line,
+            column,
+            reason: reason.to_string(),
+        }
+    }
+
+    /// An error on `token`, located from its address: it must be a slice of `input`, any other
+    /// token (like a `""` literal) is located at the end of the input
+    pub fn at(input: &str, token: &str, reason: impl Display) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + token.len() <= input.len())
+            .unwrap_or(input.len());
+        Self::at_offset(input, offset, reason)
+    }
+
+    /// An error at byte `offset` of `input`
+    pub fn at_offset(input: &str, offset: usize, reason: impl Display) -> Self {
+        let before = input.as_bytes().get(..offset).unwrap_or(input.as_bytes());
+        let line_start = before
+            .iter()
+            .rposition(|&c| c == b'\n')
+            .map_or(0, |i| i + 1);
+        let line = before.iter().filter(|&&c| c == b'\n').count() + 1;
+        // counts characters, not bytes, as a text editor would
+        let column = String::from_utf8_lossy(&before[line_start..])
+            .chars()
+            .count()
+            + 1;
+        Self::new(line, column, reason)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `token`, a slice of `input`, reporting where it is when it is invalid
+pub fn token<T>(input: &str, token: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|e| ParseError::at(input, token, format!("{token:?}: {e}")))
+}
+
+/// The next item of `items`, or an error at the end of `input` naming what is missing
+pub fn next<'a>(
+    input: &str,
+    items: &mut impl Iterator<Item = &'a str>,
+    what: &str,
+) -> Result<&'a str, ParseError> {
+    items
+        .next()
+        .ok_or_else(|| ParseError::at(input, "", format!("missing {what}")))
+}
+
+/// `items` if there are at least `min` of them, so that the parts can rely on it, or an error at
+/// the end of `input` naming what they are
+pub fn at_least<T>(
+    input: &str,
+    items: Vec<T>,
+    min: usize,
+    what: &str,
+) -> Result<Vec<T>, ParseError> {
+    if items.len() < min {
+        let reason = format!("expected at least {min} {what}, found {}", items.len());
+        return Err(ParseError::at(input, "", reason));
+    }
+    Ok(items)
+}
+
+/// The rows of a rectangular grid, made only of the characters in `allowed`
+pub fn grid<'a>(input: &'a str, allowed: &str) -> Result<Vec<&'a str>, ParseError> {
+    let rows: Vec<&str> = input.lines().collect();
+    let width = rows.first().map_or(0, |row| row.len());
+    if width == 0 {
+        return Err(ParseError::at(input, input, "expected a grid"));
+    }
+    for row in &rows {
+        if let Some(i) = row.find(|c| !allowed.contains(c)) {
+            let reason = format!("expected one of {allowed:?}");
+            return Err(ParseError::at(input, &row[i..], reason));
+        }
+        if row.len() != width {
+            let reason = format!("expected {width} columns, like the first row");
+            return Err(ParseError::at(input, row, reason));
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "12 34\n56 x8\né9 !";
+        let token = |s: &str| &input[input.find(s).unwrap()..][..s.len()];
+        assert_eq!(
+            ParseError::at(input, token("x8"), "not a number"),
+            ParseError::new(2, 4, "not a number")
+        );
+        assert_eq!(ParseError::at(input, token("12"), "").column, 1);
+        // in characters
+        assert_eq!(ParseError::at(input, token("!"), "").column, 4);
+        // not a slice of the input
+        assert_eq!(ParseError::at(input, "", "missing").line, 3);
+        assert_eq!(ParseError::at(input, "", "missing").column, 5);
+        assert_eq!(
+            ParseError::at(input, token("x8"), "not a number").to_string(),
+            "line 2, column 4: not a number"
+        );
+    }
+
+    #[test]
+    fn test_token() {
+        let input = "12 -3 x";
+        let mut words = input.split(' ');
+        assert_eq!(
+            token::<u32>(input, next(input, &mut words, "a").unwrap()),
+            Ok(12)
+        );
+        let error = token::<u32>(input, next(input, &mut words, "b").unwrap()).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 4));
+        assert_eq!(error.reason, "\"-3\": invalid digit found in string");
+        assert!(token::<u32>(input, words.next().unwrap()).is_err());
+        assert_eq!(
+            next(input, &mut words, "the third number").unwrap_err(),
+            ParseError::new(1, 8, "missing the third number")
+        );
+    }
+
+    #[test]
+    fn test_at_least() {
+        assert_eq!(at_least("1\n2\n", vec![1, 2], 2, "numbers"), Ok(vec![1, 2]));
+        assert_eq!(
+            at_least("1\n", vec![1], 2, "numbers"),
+            Err(ParseError::new(
+                2,
+                1,
+                "expected at least 2 numbers, found 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        assert_eq!(grid("#.\n..\n", "#."), Ok(vec!["#.", ".."]));
+        assert_eq!(
+            grid("#.\n.x\n", "#."),
+            Err(ParseError::new(2, 2, "expected one of \"#.\""))
+        );
+        let error = grid("#.\n.\n", "#.").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(grid("", "#.").is_err());
+    }
+}